@@ -1,13 +1,137 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Lit, Meta, NestedMeta};
 
-#[proc_macro_derive(BindingTypeView)]
+/// The options of the `#[action(...)]` attribute of a variant.
+#[derive(Default)]
+struct ActionAttribute {
+    /// The category and order described by `category = "..."` and `order = N`, if any.
+    metadata: Option<(String, i32)>,
+    /// Whether the variant wraps another `BindingTypeView`, described by `nested`.
+    nested: bool,
+}
+
+/// Parses the `#[action(category = "...", order = N, nested)]` attribute of a variant, if any.
+fn action_attribute(attrs: &[Attribute]) -> syn::Result<ActionAttribute> {
+    let attr = match attrs.iter().find(|attr| attr.path.is_ident("action")) {
+        Some(attr) => attr,
+        None => return Ok(ActionAttribute::default()),
+    };
+    let list = match attr.parse_meta()? {
+        Meta::List(list) => list,
+        meta => {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "expected #[action(category = \"...\", order = N, nested)]",
+            ))
+        }
+    };
+    let mut attribute = ActionAttribute::default();
+    let mut category = None;
+    let mut order = None;
+    for nested in list.nested.iter() {
+        match nested {
+            NestedMeta::Meta(Meta::NameValue(pair)) => match (&pair.lit, pair.path.get_ident()) {
+                (Lit::Str(value), Some(ident)) if ident == "category" => category = Some(value.value()),
+                (Lit::Int(value), Some(ident)) if ident == "order" => order = Some(value.base10_parse()?),
+                _ => return Err(syn::Error::new_spanned(pair, "unknown action attribute")),
+            },
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("nested") => attribute.nested = true,
+            _ => return Err(syn::Error::new_spanned(nested, "unknown action attribute")),
+        }
+    }
+    if category.is_some() || order.is_some() {
+        attribute.metadata = Some((category.unwrap_or_default(), order.unwrap_or_default()));
+    }
+    Ok(attribute)
+}
+
+/// Implements `BindingTypeView`. For enums, a dense action index is derived too, in declaration order: unit variants
+/// take one index each, and single-field variants tagged with `#[action(nested)]` take the indices of the
+/// `BindingTypeView` they wrap (as generated by the `input!` macro). Other single-field variants have no index.
+///
+/// Variants can be tagged with `#[action(category = "Movement", order = 1)]` to describe them in a settings screen.
+/// Nested variants without a category use the metadata of the wrapped action.
+#[proc_macro_derive(BindingTypeView, attributes(action))]
 pub fn derive_binding_type_view(_item: TokenStream) -> TokenStream {
-    let struct_name = &parse_macro_input!(_item as DeriveInput).ident;
+    let input = parse_macro_input!(_item as DeriveInput);
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let variants = match input.data {
+        Data::Enum(ref data) => &data.variants,
+        _ => {
+            return TokenStream::from(quote::quote! {
+                impl #impl_generics BindingTypeView for #struct_name #ty_generics #where_clause {}
+            })
+        }
+    };
+    let mut counts = Vec::with_capacity(variants.len());
+    let mut arms = Vec::with_capacity(variants.len());
+    let mut metadata_arms = Vec::with_capacity(variants.len());
+    for variant in variants.iter() {
+        let ident = &variant.ident;
+        let offset = quote::quote! { 0 #(+ #counts)* };
+        let attribute = match action_attribute(&variant.attrs) {
+            Ok(attribute) => attribute,
+            Err(error) => return TokenStream::from(error.to_compile_error()),
+        };
+        let metadata = attribute.metadata.map(|(category, order)| {
+            quote::quote! {
+                Some(ActionMetadata {
+                    category: String::from(#category),
+                    order: #order,
+                })
+            }
+        });
+        match variant.fields {
+            Fields::Unit => {
+                arms.push(quote::quote! { Self::#ident => Some(#offset), });
+                let metadata = metadata.unwrap_or_else(|| quote::quote! { None });
+                metadata_arms.push(quote::quote! { Self::#ident => #metadata, });
+                counts.push(quote::quote! { 1 });
+            }
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 && attribute.nested => {
+                let ty = &fields.unnamed[0].ty;
+                arms.push(quote::quote! {
+                    Self::#ident(inner) => BindingTypeView::index(inner)
+                        .filter(|index| *index < <#ty as BindingTypeView>::COUNT)
+                        .map(|index| #offset + index),
+                });
+                let metadata = metadata.unwrap_or_else(|| quote::quote! { BindingTypeView::metadata(inner) });
+                metadata_arms.push(quote::quote! { Self::#ident(inner) => #metadata, });
+                counts.push(quote::quote! { <#ty as BindingTypeView>::COUNT });
+            }
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                arms.push(quote::quote! { Self::#ident(..) => None, });
+                let metadata = metadata.unwrap_or_else(|| quote::quote! { None });
+                metadata_arms.push(quote::quote! { Self::#ident(..) => #metadata, });
+            }
+            _ => {
+                return TokenStream::from(quote::quote! {
+                    impl #impl_generics BindingTypeView for #struct_name #ty_generics #where_clause {}
+                })
+            }
+        }
+    }
     let code = quote::quote! {
-        impl BindingTypeView for #struct_name {}
+        impl #impl_generics BindingTypeView for #struct_name #ty_generics #where_clause {
+            const COUNT: usize = 0 #(+ #counts)*;
+
+            fn index(&self) -> Option<usize> {
+                match self {
+                    #(#arms)*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn metadata(&self) -> Option<ActionMetadata> {
+                match self {
+                    #(#metadata_arms)*
+                }
+            }
+        }
     };
     TokenStream::from(code)
 }
@@ -0,0 +1,225 @@
+//! Accessibility options for an input view: sticky modifiers, slow keys and single-switch scanning.
+use bevy::prelude::*;
+use bevy::utils::{Duration, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct AccessibilitySystem;
+
+/// Single-switch scanning, cycling through a list of actions and selecting the highlighted one when the switch is
+/// pressed.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct SwitchScanning<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub actions: Vec<Keys>,
+    /// The receiver used as the switch.
+    pub switch: InputReceiver,
+    /// How long each action stays highlighted.
+    pub interval: Duration,
+    pub current: usize,
+    pub last_step: Option<Instant>,
+}
+
+impl<Keys> SwitchScanning<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn new(actions: Vec<Keys>, switch: InputReceiver, interval: Duration) -> Self {
+        Self {
+            actions,
+            switch,
+            interval,
+            current: 0,
+            last_step: None,
+        }
+    }
+
+    /// Returns the currently highlighted action.
+    pub fn current(&self) -> Option<Keys> {
+        self.actions.get(self.current).copied()
+    }
+}
+
+/// Event sent when the switch is pressed while scanning, with the highlighted action.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct SwitchScanSelected<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub entity: Entity,
+    pub action: Keys,
+}
+
+/// Accessibility settings for the input view of the same entity.
+#[derive(PartialEq, Eq, Clone, Debug, Component)]
+pub struct AccessibilitySettings<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Whether a released modifier key stays active until the next key is released.
+    /// Pressing a latched modifier again releases it.
+    pub sticky_modifiers: bool,
+    /// How long a key needs to be held before being accepted. Shorter presses are ignored.
+    pub slow_keys: Option<Duration>,
+    pub scanning: Option<SwitchScanning<Keys>>,
+    /// The modifier keys kept active by sticky modifiers.
+    pub latched: Vec<KeyCode>,
+    /// The keys held but not yet accepted by slow keys.
+    pub pending: Vec<(KeyCode, Instant)>,
+}
+
+impl<Keys> Default for AccessibilitySettings<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            sticky_modifiers: false,
+            slow_keys: None,
+            scanning: None,
+            latched: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<Keys> AccessibilitySettings<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn with_sticky_modifiers(mut self) -> Self {
+        self.sticky_modifiers = true;
+        self
+    }
+
+    pub fn with_slow_keys(mut self, delay: Duration) -> Self {
+        self.slow_keys = Some(delay);
+        self
+    }
+
+    pub fn with_scanning(mut self, scanning: SwitchScanning<Keys>) -> Self {
+        self.scanning = Some(scanning);
+        self
+    }
+
+    /// Returns whether the given key event needs to be filtered by these settings.
+    pub fn filters(&self, key: KeyCode, state: PressState) -> bool {
+        match state {
+            PressState::Pressed { .. } => self.slow_keys.is_some(),
+            PressState::Released => {
+                self.pending.iter().any(|(pending, _)| *pending == key)
+                    || (self.sticky_modifiers
+                        && (Modifiers::from_key(key).is_some() || !self.latched.is_empty()))
+            }
+        }
+    }
+
+    /// Filter a key event, returning the state to apply to the input view if any, and the latched modifiers to
+    /// release.
+    pub fn filter_key(
+        &mut self,
+        key: KeyCode,
+        state: PressState,
+    ) -> (Option<PressState>, Vec<KeyCode>) {
+        match state {
+            PressState::Pressed { .. } => {
+                if self.slow_keys.is_none() {
+                    return (Some(state), Vec::new());
+                }
+                if !self.pending.iter().any(|(pending, _)| *pending == key) {
+                    self.pending.push((key, Instant::now()));
+                }
+                (None, Vec::new())
+            }
+            PressState::Released => {
+                let pending = self.pending.len();
+                self.pending.retain(|(pending, _)| *pending != key);
+                if self.pending.len() != pending {
+                    return (None, Vec::new());
+                }
+                if !self.sticky_modifiers {
+                    return (Some(state), Vec::new());
+                }
+                if Modifiers::from_key(key).is_none() {
+                    return (Some(state), std::mem::take(&mut self.latched));
+                }
+                match self.latched.iter().position(|latched| *latched == key) {
+                    Some(index) => {
+                        self.latched.remove(index);
+                        (Some(state), Vec::new())
+                    }
+                    None => {
+                        self.latched.push(key);
+                        (None, Vec::new())
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// System responsible for accepting the keys held for longer than the slow keys delay and stepping the switch
+/// scanning.
+pub(crate) fn accessibility_system<Keys>(
+    mut query: Query<(
+        Entity,
+        &mut InputView<Keys>,
+        &mut AccessibilitySettings<Keys>,
+    )>,
+    mut wr: EventWriter<SwitchScanSelected<Keys>>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, mut view, mut settings) in query.iter_mut() {
+        if let Some(delay) = settings.slow_keys {
+            if settings
+                .pending
+                .iter()
+                .any(|(_, instant)| instant.elapsed() >= delay)
+            {
+                let settings = settings.as_mut();
+                settings.pending.retain(|(key, instant)| {
+                    if instant.elapsed() < delay {
+                        return true;
+                    }
+                    view.set_input_source(InputSource::Keyboard);
+                    view.set_key_receiver_state(
+                        InputReceiver::KeyboardKey(*key),
+                        PressState::Pressed {
+                            started_pressing_instant: None,
+                        },
+                    );
+                    false
+                });
+            }
+        }
+
+        let scanning = match settings.scanning {
+            Some(ref scanning) if !scanning.actions.is_empty() => scanning,
+            _ => continue,
+        };
+        if view.state(&scanning.switch).press
+            == (PressState::Pressed {
+                started_pressing_instant: None,
+            })
+        {
+            if let Some(action) = scanning.current() {
+                wr.send(SwitchScanSelected { entity, action });
+            }
+        }
+        if scanning
+            .last_step
+            .is_none_or(|instant| instant.elapsed() >= scanning.interval)
+        {
+            if let Some(ref mut scanning) = settings.scanning {
+                if scanning.last_step.is_some() {
+                    scanning.current = (scanning.current + 1) % scanning.actions.len();
+                }
+                scanning.last_step = Some(Instant::now());
+            }
+        }
+    }
+}
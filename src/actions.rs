@@ -0,0 +1,84 @@
+//! The [`Actions`] system parameter, reading the actions of every input view without the query boilerplate. Works the
+//! same whether the input view is a component or the global resource added by [`EZInputPlugin::global`].
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// A system parameter for reading actions. The methods without an entity combine every input view, which is what
+/// single player games want: an action is pressed if it is pressed in any view.
+#[derive(SystemParam)]
+pub struct Actions<'w, 's, Keys>
+where
+    Keys: BindingTypeView,
+{
+    global: Option<Res<'w, InputView<Keys>>>,
+    views: Query<'w, 's, (Entity, &'static InputView<Keys>), Without<GlobalInputView>>,
+}
+
+impl<'w, 's, Keys> Actions<'w, 's, Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns every input view: the global view first, if any, then the view of each entity.
+    pub fn views(&self) -> impl Iterator<Item = &InputView<Keys>> {
+        self.global
+            .as_deref()
+            .into_iter()
+            .chain(self.views.iter().map(|(_, view)| view))
+    }
+
+    /// Returns the global view if any, or the first view otherwise.
+    pub fn view(&self) -> Option<&InputView<Keys>> {
+        self.views().next()
+    }
+
+    /// Returns the view of an entity.
+    pub fn view_of(&self, entity: Entity) -> Option<&InputView<Keys>> {
+        self.views.get(entity).ok().map(|(_, view)| view)
+    }
+
+    /// Returns whether an action is pressed in any view.
+    pub fn pressed(&self, kind: Keys) -> bool {
+        self.views().any(|view| view.key(&kind).pressed())
+    }
+
+    /// Returns whether an action was pressed this frame in any view.
+    pub fn just_pressed(&self, kind: Keys) -> bool {
+        self.views().any(|view| view.key(&kind).just_pressed())
+    }
+
+    /// Returns whether an action is released in every view.
+    pub fn released(&self, kind: Keys) -> bool {
+        !self.pressed(kind)
+    }
+
+    /// Returns the axis value of an action, picking the view with the furthest value.
+    pub fn axis(&self, kind: Keys) -> f32 {
+        self.views()
+            .flat_map(|view| view.axis(&kind))
+            .map(|state| state.value)
+            .fold(0., |max, value| if value.abs() > max.abs() { value } else { max })
+    }
+
+    /// Returns the stick position of an action, picking the view with the furthest position.
+    pub fn axis2(&self, kind: Keys) -> Vec2 {
+        self.views()
+            .map(|view| view.stick(&kind))
+            .fold(Vec2::ZERO, |max, stick| {
+                if stick.length() > max.length() {
+                    stick
+                } else {
+                    max
+                }
+            })
+    }
+
+    /// Returns the entities whose view has an action pressed.
+    pub fn pressed_by(&self, kind: Keys) -> impl Iterator<Item = Entity> + '_ {
+        self.views
+            .iter()
+            .filter(move |(_, view)| view.key(&kind).pressed())
+            .map(|(entity, _)| entity)
+    }
+}
@@ -0,0 +1,97 @@
+//! Adaptive trigger effects. Bevy doesn't drive the trigger motors of the DualSense, so the effects set with
+//! [`GamepadMarker::set_trigger_effect`] are sent as [`GamepadTriggerEffectRequest`] events to be applied by the
+//! gamepad backend of the application. Effects are only sent to the gamepads the backend reports in the
+//! [`AdaptiveTriggerSupport`] resource, and kept until then.
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct AdaptiveTriggerSystem;
+
+/// The resistance effect of an adaptive trigger. Positions and strengths go from 0 to 1.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum TriggerEffect {
+    /// No resistance.
+    #[default]
+    Off,
+    /// A constant resistance from the given position to the end of the pull.
+    Resistance { start: f32, strength: f32 },
+    /// A resistance between two positions that gives way past the end, like the trigger of a gun.
+    Weapon { start: f32, end: f32, strength: f32 },
+    /// A hard stop at the given position, the trigger can't be pulled further.
+    Stop { position: f32 },
+    /// A vibration from the given position to the end of the pull, with a frequency in hertz.
+    Vibration { start: f32, amplitude: f32, frequency: f32 },
+}
+
+/// Event requesting an effect on an adaptive trigger of a gamepad.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GamepadTriggerEffectRequest {
+    pub gamepad: Gamepad,
+    /// The trigger, either [`GamepadButtonType::LeftTrigger2`] or [`GamepadButtonType::RightTrigger2`].
+    pub trigger: GamepadButtonType,
+    pub effect: TriggerEffect,
+}
+
+/// The gamepads with adaptive triggers, reported by the gamepad backend of the application.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct AdaptiveTriggerSupport(pub HashSet<Gamepad>);
+
+impl AdaptiveTriggerSupport {
+    /// Report whether a gamepad has adaptive triggers.
+    pub fn set(&mut self, gamepad: Gamepad, supported: bool) {
+        if supported {
+            self.0.insert(gamepad);
+        } else {
+            self.0.remove(&gamepad);
+        }
+    }
+
+    pub fn supports(&self, gamepad: Gamepad) -> bool {
+        self.0.contains(&gamepad)
+    }
+}
+
+impl GamepadMarker {
+    /// Set the effect of an adaptive trigger. The effects are sent again whenever the gamepad reconnects.
+    pub fn set_trigger_effect(&mut self, trigger: GamepadButtonType, effect: TriggerEffect) {
+        self.trigger_effects.insert(trigger, effect);
+        self.pending_trigger_effects.insert(trigger);
+    }
+
+    /// Turn off the effects of both adaptive triggers.
+    pub fn clear_trigger_effects(&mut self) {
+        for trigger in [GamepadButtonType::LeftTrigger2, GamepadButtonType::RightTrigger2] {
+            self.set_trigger_effect(trigger, TriggerEffect::Off);
+        }
+    }
+
+    /// Returns the effect set on an adaptive trigger.
+    pub fn trigger_effect(&self, trigger: GamepadButtonType) -> TriggerEffect {
+        self.trigger_effects.get(&trigger).copied().unwrap_or_default()
+    }
+}
+
+/// System responsible for sending the pending trigger effects of the connected gamepads with adaptive triggers.
+pub(crate) fn adaptive_trigger_system<Keys>(
+    mut query: Query<&mut GamepadMarker, With<InputView<Keys>>>,
+    support: Res<AdaptiveTriggerSupport>,
+    mut wr: EventWriter<GamepadTriggerEffectRequest>,
+) where
+    Keys: BindingTypeView,
+{
+    for mut svc in query.iter_mut() {
+        if svc.pending_trigger_effects.is_empty() || !svc.connected || !support.supports(svc.gamepad) {
+            continue;
+        }
+        let gamepad = svc.gamepad;
+        let pending = std::mem::take(&mut svc.pending_trigger_effects);
+        wr.send_batch(pending.into_iter().map(|trigger| GamepadTriggerEffectRequest {
+            gamepad,
+            trigger,
+            effect: svc.trigger_effect(trigger),
+        }));
+    }
+}
@@ -0,0 +1,385 @@
+//! Aliases for renamed actions, so bindings files saved before an action was renamed still load. Register the old
+//! names in an [`ActionAliases`] table and supply it to the bindings loader (see `InputBindingsPlugin::with_aliases`).
+//!
+//! Action names are the names of their serde variants, with the variants wrapping another action joined by `::`
+//! (e.g. `Movement::Jump` for a `Movement(Jump)` action tagged `#[action(nested)]`). See [`action_name`]. The actions
+//! need to derive serde's `Serialize` and `Deserialize`, which the enumerations generated by the `input!` macro don't.
+use std::fmt;
+
+use bevy::utils::HashMap;
+use serde::de::value::{Error, StrDeserializer};
+use serde::de::{DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor};
+use serde::ser::{self, Impossible, Serialize};
+use serde::{Deserialize, Deserializer};
+
+#[cfg(any(feature = "ron_bindings", feature = "toml_bindings", feature = "json_bindings"))]
+use crate::prelude::*;
+
+/// The name of an action as written in a bindings file, before resolving its aliases.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct ActionName(pub String);
+
+impl<'de> Deserialize<'de> for ActionName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Unit variants are written as identifiers in RON and as strings in JSON or TOML, and the variants wrapping
+        // another action as `Movement(Jump)` in RON and as `{"Movement": "Jump"}` in JSON or TOML. Every format reads
+        // both forms as an enum.
+        deserializer.deserialize_enum("ActionName", &[], ActionNameVisitor)
+    }
+}
+
+/// Reads an action name, joining the variants wrapping another action with `::` like [`action_name`].
+struct ActionNameVisitor;
+
+impl<'de> Visitor<'de> for ActionNameVisitor {
+    type Value = ActionName;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an action name")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(ActionName(value.to_string()))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (ActionName(variant), access) = data.variant_seed(VariantNameSeed)?;
+        // The formats can't tell a unit variant from a newtype one before reading it, so a variant without a wrapped
+        // action fails to read as a newtype and is taken for a unit variant.
+        match access.newtype_variant::<ActionName>() {
+            Ok(ActionName(inner)) => Ok(ActionName(format!("{}::{}", variant, inner))),
+            Err(_) => Ok(ActionName(variant)),
+        }
+    }
+}
+
+/// Reads the name of a variant.
+struct VariantNameSeed;
+
+impl<'de> DeserializeSeed<'de> for VariantNameSeed {
+    type Value = ActionName;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(ActionNameVisitor)
+    }
+}
+
+/// A table mapping the old names of renamed actions to their current names.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ActionAliases {
+    pub aliases: HashMap<String, String>,
+}
+
+impl ActionAliases {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an alias from an old action name to the current one.
+    pub fn alias(mut self, old: impl Into<String>, current: impl Into<String>) -> Self {
+        self.aliases.insert(old.into(), current.into());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    /// Returns the current name of an action, following renames made over several versions.
+    pub fn resolve<'a>(&'a self, mut name: &'a str) -> &'a str {
+        // Bounded by the number of aliases, so a cycle in the table doesn't hang.
+        for _ in 0..self.aliases.len() {
+            match self.aliases.get(name) {
+                Some(current) => name = current,
+                None => break,
+            }
+        }
+        name
+    }
+
+    /// Returns the action with the given name or alias.
+    pub fn action<Keys>(&self, name: &str) -> Result<Keys, Error>
+    where
+        Keys: DeserializeOwned,
+    {
+        Keys::deserialize(ActionNameDeserializer(self.resolve(name)))
+    }
+
+    /// Resolve the action names of a bindings file.
+    #[cfg(any(feature = "ron_bindings", feature = "toml_bindings", feature = "json_bindings"))]
+    pub fn resolve_bindings<Keys>(&self, bindings: InputBindings<ActionName>) -> Result<InputBindings<Keys>, Error>
+    where
+        Keys: DeserializeOwned,
+    {
+        let resolved = bindings
+            .bindings
+            .into_iter()
+            .map(|description| {
+                Ok(BindingDescription {
+                    action: self.action(&description.action.0)?,
+                    receivers: description.receivers,
+                    default_axis_values: description.default_axis_values,
+                    priority: description.priority,
+                    while_held: description
+                        .while_held
+                        .iter()
+                        .map(|held| self.action(&held.0))
+                        .collect::<Result<_, Error>>()?,
+                    required_modifiers: description.required_modifiers,
+                    forbidden_modifiers: description.forbidden_modifiers,
+                    action_type: description.action_type,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(InputBindings {
+            version: bindings.version,
+            bindings: resolved,
+        })
+    }
+}
+
+/// Returns the name of an action, as read back by [`ActionAliases::action`]. Fails for the actions that aren't unit
+/// variants or variants wrapping another action (or a number).
+pub fn action_name<Keys>(action: &Keys) -> Result<String, Error>
+where
+    Keys: Serialize,
+{
+    action.serialize(ActionNameSerializer)
+}
+
+fn unsupported<T>() -> Result<T, Error> {
+    Err(<Error as ser::Error>::custom("actions can only be named after unit and newtype variants"))
+}
+
+/// Serializes an action into its name.
+struct ActionNameSerializer;
+
+macro_rules! serialize_display {
+    ($($method:ident: $ty:ty),*) => {
+        $(fn $method(self, value: $ty) -> Result<String, Error> {
+            Ok(value.to_string())
+        })*
+    };
+}
+
+macro_rules! serialize_unsupported {
+    ($($method:ident: $ty:ty),*) => {
+        $(fn $method(self, _: $ty) -> Result<String, Error> {
+            unsupported()
+        })*
+    };
+}
+
+impl ser::Serializer for ActionNameSerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    serialize_display!(
+        serialize_bool: bool, serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64,
+        serialize_u8: u8, serialize_u16: u16, serialize_u32: u32, serialize_u64: u64, serialize_char: char,
+        serialize_str: &str
+    );
+    serialize_unsupported!(serialize_f32: f32, serialize_f64: f64, serialize_bytes: &[u8]);
+
+    fn serialize_none(self) -> Result<String, Error> {
+        unsupported()
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<String, Error> {
+        unsupported()
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        unsupported()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<String, Error> {
+        Ok(name.to_string())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _: &'static str, value: &T) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        Ok(format!("{}::{}", variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        unsupported()
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        unsupported()
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Error> {
+        unsupported()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        unsupported()
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        unsupported()
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        unsupported()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        unsupported()
+    }
+}
+
+/// Deserializes an action from its name, the reverse of [`ActionNameSerializer`].
+struct ActionNameDeserializer<'a>(&'a str);
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),*) => {
+        $(fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.0.parse() {
+                Ok(value) => visitor.$visit(value),
+                Err(_) => self.deserialize_str(visitor),
+            }
+        })*
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for ActionNameDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_str(self.0)
+    }
+
+    deserialize_parsed!(
+        deserialize_bool => visit_bool, deserialize_i8 => visit_i8, deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32, deserialize_i64 => visit_i64, deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16, deserialize_u32 => visit_u32, deserialize_u64 => visit_u64,
+        deserialize_char => visit_char
+    );
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        if self.0 == name {
+            visitor.visit_unit()
+        } else {
+            Err(serde::de::Error::invalid_value(serde::de::Unexpected::Str(self.0), &name))
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        f32 f64 str string bytes byte_buf option unit seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de, 'a> EnumAccess<'de> for ActionNameDeserializer<'a> {
+    type Error = Error;
+    type Variant = ActionNameVariant<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), Error> {
+        let (variant, inner) = match self.0.split_once("::") {
+            Some((variant, inner)) => (variant, Some(inner)),
+            None => (self.0, None),
+        };
+        let variant: StrDeserializer<Error> = variant.into_deserializer();
+        Ok((seed.deserialize(variant)?, ActionNameVariant(inner)))
+    }
+}
+
+/// The name of the action wrapped by a variant, if any.
+struct ActionNameVariant<'a>(Option<&'a str>);
+
+impl<'de, 'a> VariantAccess<'de> for ActionNameVariant<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0 {
+            None => Ok(()),
+            Some(_) => unsupported(),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        match self.0 {
+            Some(inner) => seed.deserialize(ActionNameDeserializer(inner)),
+            None => unsupported(),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, _: V) -> Result<V::Value, Error> {
+        unsupported()
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _: &'static [&'static str], _: V) -> Result<V::Value, Error> {
+        unsupported()
+    }
+}
+
+// Test to check that renamed actions are resolved through their aliases, including chained renames.
+#[test]
+fn action_aliases_test() {
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    enum AliasTestAction {
+        Jump,
+        Interact,
+    }
+
+    let aliases = ActionAliases::new().alias("Use", "Activate").alias("Activate", "Interact");
+    assert_eq!(aliases.action::<AliasTestAction>("Jump"), Ok(AliasTestAction::Jump));
+    assert_eq!(aliases.action::<AliasTestAction>("Use"), Ok(AliasTestAction::Interact));
+    assert!(aliases.action::<AliasTestAction>("Crouch").is_err());
+}
@@ -0,0 +1,120 @@
+//! Analog keyboard support (e.g. Wooting keyboards), feeding the depth of each key to the
+//! [`InputReceiver::KeyboardAnalog`] receivers so walking speed can be controlled by key depth like a gamepad stick.
+//! The device access is provided by an [`AnalogKeyboardBackend`], usually wrapping the vendor SDK.
+use bevy::ecs::event::Events;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct AnalogKeyboardSystem;
+
+/// A source of analog key depths.
+pub trait AnalogKeyboardBackend: Send + Sync + 'static {
+    /// Returns the depth, from 0 to 1, of every key currently pressed.
+    fn read(&mut self) -> Vec<(KeyCode, f32)>;
+}
+
+/// Event sent when the depth of an analog key changes. Released keys have a depth of zero.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct AnalogKeyInput {
+    /// The keyboard the key is on, or none for the default keyboard. Entities with an [`InputDevice`] only receive the
+    /// events of their keyboard.
+    pub device: Option<DeviceId>,
+    pub key: KeyCode,
+    pub depth: f32,
+}
+
+/// The backend polled by the [`AnalogKeyboardPlugin`] and the depths of the keys pressed in the last poll.
+pub struct AnalogKeyboard<B>
+where
+    B: AnalogKeyboardBackend,
+{
+    pub backend: B,
+    /// The keyboard read by the backend, if it's a specific one.
+    pub device: Option<DeviceId>,
+    pub depths: Vec<(KeyCode, f32)>,
+}
+
+/// A [`Plugin`] polling an analog keyboard backend every frame and sending the [`AnalogKeyInput`] events handled by
+/// the entities with a [`KeyboardMarker`].
+pub struct AnalogKeyboardPlugin<B>
+where
+    B: AnalogKeyboardBackend + Clone,
+{
+    pub backend: B,
+    /// The keyboard read by the backend, for entities routed to a specific keyboard with an [`InputDevice`].
+    pub device: Option<DeviceId>,
+}
+
+impl<B> AnalogKeyboardPlugin<B>
+where
+    B: AnalogKeyboardBackend + Clone,
+{
+    pub fn new(backend: B) -> Self {
+        Self { backend, device: None }
+    }
+
+    /// Tag the events of the backend with the given keyboard.
+    pub fn with_device(mut self, device: DeviceId) -> Self {
+        self.device = Some(device);
+        self
+    }
+}
+
+impl<B> Plugin for AnalogKeyboardPlugin<B>
+where
+    B: AnalogKeyboardBackend + Clone,
+{
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<Events<AnalogKeyInput>>() {
+            app.add_event::<AnalogKeyInput>();
+        }
+        app.insert_resource(AnalogKeyboard {
+            backend: self.backend.clone(),
+            device: self.device,
+            depths: Vec::new(),
+        });
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            analog_keyboard_system::<B>
+                .label(AnalogKeyboardSystem)
+                .before(KeyboardInputHandlingSystem),
+        );
+    }
+}
+
+/// System responsible for polling the analog keyboard and sending an event for every key whose depth changed.
+pub(crate) fn analog_keyboard_system<B>(
+    mut keyboard: ResMut<AnalogKeyboard<B>>,
+    mut wr: EventWriter<AnalogKeyInput>,
+) where
+    B: AnalogKeyboardBackend,
+{
+    let depths = keyboard.backend.read();
+    if depths == keyboard.depths {
+        return;
+    }
+    let device = keyboard.device;
+    let pressed: HashSet<KeyCode> = depths.iter().map(|(key, _)| *key).collect();
+    let released = keyboard
+        .depths
+        .iter()
+        .filter(|(key, _)| !pressed.contains(key))
+        .map(|(key, _)| AnalogKeyInput {
+            device,
+            key: *key,
+            depth: 0.,
+        });
+    let changed = depths
+        .iter()
+        .filter(|depth| !keyboard.depths.contains(depth))
+        .map(|(key, depth)| AnalogKeyInput {
+            device,
+            key: *key,
+            depth: *depth,
+        });
+    wr.send_batch(released.chain(changed).collect::<Vec<_>>().into_iter());
+    keyboard.depths = depths;
+}
@@ -0,0 +1,360 @@
+//! Bindings loadable as a Bevy asset from RON (`.bindings.ron`), TOML (`.bindings.toml`) or JSON (`.bindings.json`)
+//! files, hot-reloading the bindings of every input view holding the asset handle (or of the global view, when the
+//! handle is inserted as a resource). Requires the `ron_bindings`, `toml_bindings` or `json_bindings` feature.
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use bevy::asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::{TypeUuid, Uuid};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct InputBindingsAssetSystem;
+
+/// A single binding as described in a bindings file.
+#[derive(PartialEq, Clone, Debug, Deserialize, Serialize)]
+pub struct BindingDescription<Keys> {
+    pub action: Keys,
+    pub receivers: Vec<Vec<InputReceiver>>,
+    #[serde(default)]
+    pub default_axis_values: Vec<(InputReceiver, f32)>,
+    #[serde(default)]
+    pub priority: i32,
+    /// The actions that must be held for this binding to be pressed, see [`ActionBinding::while_held`].
+    #[serde(default = "Vec::new")]
+    pub while_held: Vec<Keys>,
+    /// The keyboard modifiers that must be held, see [`ActionBinding::require_modifiers`].
+    #[serde(default)]
+    pub required_modifiers: Modifiers,
+    /// The keyboard modifiers that must not be held, see [`ActionBinding::forbid_modifiers`].
+    #[serde(default)]
+    pub forbidden_modifiers: Modifiers,
+    #[serde(default)]
+    pub action_type: ActionType,
+}
+
+impl<Keys> BindingDescription<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Describe an action binding.
+    pub fn from_binding(binding: &ActionBinding<Keys>) -> Self {
+        Self {
+            action: binding.key,
+            receivers: binding
+                .input_receivers
+                .iter()
+                .map(|receivers| receivers.0.clone())
+                .collect(),
+            default_axis_values: Vec::new(),
+            priority: binding.priority,
+            while_held: binding.while_held.clone(),
+            required_modifiers: binding.required_modifiers,
+            forbidden_modifiers: binding.forbidden_modifiers,
+            action_type: binding.action_type,
+        }
+    }
+
+    /// Convert this description into an action binding.
+    pub fn to_binding(&self) -> ActionBinding<Keys> {
+        let mut binding = ActionBinding::new_from_vec(self.action, self.receivers.clone());
+        binding
+            .priority(self.priority)
+            .require_modifiers(self.required_modifiers)
+            .forbid_modifiers(self.forbidden_modifiers)
+            .action_type(self.action_type);
+        for held in self.while_held.iter() {
+            binding.while_held(*held);
+        }
+        for (receiver, value) in self.default_axis_values.iter() {
+            binding.default_axis_value(*receiver, *value);
+        }
+        binding
+    }
+}
+
+/// An asset containing all bindings of an input view.
+///
+/// Note that the asset type UUID is shared between all binding types, so only one binding type can be registered
+/// through [`InputBindingsPlugin`] in the same application.
+#[derive(PartialEq, Clone, Debug, Deserialize, Serialize)]
+pub struct InputBindings<Keys> {
+    /// The version of the bindings format of the game, used to migrate old files (see [`BindingMigrations`]).
+    #[serde(default)]
+    pub version: u32,
+    pub bindings: Vec<BindingDescription<Keys>>,
+}
+
+impl<Keys> TypeUuid for InputBindings<Keys> {
+    const TYPE_UUID: Uuid = Uuid::from_u128(0x6f2c_4f0e_8a51_4d5b_9d0a_3c7e_52b1_e4a9);
+}
+
+impl<Keys> InputBindings<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Describe the current bindings of a view, including the default axis values of their receivers. The bindings are
+    /// stamped with the given version of the bindings format (see [`BindingMigrations::version`]), so the files saved
+    /// from them aren't migrated again when loaded.
+    pub fn from_view(view: &InputView<Keys>, version: u32) -> Self {
+        let bindings = view
+            .bindings
+            .values()
+            .map(|binding| {
+                let mut description = BindingDescription::from_binding(binding);
+                description.default_axis_values = description
+                    .receivers
+                    .iter()
+                    .flatten()
+                    .filter_map(|rcv| {
+                        view.descriptor(rcv)
+                            .filter(|descriptor| descriptor.default_axis_value != 0.)
+                            .map(|descriptor| (*rcv, descriptor.default_axis_value))
+                    })
+                    .collect();
+                description
+            })
+            .collect();
+        Self { version, bindings }
+    }
+
+    /// Replace all bindings of the given view with the bindings of this asset.
+    pub fn apply(&self, view: &mut InputView<Keys>) {
+        let removed: Vec<Keys> = view
+            .bindings
+            .keys()
+            .filter(|kind| {
+                !self
+                    .bindings
+                    .iter()
+                    .any(|description| description.action == **kind)
+            })
+            .copied()
+            .collect();
+        for kind in removed {
+            view.clear_binding(&kind);
+        }
+        for description in self.bindings.iter() {
+            view.apply_binding(&mut description.to_binding());
+        }
+    }
+}
+
+/// The asset loader for [`InputBindings`], resolving the renamed actions of the loaded files through its aliases, or
+/// migrating the loaded files if it has migrations.
+pub struct InputBindingsLoader<Keys> {
+    pub aliases: ActionAliases,
+    pub migrations: Option<Arc<BindingMigrations<Keys>>>,
+    phantom_keys: PhantomData<Keys>,
+}
+
+impl<Keys> Default for InputBindingsLoader<Keys> {
+    fn default() -> Self {
+        Self {
+            aliases: ActionAliases::default(),
+            migrations: None,
+            phantom_keys: PhantomData,
+        }
+    }
+}
+
+/// Parse a bindings file of the given extension.
+fn parse_bindings<T>(extension: &str, bytes: &[u8]) -> Result<T, anyhow::Error>
+where
+    T: DeserializeOwned,
+{
+    Ok(match extension {
+        #[cfg(feature = "ron_bindings")]
+        "ron" => ron::de::from_bytes(bytes)?,
+        #[cfg(feature = "toml_bindings")]
+        "toml" => toml::from_slice(bytes)?,
+        #[cfg(feature = "json_bindings")]
+        "json" => serde_json::from_slice(bytes)?,
+        _ => anyhow::bail!("unsupported bindings file extension: {}", extension),
+    })
+}
+
+impl<Keys> AssetLoader for InputBindingsLoader<Keys>
+where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let extension = load_context
+                .path()
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .unwrap_or_default();
+            let bindings: InputBindings<Keys> = if let Some(ref migrations) = self.migrations {
+                let (bindings, report) = migrations.migrate(parse_bindings(extension, bytes)?);
+                for unmigratable in report.unmigratable.iter() {
+                    warn!(
+                        "dropped the binding of {} from {:?}: {}",
+                        unmigratable.action,
+                        load_context.path(),
+                        unmigratable.reason
+                    );
+                }
+                bindings
+            } else if self.aliases.is_empty() {
+                parse_bindings(extension, bytes)?
+            } else {
+                self.aliases
+                    .resolve_bindings(parse_bindings(extension, bytes)?)?
+            };
+            load_context.set_default_asset(LoadedAsset::new(bindings));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[
+            #[cfg(feature = "ron_bindings")]
+            "bindings.ron",
+            #[cfg(feature = "toml_bindings")]
+            "bindings.toml",
+            #[cfg(feature = "json_bindings")]
+            "bindings.json",
+        ]
+    }
+}
+
+/// A [`Plugin`] that registers the [`InputBindings`] asset and keeps the input views holding a handle to it updated.
+pub struct InputBindingsPlugin<Keys>
+where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    /// The aliases of the renamed actions, used when loading bindings files.
+    pub aliases: ActionAliases,
+    /// The migrations of old bindings files, replacing the aliases above if any.
+    pub migrations: Option<Arc<BindingMigrations<Keys>>>,
+    phantom_keys: PhantomData<Keys>,
+}
+
+impl<Keys> Default for InputBindingsPlugin<Keys>
+where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    fn default() -> Self {
+        Self {
+            aliases: ActionAliases::default(),
+            migrations: None,
+            phantom_keys: PhantomData,
+        }
+    }
+}
+
+impl<Keys> InputBindingsPlugin<Keys>
+where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    /// Creates the plugin with the aliases of the renamed actions, so bindings files saved with the old names still
+    /// load.
+    pub fn with_aliases(aliases: ActionAliases) -> Self {
+        Self {
+            aliases,
+            ..Default::default()
+        }
+    }
+
+    /// Creates the plugin with the migrations of old bindings files, so files saved by previous versions of the game
+    /// load with the defaults of the new actions.
+    pub fn with_migrations(migrations: BindingMigrations<Keys>) -> Self {
+        Self {
+            migrations: Some(Arc::new(migrations)),
+            ..Default::default()
+        }
+    }
+}
+
+impl<Keys> Plugin for InputBindingsPlugin<Keys>
+where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    fn build(&self, app: &mut App) {
+        app.add_asset::<InputBindings<Keys>>()
+            .add_asset_loader(InputBindingsLoader::<Keys> {
+                aliases: self.aliases.clone(),
+                migrations: self.migrations.clone(),
+                ..Default::default()
+            })
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                input_bindings_asset_system::<Keys>
+                    .label(InputBindingsAssetSystem)
+                    .before(EZInputSet::Resolve),
+            );
+    }
+}
+
+/// System responsible for applying loaded or modified bindings assets to the input views holding their handle. In
+/// global mode, the handle of the global view is a `Handle<InputBindings<Keys>>` resource.
+#[allow(clippy::type_complexity)]
+pub(crate) fn input_bindings_asset_system<Keys>(
+    mut rd: EventReader<AssetEvent<InputBindings<Keys>>>,
+    assets: Res<Assets<InputBindings<Keys>>>,
+    global_handle: Option<Res<Handle<InputBindings<Keys>>>>,
+    mut query: Query<(
+        &mut InputView<Keys>,
+        Option<&Handle<InputBindings<Keys>>>,
+        Option<&GlobalInputView>,
+    )>,
+) where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    for ev in rd.iter() {
+        let handle = match ev {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
+            AssetEvent::Removed { .. } => continue,
+        };
+        let bindings = match assets.get(handle) {
+            Some(bindings) => bindings,
+            None => continue,
+        };
+        for (mut view, view_handle, global) in query.iter_mut() {
+            // The global view is held by the hidden entity while the input systems run.
+            let view_handle = view_handle.or_else(|| global.and(global_handle.as_deref()));
+            if view_handle == Some(handle) {
+                bindings.apply(&mut view);
+            }
+        }
+    }
+}
+
+// Test to check that modifiers and action types survive describing a view and applying the description back.
+#[test]
+fn binding_description_round_trip_test() {
+    use bevy::prelude::KeyCode;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+    enum Action {
+        Save,
+        Back,
+    }
+    impl BindingTypeView for Action {}
+
+    let mut view = InputView::new();
+    view.add_binding(
+        ActionBinding::from(Action::Save)
+            .receivers(InputReceiver::KeyboardKey(KeyCode::S).into())
+            .require_modifiers(Modifiers::CTRL)
+            .action_type(ActionType::Button),
+    );
+    view.add_binding(
+        ActionBinding::from(Action::Back)
+            .receivers(InputReceiver::KeyboardKey(KeyCode::S).into())
+            .forbid_modifiers(Modifiers::CTRL),
+    );
+    let mut applied = InputView::new();
+    InputBindings::from_view(&view, 1).apply(&mut applied);
+    for action in [Action::Save, Action::Back] {
+        assert_eq!(applied.bindings.get(&action), view.bindings.get(&action));
+    }
+}
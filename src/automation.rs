@@ -0,0 +1,143 @@
+//! Scripted sequences of synthetic receiver presses and releases, for tutorials pressing the buttons for the player,
+//! one-button accessibility combos and tests. Insert a [`MacroPlayer`] on an input view entity to play a sequence.
+use bevy::prelude::*;
+use bevy::utils::{Duration, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct MacroPlayerSystem;
+
+/// A step of a macro.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum MacroStep {
+    /// Press a receiver with a value of 1.
+    Press(InputReceiver),
+    /// Release a receiver.
+    Release(InputReceiver),
+    /// Set the value of a receiver, releasing it if zero.
+    Axis(InputReceiver, f32),
+    /// Wait before playing the next steps.
+    Wait(Duration),
+}
+
+/// Event sent when a [`MacroPlayer`] plays its last step.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct MacroFinished {
+    pub entity: Entity,
+}
+
+/// Plays a sequence of [`MacroStep`]s into the input view of its entity, through the same injection used by the
+/// [`InputInjector`]. Use the [`GlobalInputView`] entity to play into the global view.
+#[derive(PartialEq, Clone, Debug, Component, Default)]
+pub struct MacroPlayer {
+    pub steps: Vec<MacroStep>,
+    /// The index of the next step to play.
+    pub next: usize,
+    /// The instant in which the current wait step ends.
+    pub waiting_until: Option<Instant>,
+    /// Whether to restart from the first step once the last one is played.
+    pub looping: bool,
+}
+
+impl MacroPlayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a step pressing a receiver.
+    pub fn press(mut self, receiver: impl Into<InputReceiver>) -> Self {
+        self.steps.push(MacroStep::Press(receiver.into()));
+        self
+    }
+
+    /// Add a step releasing a receiver.
+    pub fn release(mut self, receiver: impl Into<InputReceiver>) -> Self {
+        self.steps.push(MacroStep::Release(receiver.into()));
+        self
+    }
+
+    /// Add a step setting the value of a receiver.
+    pub fn axis(mut self, receiver: impl Into<InputReceiver>, value: f32) -> Self {
+        self.steps.push(MacroStep::Axis(receiver.into(), value));
+        self
+    }
+
+    /// Add a step waiting for the given duration.
+    pub fn wait(mut self, duration: Duration) -> Self {
+        self.steps.push(MacroStep::Wait(duration));
+        self
+    }
+
+    /// Add the steps pressing a receiver, holding it for the given duration and releasing it.
+    pub fn tap(self, receiver: impl Into<InputReceiver>, hold: Duration) -> Self {
+        let receiver = receiver.into();
+        self.press(receiver).wait(hold).release(receiver)
+    }
+
+    /// Make the macro restart from the first step once finished.
+    pub fn looped(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Returns whether every step was played.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.next >= self.steps.len()
+    }
+
+    /// Play the macro again from the first step.
+    pub fn restart(&mut self) {
+        self.next = 0;
+        self.waiting_until = None;
+    }
+
+    /// Play the steps due at the given instant, returning the receiver values to inject.
+    pub fn advance(&mut self, now: Instant) -> Vec<(InputReceiver, f32)> {
+        let mut values = Vec::new();
+        while let Some(step) = self.steps.get(self.next) {
+            match *step {
+                MacroStep::Press(receiver) => values.push((receiver, 1.)),
+                MacroStep::Release(receiver) => values.push((receiver, 0.)),
+                MacroStep::Axis(receiver, value) => values.push((receiver, value)),
+                MacroStep::Wait(duration) => {
+                    let until = *self.waiting_until.get_or_insert(now + duration);
+                    if now < until {
+                        break;
+                    }
+                    self.waiting_until = None;
+                }
+            }
+            self.next += 1;
+            if self.looping && self.next >= self.steps.len() {
+                self.next = 0;
+                break;
+            }
+        }
+        values
+    }
+}
+
+/// System responsible for playing every unfinished macro, injecting its receivers into the input view of its entity.
+pub(crate) fn macro_player_system(
+    mut query: Query<(Entity, &mut MacroPlayer)>,
+    mut injected: EventWriter<InjectedInput>,
+    mut finished: EventWriter<MacroFinished>,
+) {
+    let now = Instant::now();
+    for (entity, mut player) in query.iter_mut() {
+        if player.is_finished() {
+            continue;
+        }
+        let values = player.advance(now);
+        injected.send_batch(values.into_iter().map(|(receiver, value)| InjectedInput {
+            entity: Some(entity),
+            receiver,
+            value,
+            pressed: value != 0.,
+        }));
+        if player.is_finished() {
+            finished.send(MacroFinished { entity });
+        }
+    }
+}
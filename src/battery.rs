@@ -0,0 +1,87 @@
+//! Gamepad battery status. Bevy doesn't report the battery of the gamepads, so the gamepad backend of the application
+//! (e.g. the power info of gilrs) reports it through the [`GamepadBatteryStatus`] resource.
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct GamepadBatterySystem;
+
+/// The battery status of a gamepad. Levels are percentages from 0 to 100.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum GamepadBattery {
+    /// The backend doesn't know or doesn't support the battery status.
+    #[default]
+    Unknown,
+    /// The gamepad is powered by a cable and has no battery.
+    Wired,
+    Discharging(u8),
+    Charging(u8),
+    Charged,
+}
+
+impl GamepadBattery {
+    /// Returns the battery level, if known.
+    pub fn level(&self) -> Option<u8> {
+        match *self {
+            GamepadBattery::Discharging(level) | GamepadBattery::Charging(level) => Some(level),
+            GamepadBattery::Charged => Some(100),
+            GamepadBattery::Unknown | GamepadBattery::Wired => None,
+        }
+    }
+
+    /// Returns whether the gamepad is discharging below the given level, so the player can be warned.
+    pub fn is_low(&self, threshold: u8) -> bool {
+        matches!(*self, GamepadBattery::Discharging(level) if level < threshold)
+    }
+}
+
+/// The battery status of every gamepad, reported by the gamepad backend of the application.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct GamepadBatteryStatus(pub HashMap<Gamepad, GamepadBattery>);
+
+impl GamepadBatteryStatus {
+    /// Report the battery status of a gamepad.
+    pub fn set(&mut self, gamepad: Gamepad, battery: GamepadBattery) {
+        if self.0.get(&gamepad) != Some(&battery) {
+            self.0.insert(gamepad, battery);
+        }
+    }
+
+    /// Returns the battery status of a gamepad.
+    pub fn get(&self, gamepad: Gamepad) -> GamepadBattery {
+        self.0.get(&gamepad).copied().unwrap_or_default()
+    }
+}
+
+/// Event sent when the battery status of the gamepad of a [`GamepadMarker`] changes.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct GamepadBatteryChanged {
+    pub entity: Entity,
+    pub gamepad: Gamepad,
+    pub battery: GamepadBattery,
+}
+
+/// System responsible for copying the reported battery status to the gamepad markers, sending a
+/// [`GamepadBatteryChanged`] event for every change.
+pub(crate) fn gamepad_battery_system<Keys>(
+    mut query: Query<(Entity, &mut GamepadMarker), With<InputView<Keys>>>,
+    status: Res<GamepadBatteryStatus>,
+    mut wr: EventWriter<GamepadBatteryChanged>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, mut svc) in query.iter_mut() {
+        let battery = status.get(svc.gamepad);
+        if svc.battery == battery {
+            continue;
+        }
+        svc.battery = battery;
+        wr.send(GamepadBatteryChanged {
+            entity,
+            gamepad: svc.gamepad,
+            battery,
+        });
+    }
+}
@@ -9,11 +9,197 @@ use std::{collections::HashMap, fmt::Debug, hash::Hash};
 pub trait BindingTypeView:
     PartialEq + Eq + Hash + Clone + Copy + Debug + Send + Sync + 'static
 {
+    /// The number of actions with a dense index, usable in const contexts. Zero if the actions aren't indexed.
+    const COUNT: usize = 0;
+
+    /// Returns the dense index of this action, in `0..Self::COUNT`. Derived for enumerations by the
+    /// `BindingTypeView` macro, letting [`ActionMap`] store the actions in an array instead of hashing them.
+    fn index(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the category and display order of this action, used to build settings screens. Derived from the
+    /// `#[action(category = "...", order = N)]` attributes by the `BindingTypeView` macro.
+    fn metadata(&self) -> Option<ActionMetadata> {
+        None
+    }
+}
+
+/// A map from actions to values, stored in an array indexed by [`BindingTypeView::index`], falling back to hashing
+/// for the actions without an index.
+#[derive(Clone, Debug)]
+pub struct ActionMap<Keys, V>
+where
+    Keys: BindingTypeView,
+{
+    dense: Vec<Option<(Keys, V)>>,
+    sparse: HashMap<Keys, V>,
+}
+
+impl<Keys, V> Default for ActionMap<Keys, V>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            dense: Vec::new(),
+            sparse: HashMap::new(),
+        }
+    }
+}
+
+impl<Keys, V> PartialEq for ActionMap<Keys, V>
+where
+    Keys: BindingTypeView,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(kind, value)| other.get(kind) == Some(value))
+    }
+}
+
+impl<Keys, V> ActionMap<Keys, V>
+where
+    Keys: BindingTypeView,
+{
+    /// Creates an empty action map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of an action.
+    pub fn get(&self, kind: &Keys) -> Option<&V> {
+        match kind.index() {
+            Some(index) => self.dense.get(index)?.as_ref().map(|(_, value)| value),
+            None => self.sparse.get(kind),
+        }
+    }
+
+    /// Returns the value of an action mutably.
+    pub fn get_mut(&mut self, kind: &Keys) -> Option<&mut V> {
+        match kind.index() {
+            Some(index) => self.dense.get_mut(index)?.as_mut().map(|(_, value)| value),
+            None => self.sparse.get_mut(kind),
+        }
+    }
+
+    /// Returns whether the action has a value.
+    pub fn contains_key(&self, kind: &Keys) -> bool {
+        self.get(kind).is_some()
+    }
+
+    /// Set the value of an action, returning the previous one.
+    pub fn insert(&mut self, kind: Keys, value: V) -> Option<V> {
+        match kind.index() {
+            Some(index) => {
+                if index >= self.dense.len() {
+                    self.dense.resize_with(Keys::COUNT.max(index + 1), || None);
+                }
+                self.dense[index]
+                    .replace((kind, value))
+                    .map(|(_, value)| value)
+            }
+            None => self.sparse.insert(kind, value),
+        }
+    }
+
+    /// Remove the value of an action, returning it.
+    pub fn remove(&mut self, kind: &Keys) -> Option<V> {
+        match kind.index() {
+            Some(index) => self.dense.get_mut(index)?.take().map(|(_, value)| value),
+            None => self.sparse.remove(kind),
+        }
+    }
+
+    /// Returns the number of actions with a value.
+    pub fn len(&self) -> usize {
+        self.dense.iter().flatten().count() + self.sparse.len()
+    }
+
+    /// Returns whether no action has a value.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every value.
+    pub fn clear(&mut self) {
+        self.dense.clear();
+        self.sparse.clear();
+    }
+
+    /// Iterate over every action and its value, the indexed actions first.
+    pub fn iter(&self) -> impl Iterator<Item = (&Keys, &V)> {
+        self.dense
+            .iter()
+            .flatten()
+            .map(|(kind, value)| (kind, value))
+            .chain(self.sparse.iter())
+    }
+
+    /// Iterate over every action with a value.
+    pub fn keys(&self) -> impl Iterator<Item = &Keys> {
+        self.iter().map(|(kind, _)| kind)
+    }
+
+    /// Iterate over every value.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Iterate mutably over every value.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.dense
+            .iter_mut()
+            .flatten()
+            .map(|(_, value)| value)
+            .chain(self.sparse.values_mut())
+    }
+
+    /// Consume the map, returning every value.
+    pub fn into_values(self) -> impl Iterator<Item = V> {
+        self.dense
+            .into_iter()
+            .flatten()
+            .map(|(_, value)| value)
+            .chain(self.sparse.into_values())
+    }
+}
+
+impl<Keys, V> FromIterator<(Keys, V)> for ActionMap<Keys, V>
+where
+    Keys: BindingTypeView,
+{
+    fn from_iter<T: IntoIterator<Item = (Keys, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (kind, value) in iter {
+            map.insert(kind, value);
+        }
+        map
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash, Deserialize, Serialize, Default)]
 pub struct InputReceivers(pub Vec<InputReceiver>);
 
+impl InputReceivers {
+    /// Returns every input source used by this collection of receivers. A chord can freely mix sources, e.g. a
+    /// keyboard modifier and a mouse button.
+    pub fn sources(&self) -> Vec<InputSource> {
+        let mut sources = Vec::with_capacity(self.0.len());
+        for source in self.0.iter().map(InputReceiver::source) {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        sources
+    }
+
+    /// Returns whether this collection mixes receivers from different input sources.
+    pub fn is_cross_device(&self) -> bool {
+        self.sources().len() > 1
+    }
+}
+
 impl From<Vec<InputReceiver>> for InputReceivers {
     fn from(input_receivers: Vec<InputReceiver>) -> Self {
         Self(input_receivers)
@@ -72,6 +258,29 @@ impl
     }
 }
 
+/// The type of value accepted by an action.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, Deserialize, Serialize, Default)]
+pub enum ActionType {
+    /// The action accepts both buttons and axis.
+    #[default]
+    Any,
+    /// The action only accepts buttons.
+    Button,
+    /// The action is meant to be used as an axis.
+    Axis,
+}
+
+impl ActionType {
+    /// Returns whether a receiver of the given kind can be bound to the action. Axis actions accept buttons too,
+    /// through their default axis values.
+    pub fn accepts(&self, kind: ReceiverKind) -> bool {
+        match self {
+            ActionType::Any | ActionType::Axis => true,
+            ActionType::Button => kind == ReceiverKind::Button,
+        }
+    }
+}
+
 /// The binding itself, and its associated receivers.
 #[derive(PartialEq, Clone, Debug, Deserialize, Serialize, Default)]
 pub struct ActionBinding<InputKey>
@@ -81,6 +290,21 @@ where
     pub key: InputKey,
     pub input_receivers: HashSet<InputReceivers>,
     pub default_axis_value: HashMap<InputReceiver, f32>,
+    /// Keyboard modifiers that must be held for this action to be pressed.
+    #[serde(default)]
+    pub required_modifiers: Modifiers,
+    /// Keyboard modifiers that must not be held for this action to be pressed.
+    #[serde(default)]
+    pub forbidden_modifiers: Modifiers,
+    /// When actions share a receiver, only the pressed action with the highest priority fires.
+    #[serde(default)]
+    pub priority: i32,
+    /// Actions that must be held for this action to be pressed (e.g. Parry on the Dodge button while Block is held).
+    #[serde(default = "Vec::new")]
+    pub while_held: Vec<InputKey>,
+    /// The type of value accepted by this action, used when validating bindings.
+    #[serde(default)]
+    pub action_type: ActionType,
 }
 
 impl<InputKey> From<InputKey> for ActionBinding<InputKey>
@@ -93,6 +317,11 @@ where
             key,
             input_receivers: HashSet::default(),
             default_axis_value: HashMap::default(),
+            required_modifiers: Modifiers::NONE,
+            forbidden_modifiers: Modifiers::NONE,
+            priority: 0,
+            while_held: Vec::new(),
+            action_type: ActionType::Any,
         }
     }
 }
@@ -107,6 +336,11 @@ where
             key,
             default_axis_value: HashMap::default(),
             input_receivers: receivers,
+            required_modifiers: Modifiers::NONE,
+            forbidden_modifiers: Modifiers::NONE,
+            priority: 0,
+            while_held: Vec::new(),
+            action_type: ActionType::Any,
         }
     }
 
@@ -119,6 +353,11 @@ where
                 .iter()
                 .map(|vec| InputReceivers::from(vec.clone()))
                 .collect(),
+            required_modifiers: Modifiers::NONE,
+            forbidden_modifiers: Modifiers::NONE,
+            priority: 0,
+            while_held: Vec::new(),
+            action_type: ActionType::Any,
         }
     }
 
@@ -134,6 +373,46 @@ where
         self
     }
 
+    /// Require the given keyboard modifiers to be held for this action to be pressed (e.g. Ctrl+S).
+    pub fn require_modifiers(&mut self, modifiers: Modifiers) -> &mut Self {
+        self.required_modifiers = modifiers;
+        self
+    }
+
+    /// Forbid the given keyboard modifiers, so this action isn't pressed while they are held (e.g. S but not Ctrl+S).
+    pub fn forbid_modifiers(&mut self, modifiers: Modifiers) -> &mut Self {
+        self.forbidden_modifiers = modifiers;
+        self
+    }
+
+    /// Set the type of value accepted by this action.
+    pub fn action_type(&mut self, action_type: ActionType) -> &mut Self {
+        self.action_type = action_type;
+        self
+    }
+
+    /// Set the priority of this action. When actions share a receiver, the other pressed actions with a lower priority
+    /// are shadowed by this action.
+    pub fn priority(&mut self, priority: i32) -> &mut Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Require another action to be held for this action to be pressed. When a pressed action shares receivers with
+    /// a conditional action of the same priority, the conditional one wins, so the same button can perform a
+    /// different action while a "modifier action" is held.
+    pub fn while_held(&mut self, action: InputKey) -> &mut Self {
+        if !self.while_held.contains(&action) {
+            self.while_held.push(action);
+        }
+        self
+    }
+
+    /// Returns whether the given held modifiers satisfy the modifiers required and forbidden by this action.
+    pub fn accepts_modifiers(&self, modifiers: Modifiers) -> bool {
+        modifiers.contains(self.required_modifiers) && !modifiers.intersects(self.forbidden_modifiers)
+    }
+
     pub fn default_axis_value(&mut self, receiver: InputReceiver, value: f32) -> &mut Self {
         self.default_axis_value.insert(receiver, value);
         self
@@ -148,3 +427,103 @@ where
         self
     }
 }
+
+// Test to check the derived dense indices of nested action enumerations.
+#[test]
+fn action_index_test() {
+    use ezinput_macros::BindingTypeView;
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Movement {
+        Jump,
+        Crouch,
+    }
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Pause,
+        #[action(nested)]
+        Movement(Movement),
+        Interact,
+    }
+
+    assert_eq!(Action::COUNT, 4);
+    assert_eq!(Action::Pause.index(), Some(0));
+    assert_eq!(Action::Movement(Movement::Crouch).index(), Some(2));
+    assert_eq!(Action::Interact.index(), Some(3));
+
+    let mut map = ActionMap::new();
+    map.insert(Action::Interact, 1);
+    map.insert(Action::Movement(Movement::Jump), 2);
+    assert_eq!(map.get(&Action::Interact), Some(&1));
+    assert_eq!(map.remove(&Action::Movement(Movement::Jump)), Some(2));
+    assert_eq!(map.len(), 1);
+}
+
+// Test to check that single-field variants not tagged as nested derive without a dense index.
+#[test]
+fn unindexed_variant_test() {
+    use ezinput_macros::BindingTypeView;
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Pause,
+        Slot(u8),
+    }
+
+    assert_eq!(Action::COUNT, 1);
+    assert_eq!(Action::Slot(3).index(), None);
+    assert_eq!(Action::Slot(3).metadata(), None);
+
+    let mut map = ActionMap::new();
+    map.insert(Action::Slot(3), 1);
+    map.insert(Action::Pause, 2);
+    assert_eq!(map.get(&Action::Slot(3)), Some(&1));
+    assert_eq!(map.len(), 2);
+}
+
+// Test to check that a conditional binding takes over its button while the action it waits for is held.
+#[test]
+fn conditional_binding_test() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Dodge,
+        Parry,
+        Block,
+    }
+    impl BindingTypeView for Action {}
+
+    let south = InputReceiver::GamepadButton(GamepadButtonType::South);
+    let trigger = InputReceiver::GamepadButton(GamepadButtonType::LeftTrigger);
+    let pressed = PressState::Pressed {
+        started_pressing_instant: None,
+    };
+    let mut view = InputView::new();
+    view.add_binding(ActionBinding::from(Action::Dodge).receivers(south.into()));
+    view.add_binding(ActionBinding::from(Action::Parry).receivers(south.into()).while_held(Action::Block));
+    view.add_binding(ActionBinding::from(Action::Block).receivers(trigger.into()));
+    view.set_key_receiver_state(south, pressed);
+    assert!(view.key(&Action::Dodge).pressed());
+    assert!(view.key(&Action::Parry).released());
+    view.set_key_receiver_state(trigger, pressed);
+    assert!(view.key(&Action::Dodge).released());
+    assert!(view.key(&Action::Parry).pressed());
+}
+
+// Test to check that clearing a source strips its receivers from the chords mixing sources and reports the change.
+#[test]
+fn clear_source_test() {
+    use bevy::prelude::{KeyCode, MouseButton};
+
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Shoot;
+    impl BindingTypeView for Shoot {}
+
+    let shift = InputReceiver::KeyboardKey(KeyCode::LShift);
+    let click = InputReceiver::MouseButton(MouseButton::Left);
+    let mut view = InputView::new();
+    view.add_binding(ActionBinding::from(Shoot).receivers(InputReceivers(vec![shift, click])));
+    view.clear_from_specific_source(InputSource::Mouse);
+    assert_eq!(view.receiver_index.get(&Shoot), Some(&vec![shift]));
+    assert_eq!(view.binding_changes.len(), 1);
+}
@@ -0,0 +1,174 @@
+//! A compact bitset of the pressed receivers of an input view, indexed through a [`ReceiverRegistry`]. Chords are
+//! evaluated by comparing their precomputed mask with the pressed receivers, so evaluating dozens of chords per frame
+//! costs a few word comparisons each instead of a lookup per receiver.
+use bevy::utils::HashMap;
+
+use crate::prelude::*;
+
+/// A dense index for every receiver used by an input view.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ReceiverRegistry {
+    ids: HashMap<InputReceiver, usize>,
+}
+
+impl ReceiverRegistry {
+    /// Returns the index of a receiver, registering it if needed.
+    pub fn register(&mut self, input: InputReceiver) -> usize {
+        let next = self.ids.len();
+        *self.ids.entry(input).or_insert(next)
+    }
+
+    /// Returns the index of a registered receiver.
+    pub fn get(&self, input: &InputReceiver) -> Option<usize> {
+        self.ids.get(input).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// A set of receiver indices stored as bits.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Hash)]
+pub struct ReceiverBitSet {
+    pub words: Vec<u64>,
+}
+
+impl ReceiverBitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        let word = index / 64;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (index % 64);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if let Some(word) = self.words.get_mut(index / 64) {
+            *word &= !(1 << (index % 64));
+        }
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        if value {
+            self.insert(index);
+        } else {
+            self.remove(index);
+        }
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.words
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    /// Returns whether every index of the other set is in this set.
+    pub fn contains_all(&self, other: &ReceiverBitSet) -> bool {
+        other
+            .words
+            .iter()
+            .enumerate()
+            .all(|(index, word)| self.words.get(index).copied().unwrap_or_default() & word == *word)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    /// Returns the number of indices in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Rebuild the pressed receivers from the descriptors. The view keeps them up to date when written through its
+    /// methods; call it after writing the descriptors directly.
+    pub fn refresh_pressed_receivers(&mut self) {
+        self.pressed_receivers.clear();
+        for descriptor in self.descriptors.iter() {
+            if descriptor.axis.press.pressed() {
+                let index = self.receiver_registry.register(descriptor.input);
+                self.pressed_receivers.insert(index);
+            }
+        }
+    }
+
+    /// Update the pressed bit of a receiver from its descriptor.
+    pub(crate) fn sync_pressed_receiver(&mut self, input: InputReceiver) {
+        let pressed = self.state(&input).press.pressed();
+        let index = self.receiver_registry.register(input);
+        self.pressed_receivers.set(index, pressed);
+    }
+
+    /// Compute the masks of the chords bound to an action.
+    pub(crate) fn index_chords(&mut self, kind: &Keys) {
+        let binding = match self.bindings.get(kind) {
+            Some(binding) => binding,
+            None => return,
+        };
+        for receivers in binding.input_receivers.iter() {
+            if self.chord_masks.contains_key(receivers) {
+                continue;
+            }
+            let mut mask = ReceiverBitSet::new();
+            for rcv in receivers.0.iter() {
+                mask.insert(self.receiver_registry.register(*rcv));
+            }
+            self.chord_masks.insert(receivers.clone(), mask);
+        }
+    }
+
+    /// Returns whether every receiver of a chord is pressed.
+    pub fn chord_pressed(&self, receivers: &InputReceivers) -> bool {
+        match self.chord_masks.get(receivers) {
+            Some(mask) => self.pressed_receivers.contains_all(mask),
+            None => receivers.0.iter().all(|rcv| self.state(rcv).press.pressed()),
+        }
+    }
+}
+
+// Test to check that a chord is only pressed once every receiver is pressed.
+#[test]
+fn chord_bitset_test() {
+    use bevy::prelude::KeyCode;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    enum Action {
+        Save,
+    }
+    impl BindingTypeView for Action {}
+
+    let chord = InputReceivers::from((KeyCode::LControl.into(), KeyCode::S.into()));
+    let mut view = InputView::new();
+    view.add_binding(ActionBinding::from(Action::Save).receivers(chord.clone()));
+    let pressed = PressState::Pressed {
+        started_pressing_instant: None,
+    };
+    view.set_key_receiver_state(KeyCode::LControl.into(), pressed);
+    assert!(!view.chord_pressed(&chord));
+    view.set_key_receiver_state(KeyCode::S.into(), pressed);
+    assert!(view.chord_pressed(&chord));
+    view.set_key_receiver_state(KeyCode::LControl.into(), PressState::Released);
+    assert!(!view.chord_pressed(&chord));
+    view.release_all();
+    assert!(view.pressed_receivers.is_empty());
+    view.rebind(Action::Save, vec![InputReceiver::KeyboardKey(KeyCode::F5).into()]);
+    assert!(!view.chord_masks.contains_key(&chord));
+}
@@ -0,0 +1,74 @@
+//! Browser (WASM) compatibility hooks: pointer lock for mouse-look bindings and gamepads only visible on poll.
+//! The toggles are enabled by default on `wasm32` targets only.
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct BrowserPointerLockSystem;
+
+/// Toggles adjusting the input handling to browser quirks.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct BrowserInputCompat {
+    /// Lock and hide the cursor of the primary window while an input view has a mouse-look binding
+    /// (bound to [`InputReceiver::MouseAxisDelta`]), and release it otherwise.
+    pub pointer_lock: bool,
+    /// Consider a gamepad connected as soon as it reports values, since the browser Gamepad API only exposes
+    /// gamepads when polled, without sending a connection event.
+    pub poll_gamepads: bool,
+}
+
+impl Default for BrowserInputCompat {
+    fn default() -> Self {
+        let browser = cfg!(target_arch = "wasm32");
+        Self {
+            pointer_lock: browser,
+            poll_gamepads: browser,
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns whether any binding of this input view is bound to a mouse delta, usually for mouse-look.
+    pub fn has_mouse_look(&self) -> bool {
+        self.bindings.values().any(|binding| {
+            binding.input_receivers.iter().any(|receivers| {
+                receivers
+                    .0
+                    .iter()
+                    .any(|rcv| matches!(rcv, InputReceiver::MouseAxisDelta(_)))
+            })
+        })
+    }
+}
+
+/// System responsible for requesting the pointer lock when a mouse-look binding is active. The cursor is left to the
+/// cursor mode system while the current context of an input view has a cursor mode, so the two don't fight over it.
+pub(crate) fn browser_pointer_lock_system<Keys>(
+    compat: Res<BrowserInputCompat>,
+    query: Query<&InputView<Keys>>,
+    windows: Option<ResMut<Windows>>,
+) where
+    Keys: BindingTypeView,
+{
+    if !compat.pointer_lock {
+        return;
+    }
+    let mut windows = match windows {
+        Some(windows) => windows,
+        None => return,
+    };
+    if query.iter().any(|view| view.cursor_mode().is_some()) {
+        return;
+    }
+    let lock = query.iter().any(|view| view.has_mouse_look());
+    if let Some(window) = windows.get_primary_mut() {
+        if window.cursor_locked() != lock {
+            window.set_cursor_lock_mode(lock);
+            window.set_cursor_visibility(!lock);
+        }
+    }
+}
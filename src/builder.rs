@@ -0,0 +1,252 @@
+//! A builder for input views, validating the bindings instead of silently accepting misconfigurations.
+use std::fmt::{Debug, Display};
+
+use crate::prelude::*;
+
+/// A misconfiguration found while building an input view.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum InputViewError<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The action was added more than once.
+    DuplicateAction(Keys),
+    /// The action has no receivers.
+    EmptyAction(Keys),
+    /// The same receiver appears more than once in a collection of receivers of the action.
+    DuplicateReceiver { action: Keys, receiver: InputReceiver },
+    /// The same collection of receivers is bound to two actions with the same priority, which can both be pressed at
+    /// once.
+    ConflictingReceivers {
+        receivers: InputReceivers,
+        actions: (Keys, Keys),
+    },
+    /// An axis receiver is bound to an action that only accepts buttons.
+    AxisOnButtonAction { action: Keys, receiver: InputReceiver },
+}
+
+impl<Keys> Display for InputViewError<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputViewError::DuplicateAction(action) => {
+                write!(f, "{:?} was added more than once", action)
+            }
+            InputViewError::EmptyAction(action) => write!(f, "{:?} has no receivers", action),
+            InputViewError::DuplicateReceiver { action, receiver } => {
+                write!(f, "{:?} is bound more than once in {:?}", receiver, action)
+            }
+            InputViewError::ConflictingReceivers { receivers, actions } => write!(
+                f,
+                "{:?} is bound to both {:?} and {:?} with the same priority",
+                receivers.0, actions.0, actions.1
+            ),
+            InputViewError::AxisOnButtonAction { action, receiver } => write!(
+                f,
+                "{:?} is an axis, but {:?} only accepts buttons",
+                receiver, action
+            ),
+        }
+    }
+}
+
+impl<Keys> std::error::Error for InputViewError<Keys> where Keys: BindingTypeView {}
+
+/// A builder for [`InputView`]s, validating all bindings when building the view.
+#[derive(PartialEq, Clone, Debug)]
+pub struct InputViewBuilder<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub capacity: usize,
+    pub bindings: Vec<ActionBinding<Keys>>,
+}
+
+impl<Keys> Default for InputViewBuilder<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Keys> InputViewBuilder<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Creates an empty builder for an input view with a default of 16 capacity.
+    pub fn new() -> Self {
+        Self {
+            capacity: 16,
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Set the capacity of the input view.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Add a new binding to the input view.
+    pub fn binding(mut self, binding: ActionBinding<Keys>) -> Self {
+        self.bindings.push(binding);
+        self
+    }
+
+    /// Returns all misconfigurations of the bindings added so far.
+    pub fn validate(&self) -> Vec<InputViewError<Keys>> {
+        let mut errors = Vec::new();
+        for (index, binding) in self.bindings.iter().enumerate() {
+            let previous = &self.bindings[..index];
+            if previous.iter().any(|other| other.key == binding.key) {
+                errors.push(InputViewError::DuplicateAction(binding.key));
+                continue;
+            }
+            if binding.input_receivers.iter().all(|rcvs| rcvs.0.is_empty()) {
+                errors.push(InputViewError::EmptyAction(binding.key));
+            }
+            for receivers in binding.input_receivers.iter() {
+                for (position, receiver) in receivers.0.iter().enumerate() {
+                    if receivers.0[..position].contains(receiver) {
+                        errors.push(InputViewError::DuplicateReceiver {
+                            action: binding.key,
+                            receiver: *receiver,
+                        });
+                    }
+                    if !binding.action_type.accepts(receiver.kind()) {
+                        errors.push(InputViewError::AxisOnButtonAction {
+                            action: binding.key,
+                            receiver: *receiver,
+                        });
+                    }
+                }
+                let conflict = previous.iter().find(|other| {
+                    other.priority == binding.priority
+                        && other.input_receivers.contains(receivers)
+                        && can_press_together(other, binding)
+                });
+                if let Some(other) = conflict {
+                    errors.push(InputViewError::ConflictingReceivers {
+                        receivers: receivers.clone(),
+                        actions: (other.key, binding.key),
+                    });
+                }
+            }
+        }
+        errors
+    }
+
+    /// Build the input view, returning every misconfiguration found if any.
+    pub fn build(self) -> Result<InputView<Keys>, Vec<InputViewError<Keys>>> {
+        let errors = self.validate();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let mut view = InputView::with_capacity(self.capacity);
+        for mut binding in self.bindings {
+            view.add_binding(&mut binding);
+        }
+        Ok(view)
+    }
+}
+
+/// Returns whether two bindings sharing receivers can be pressed together: the modifiers they require and forbid can be
+/// held at once, and neither waits for more held actions than the other, which would shadow it.
+fn can_press_together<Keys>(a: &ActionBinding<Keys>, b: &ActionBinding<Keys>) -> bool
+where
+    Keys: BindingTypeView,
+{
+    let required = a.required_modifiers | b.required_modifiers;
+    !required.intersects(a.forbidden_modifiers | b.forbidden_modifiers) && a.while_held.len() == b.while_held.len()
+}
+
+// Test to check that misconfigured bindings are reported by the builder.
+#[test]
+fn input_view_builder_validation_test() {
+    use bevy::prelude::{GamepadAxisType, KeyCode};
+    use ezinput_macros::BindingTypeView;
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Jump,
+        Crouch,
+        Fire,
+    }
+
+    let mut jump = ActionBinding::from(Action::Jump);
+    jump.receivers(KeyboardKey(KeyCode::Space).into());
+    let mut crouch = ActionBinding::from(Action::Crouch);
+    crouch.receivers(KeyboardKey(KeyCode::Space).into());
+    let mut fire = ActionBinding::from(Action::Fire);
+    fire.action_type(ActionType::Button)
+        .receivers(GamepadAxis(GamepadAxisType::RightZ).into());
+
+    let errors = InputViewBuilder::new()
+        .binding(jump.clone())
+        .binding(crouch)
+        .binding(fire)
+        .binding(ActionBinding::from(Action::Jump))
+        .validate();
+    assert_eq!(
+        errors,
+        vec![
+            InputViewError::ConflictingReceivers {
+                receivers: KeyboardKey(KeyCode::Space).into(),
+                actions: (Action::Jump, Action::Crouch),
+            },
+            InputViewError::AxisOnButtonAction {
+                action: Action::Fire,
+                receiver: GamepadAxis(GamepadAxisType::RightZ),
+            },
+            InputViewError::DuplicateAction(Action::Jump),
+        ]
+    );
+    assert!(InputViewBuilder::new().binding(jump).build().is_ok());
+}
+
+// Test to check that bindings sharing receivers only conflict when both can be pressed at once.
+#[test]
+fn conditional_conflict_test() {
+    use bevy::prelude::KeyCode;
+    use ezinput_macros::BindingTypeView;
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Save,
+        Back,
+        Block,
+        Parry,
+    }
+
+    let mut save = ActionBinding::from(Action::Save);
+    save.receivers(KeyboardKey(KeyCode::S).into())
+        .require_modifiers(Modifiers::CTRL);
+    let mut back = ActionBinding::from(Action::Back);
+    back.receivers(KeyboardKey(KeyCode::S).into())
+        .forbid_modifiers(Modifiers::CTRL);
+    let mut block = ActionBinding::from(Action::Block);
+    block.receivers(KeyboardKey(KeyCode::Q).into());
+    let mut parry = ActionBinding::from(Action::Parry);
+    parry.receivers(KeyboardKey(KeyCode::Q).into())
+        .while_held(Action::Back);
+    let builder = InputViewBuilder::new()
+        .binding(save.clone())
+        .binding(back.clone())
+        .binding(block)
+        .binding(parry);
+    assert!(builder.validate().is_empty());
+
+    back.forbid_modifiers(Modifiers::NONE);
+    let errors = InputViewBuilder::new().binding(save).binding(back).validate();
+    assert_eq!(
+        errors,
+        vec![InputViewError::ConflictingReceivers {
+            receivers: KeyboardKey(KeyCode::S).into(),
+            actions: (Action::Save, Action::Back),
+        }]
+    );
+}
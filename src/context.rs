@@ -0,0 +1,226 @@
+//! A stack of input contexts built on the binding sets (e.g. gameplay, then a pause menu on top of it), with the
+//! cursor grabbed or released depending on the active context. The press timers of a context can be frozen while it
+//! is covered, so returning from a pause menu while still holding a button resumes the press where it was.
+use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap, HashSet, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct CursorModeSystem;
+
+/// How the cursor of the primary window behaves while a context is active.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum CursorMode {
+    /// The cursor is visible and free to leave the window, as needed by menus.
+    #[default]
+    Free,
+    /// The cursor is locked and hidden, as needed by mouse-look.
+    Grabbed,
+}
+
+/// The presses of a covered context, frozen until the context is on top of the stack again.
+#[derive(PartialEq, Clone, Debug)]
+pub struct FrozenContext<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The receivers held when the context was covered, and for how long they were held.
+    pub receivers: Vec<(InputReceiver, Duration)>,
+    /// The snapshots of the actions pressed when the context was covered.
+    pub snapshots: Vec<(Keys, ActionSnapshot)>,
+}
+
+/// The pushed contexts, from the bottom to the top, and the cursor mode of each context.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ContextStack<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub stack: Vec<String>,
+    pub cursor_modes: HashMap<String, CursorMode>,
+    /// The contexts whose press timers are frozen while covered, instead of being released.
+    pub freezing: HashSet<String>,
+    pub frozen: HashMap<String, FrozenContext<Keys>>,
+    /// The binding set active before the first context was pushed, switched back to when the last one is popped.
+    pub base: Option<String>,
+}
+
+impl<Keys> Default for ContextStack<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            stack: Vec::new(),
+            cursor_modes: HashMap::default(),
+            freezing: HashSet::default(),
+            frozen: HashMap::default(),
+            base: None,
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Push a context on top of the stack, switching to the binding set of the same name. Returns false if the
+    /// binding set doesn't exist.
+    pub fn push_context(&mut self, name: impl Into<String>) -> bool {
+        let name = name.into();
+        if !self.binding_sets.contains_key(&name) && self.active_set.as_ref() != Some(&name) {
+            return false;
+        }
+        let covered = self
+            .current_context()
+            .filter(|covered| self.contexts.freezing.contains(*covered))
+            .map(String::from);
+        if let Some(covered) = covered {
+            let frozen = self.freeze_presses();
+            self.contexts.frozen.insert(covered, frozen);
+        }
+        if self.contexts.stack.is_empty() {
+            self.contexts.base = Some(
+                self.active_set
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_BINDING_SET.to_string()),
+            );
+        }
+        self.switch_set(name.clone());
+        self.contexts.stack.push(name);
+        true
+    }
+
+    /// Pop the context on top of the stack, switching back to the binding set of the context below it, or to the
+    /// bindings active before the first context was pushed. Returns the popped context.
+    pub fn pop_context(&mut self) -> Option<String> {
+        let popped = self.contexts.stack.pop()?;
+        if let Some(below) = self.contexts.stack.last().cloned() {
+            self.switch_set(below.clone());
+            if let Some(frozen) = self.contexts.frozen.remove(&below) {
+                self.resume_presses(frozen);
+            }
+        } else if let Some(base) = self.contexts.base.take() {
+            self.switch_set(base);
+        }
+        Some(popped)
+    }
+
+    /// Freeze the press timers of the given context while other contexts are pushed on top of it, instead of
+    /// releasing its actions.
+    pub fn set_context_freezes_timers(&mut self, name: impl Into<String>, freeze: bool) {
+        let name = name.into();
+        if freeze {
+            self.contexts.freezing.insert(name);
+        } else {
+            self.contexts.freezing.remove(&name);
+            self.contexts.frozen.remove(&name);
+        }
+    }
+
+    /// Store the held receivers and pressed actions, removing the snapshots so covering the context doesn't
+    /// release its actions.
+    fn freeze_presses(&mut self) -> FrozenContext<Keys> {
+        let receivers = self
+            .descriptors
+            .iter()
+            .filter_map(|descriptor| descriptor.axis.press.elapsed().map(|held| (descriptor.input, held)))
+            .collect();
+        let pressed: Vec<Keys> = self
+            .snapshots
+            .iter()
+            .filter(|(_, snapshot)| snapshot.pressed)
+            .map(|(kind, _)| *kind)
+            .collect();
+        let snapshots = pressed
+            .into_iter()
+            .filter_map(|kind| self.snapshots.remove(&kind).map(|snapshot| (kind, snapshot)))
+            .collect();
+        FrozenContext {
+            receivers,
+            snapshots,
+        }
+    }
+
+    /// Resume the presses of a frozen context for the receivers still held, with their press timers shifted by
+    /// the time spent covered.
+    fn resume_presses(&mut self, frozen: FrozenContext<Keys>) {
+        let now = Instant::now();
+        for (input, held) in frozen.receivers {
+            let descriptor = match self.descriptor_mut(&input) {
+                Some(descriptor) if descriptor.axis.press.pressed() => descriptor,
+                _ => continue,
+            };
+            descriptor.axis.press = PressState::Pressed {
+                started_pressing_instant: Some(now.checked_sub(held).unwrap_or(now)),
+            };
+            self.sync_pressed_receiver(input);
+            self.suppressed.remove(&input);
+        }
+        for (kind, snapshot) in frozen.snapshots {
+            self.snapshots.insert(kind, snapshot);
+        }
+    }
+
+    /// Returns the context on top of the stack.
+    pub fn current_context(&self) -> Option<&str> {
+        self.contexts.stack.last().map(String::as_str)
+    }
+
+    /// Set the cursor mode applied while the given context is on top of the stack.
+    pub fn set_context_cursor_mode(&mut self, name: impl Into<String>, mode: CursorMode) {
+        self.contexts.cursor_modes.insert(name.into(), mode);
+    }
+
+    /// Returns the cursor mode of the current context, or none if it has no cursor mode.
+    pub fn cursor_mode(&self) -> Option<CursorMode> {
+        self.current_context()
+            .and_then(|name| self.contexts.cursor_modes.get(name))
+            .copied()
+    }
+}
+
+/// System responsible for grabbing the cursor of the primary window while the current context of any input view
+/// is [`CursorMode::Grabbed`], and releasing it otherwise. The cursor is left untouched while no current context
+/// has a cursor mode.
+pub(crate) fn cursor_mode_system<Keys>(query: Query<&InputView<Keys>>, windows: Option<ResMut<Windows>>)
+where
+    Keys: BindingTypeView,
+{
+    let modes: Vec<CursorMode> = query.iter().filter_map(|view| view.cursor_mode()).collect();
+    if modes.is_empty() {
+        return;
+    }
+    let grab = modes.contains(&CursorMode::Grabbed);
+    let mut windows = match windows {
+        Some(windows) => windows,
+        None => return,
+    };
+    if let Some(window) = windows.get_primary_mut() {
+        if window.cursor_locked() != grab {
+            window.set_cursor_lock_mode(grab);
+            window.set_cursor_visibility(!grab);
+        }
+    }
+}
+
+// Test to check that popping the last context restores the bindings active before the first push.
+#[test]
+fn context_stack_test() {
+    use bevy::prelude::KeyCode;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let space = InputReceiver::KeyboardKey(KeyCode::Space);
+    let mut view = InputView::new();
+    view.add_binding(ActionBinding::from(Jump).receivers(space.into()));
+    view.add_binding_set("pause", Vec::new());
+    assert!(view.push_context("pause"));
+    assert!(view.bindings.get(&Jump).is_none());
+    assert_eq!(view.pop_context().as_deref(), Some("pause"));
+    assert_eq!(view.receiver_index.get(&Jump), Some(&vec![space]));
+    assert_eq!(view.current_context(), None);
+}
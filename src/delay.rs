@@ -0,0 +1,241 @@
+//! Frame-lagged input for delay-based netcode. With an input delay of N frames, the resolved state of each action is
+//! queued and only seen N frames later, so local players suffer the same delay as the remote ones. Set it with
+//! [`InputView::set_input_delay`]; [`InputView::live_axis`], [`InputView::live_stick`], [`InputView::live_resolve`]
+//! and [`InputView::live_active_receivers`] still read the current state, e.g. to send it to the remote players right
+//! away.
+use std::collections::VecDeque;
+
+use bevy::math::Vec2;
+use bevy::utils::{HashMap, Instant};
+
+use crate::prelude::*;
+
+/// The state of an action as resolved in a past frame.
+#[derive(PartialEq, Clone, Debug)]
+pub struct DelayedAction<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub axes: Vec<AxisState>,
+    pub stick: Vec2,
+    /// The receivers pressing the action, if it was active.
+    pub receivers: Option<InputReceivers>,
+    /// Why the action wasn't active, if it wasn't.
+    pub inactive: Option<ActionResolution<'static, Keys>>,
+    /// The frame in which the delayed press became visible, if the action is pressed.
+    pub press_frame: Option<u64>,
+    /// The instant in which the delayed press became visible, if the action is pressed.
+    pub press_instant: Option<Instant>,
+}
+
+impl<Keys> DelayedAction<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn pressed(&self) -> bool {
+        self.axes.last().is_some_and(|state| state.press.pressed())
+    }
+}
+
+/// The resolved actions of a frame, waiting to be seen.
+#[derive(PartialEq, Clone, Debug)]
+pub struct DelayedFrame<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The actions not at rest. Inactive actions are left out.
+    pub actions: HashMap<Keys, DelayedAction<Keys>>,
+    pub input_source: Option<InputSource>,
+}
+
+impl<Keys> Default for DelayedFrame<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            actions: HashMap::default(),
+            input_source: None,
+        }
+    }
+}
+
+/// The queued action states of an input view with an input delay.
+#[derive(PartialEq, Clone, Debug)]
+pub struct InputDelay<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The number of frames the action states are delayed by. Disabled while zero.
+    pub frames: usize,
+    /// The frames resolved last, from the oldest to the newest.
+    pub queue: VecDeque<DelayedFrame<Keys>>,
+    /// The frame seen now, resolved `frames` frames ago.
+    pub delayed: DelayedFrame<Keys>,
+    /// The last [`InputFrame`] the queue advanced in.
+    pub frame: u64,
+}
+
+impl<Keys> Default for InputDelay<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            frames: 0,
+            queue: VecDeque::new(),
+            delayed: DelayedFrame::default(),
+            frame: 0,
+        }
+    }
+}
+
+impl<Keys> InputDelay<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Show the oldest queued frames until the queue fits the delay. The presses that weren't visible yet are
+    /// restarted, so they are just pressed in the frame they become visible.
+    fn catch_up(&mut self) {
+        while self.queue.len() > self.frames {
+            let mut next = match self.queue.pop_front() {
+                Some(next) => next,
+                None => break,
+            };
+            let now = Instant::now();
+            for (kind, action) in next.actions.iter_mut() {
+                if !action.pressed() {
+                    continue;
+                }
+                let (frame, instant) = match self.delayed.actions.get(kind).filter(|previous| previous.pressed()) {
+                    Some(previous) => (previous.press_frame, previous.press_instant),
+                    None => (Some(self.frame), None),
+                };
+                action.press_frame = frame;
+                action.press_instant = instant.or(Some(now));
+                // The first frame of a press has no instant, so it reads as just pressed until the next tick.
+                let started_pressing_instant = instant;
+                for state in action.axes.iter_mut().filter(|state| state.press.pressed()) {
+                    state.press = PressState::Pressed {
+                        started_pressing_instant,
+                    };
+                }
+            }
+            self.delayed = next;
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Delay the action states by the given number of frames, or remove the delay if zero. Shortening the delay drops
+    /// the states that are now too old.
+    pub fn set_input_delay(&mut self, frames: usize) {
+        self.delay.frames = frames;
+        if frames == 0 {
+            self.delay.queue.clear();
+            self.delay.delayed = DelayedFrame::default();
+            return;
+        }
+        self.delay.catch_up();
+    }
+
+    /// Returns the number of frames the action states are delayed by.
+    pub fn input_delay(&self) -> usize {
+        self.delay.frames
+    }
+
+    /// Returns the last input source used, delayed along with the actions.
+    pub fn input_source(&self) -> Option<InputSource> {
+        if self.delay.frames > 0 {
+            return self.delay.delayed.input_source;
+        }
+        self.last_input_source
+    }
+
+    /// Returns the resolution of an action as delayed by the input delay.
+    pub(crate) fn delayed_resolution(&self, kind: &Keys) -> ActionResolution<'_, Keys> {
+        match self.delay.delayed.actions.get(kind) {
+            Some(DelayedAction {
+                receivers: Some(receivers),
+                ..
+            }) => ActionResolution::Active(receivers),
+            Some(DelayedAction {
+                inactive: Some(resolution),
+                ..
+            }) => *resolution,
+            _ => ActionResolution::Inactive,
+        }
+    }
+
+    /// Queue the actions resolved in the given frame and show the ones resolved `frames` frames ago. Done by the tick
+    /// system every frame.
+    pub(crate) fn advance_input_delay(&mut self, frame: u64) {
+        let mut actions = HashMap::default();
+        for kind in self.bindings.keys() {
+            let (receivers, inactive) = match self.live_resolve(kind) {
+                ActionResolution::Active(receivers) => (Some(receivers.clone()), None),
+                ActionResolution::Inactive => (None, None),
+                ActionResolution::Gated => (None, Some(ActionResolution::Gated)),
+                ActionResolution::Occluded => (None, Some(ActionResolution::Occluded)),
+                ActionResolution::Shadowed(other) => (None, Some(ActionResolution::Shadowed(other))),
+            };
+            let axes = self.live_axis(kind);
+            let stick = self.live_stick(kind);
+            if receivers.is_none() && inactive.is_none() && stick == Vec2::ZERO {
+                continue;
+            }
+            actions.insert(
+                *kind,
+                DelayedAction {
+                    axes,
+                    stick,
+                    receivers,
+                    inactive,
+                    press_frame: None,
+                    press_instant: None,
+                },
+            );
+        }
+        self.delay.queue.push_back(DelayedFrame {
+            actions,
+            input_source: self.last_input_source,
+        });
+        self.delay.frame = frame;
+        self.delay.catch_up();
+    }
+}
+
+// Test to check that a press is only seen once the input delay elapsed, and is just pressed when it shows up.
+#[test]
+fn input_delay_test() {
+    use bevy::prelude::KeyCode;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let mut view = InputView::new();
+    view.add_binding(ActionBinding::from(Jump).receivers(InputReceiver::KeyboardKey(KeyCode::Space).into()));
+    view.set_input_delay(2);
+    view.set_key_receiver_state(
+        KeyCode::Space.into(),
+        PressState::Pressed {
+            started_pressing_instant: Some(Instant::now()),
+        },
+    );
+    view.advance_input_delay(1);
+    assert!(!view.key(&Jump).pressed());
+    assert!(view.live_axis(&Jump).iter().all(|state| state.press.pressed()));
+    view.advance_input_delay(2);
+    assert!(!view.key(&Jump).pressed());
+    view.advance_input_delay(3);
+    assert!(view.key(&Jump).just_pressed());
+    assert_eq!(view.press_frame(&Jump), Some(3));
+    assert!(view.active_receivers(&Jump).is_some());
+    view.advance_input_delay(4);
+    assert!(view.key(&Jump).pressed());
+    assert_eq!(view.press_frame(&Jump), Some(3));
+}
@@ -0,0 +1,66 @@
+//! Device-aware keyboard and mouse input, so two physically distinct keyboards or mice can drive different input
+//! views (arcade cabinets, couch co-op). Bevy doesn't expose device identifiers, so a platform integration (e.g. raw
+//! input on Windows or evdev on Linux) needs to send the device events.
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// The identifier of a physical keyboard or mouse, as reported by the platform.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct DeviceId(pub u64);
+
+/// The keyboard and mouse devices driving the input view of an entity. Entities with a device only receive the
+/// device events of that device, ignoring the regular Bevy keyboard or mouse events.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Copy, Default)]
+pub struct InputDevice {
+    pub keyboard: Option<DeviceId>,
+    pub mouse: Option<DeviceId>,
+}
+
+impl InputDevice {
+    pub fn keyboard(device: DeviceId) -> Self {
+        Self {
+            keyboard: Some(device),
+            mouse: None,
+        }
+    }
+
+    pub fn mouse(device: DeviceId) -> Self {
+        Self {
+            keyboard: None,
+            mouse: Some(device),
+        }
+    }
+}
+
+/// A key pressed or released on a specific keyboard.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct DeviceKeyboardInput {
+    pub device: DeviceId,
+    pub key: KeyCode,
+    pub state: PressState,
+}
+
+/// A button pressed or released on a specific mouse.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct DeviceMouseButtonInput {
+    pub device: DeviceId,
+    pub button: MouseButton,
+    pub state: PressState,
+}
+
+/// A specific mouse moved. The location of the mouse is the sum of its movements, since only the system cursor has a
+/// location on screen.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct DeviceMouseMotion {
+    pub device: DeviceId,
+    pub delta: Vec2,
+}
+
+/// The wheel of a specific mouse scrolled vertically.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct DeviceMouseWheel {
+    pub device: DeviceId,
+    pub y: f32,
+}
@@ -0,0 +1,199 @@
+//! Quantization of stick actions into 4-way or 8-way directions, with hysteresis to prevent jitter at the boundaries.
+//! Useful for grid movement and fighting-game notation.
+use std::f32::consts::{FRAC_PI_4, PI, TAU};
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct DirectionSystem;
+
+/// One of the eight directions of a stick, with the Y axis pointing up.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Dir8 {
+    Right,
+    UpRight,
+    Up,
+    UpLeft,
+    Left,
+    DownLeft,
+    Down,
+    DownRight,
+}
+
+impl Dir8 {
+    /// Every direction, counterclockwise from the right.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Right,
+        Dir8::UpRight,
+        Dir8::Up,
+        Dir8::UpLeft,
+        Dir8::Left,
+        Dir8::DownLeft,
+        Dir8::Down,
+        Dir8::DownRight,
+    ];
+
+    /// Returns the angle of the direction in radians, counterclockwise from the right.
+    pub fn angle(&self) -> f32 {
+        Self::ALL.iter().position(|dir| dir == self).unwrap_or_default() as f32 * FRAC_PI_4
+    }
+
+    /// Returns the unit vector of the direction.
+    pub fn to_vec(&self) -> Vec2 {
+        Vec2::new(self.angle().cos(), self.angle().sin())
+    }
+
+    /// Returns whether this direction is up, down, left or right.
+    pub fn is_cardinal(&self) -> bool {
+        matches!(self, Dir8::Right | Dir8::Up | Dir8::Left | Dir8::Down)
+    }
+
+    /// Returns the direction in numpad notation, as used in fighting games (e.g. 6 for right, 3 for down-right).
+    pub fn numpad(&self) -> u8 {
+        match self {
+            Dir8::Right => 6,
+            Dir8::UpRight => 9,
+            Dir8::Up => 8,
+            Dir8::UpLeft => 7,
+            Dir8::Left => 4,
+            Dir8::DownLeft => 1,
+            Dir8::Down => 2,
+            Dir8::DownRight => 3,
+        }
+    }
+}
+
+/// The quantization of a stick action.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct DirectionSettings {
+    /// Whether the diagonals are allowed (8-way) or not (4-way).
+    pub diagonals: bool,
+    /// The stick length under which there is no direction.
+    pub dead_zone: f32,
+    /// The extra angle in radians the stick needs to move past a boundary to leave the current direction.
+    pub hysteresis: f32,
+}
+
+impl Default for DirectionSettings {
+    fn default() -> Self {
+        Self {
+            diagonals: true,
+            dead_zone: 0.5,
+            hysteresis: 0.1,
+        }
+    }
+}
+
+impl DirectionSettings {
+    /// Quantize a stick position, keeping the previous direction while the stick is within its hysteresis margin.
+    pub fn quantize(&self, stick: Vec2, previous: Option<Dir8>) -> Option<Dir8> {
+        if stick.length() < self.dead_zone || stick.length() == 0. {
+            return None;
+        }
+        let sector = if self.diagonals { FRAC_PI_4 } else { PI / 2. };
+        let angle = stick.y.atan2(stick.x).rem_euclid(TAU);
+        if let Some(previous) = previous.filter(|dir| self.diagonals || dir.is_cardinal()) {
+            let distance = (angle - previous.angle() + PI).rem_euclid(TAU) - PI;
+            if distance.abs() <= sector / 2. + self.hysteresis {
+                return Some(previous);
+            }
+        }
+        let index = (angle / sector).round() as usize % (TAU / sector).round() as usize;
+        let step = if self.diagonals { 1 } else { 2 };
+        Some(Dir8::ALL[index * step])
+    }
+}
+
+/// Per action quantization configuration and the current directions.
+#[derive(PartialEq, Clone, Debug)]
+pub struct DirectionTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub settings: HashMap<Keys, DirectionSettings>,
+    pub current: HashMap<Keys, Dir8>,
+}
+
+impl<Keys> Default for DirectionTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            settings: HashMap::default(),
+            current: HashMap::default(),
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the direction of a stick action. Actions configured with [`InputView::set_direction_settings`] apply
+    /// hysteresis, the others are quantized into 8 directions every time.
+    pub fn direction(&self, kind: &Keys) -> Option<Dir8> {
+        if self.direction.settings.contains_key(kind) {
+            return self.direction.current.get(kind).copied();
+        }
+        DirectionSettings::default().quantize(self.stick(kind), None)
+    }
+
+    /// Enable or disable the quantization with hysteresis of a stick action.
+    pub fn set_direction_settings(&mut self, kind: Keys, settings: Option<DirectionSettings>) {
+        match settings {
+            Some(settings) => self.direction.settings.insert(kind, settings),
+            None => self.direction.settings.remove(&kind),
+        };
+        self.direction.current.remove(&kind);
+    }
+}
+
+/// System responsible for updating the direction of every configured stick action.
+pub(crate) fn direction_system<Keys>(mut query: Query<&mut InputView<Keys>>)
+where
+    Keys: BindingTypeView,
+{
+    for mut view in query.iter_mut() {
+        let changed: Vec<(Keys, Option<Dir8>)> = view
+            .direction
+            .settings
+            .iter()
+            .filter_map(|(kind, settings)| {
+                let previous = view.direction.current.get(kind).copied();
+                let direction = settings.quantize(view.stick(kind), previous);
+                (direction != previous).then_some((*kind, direction))
+            })
+            .collect();
+        for (kind, direction) in changed {
+            match direction {
+                Some(direction) => view.direction.current.insert(kind, direction),
+                None => view.direction.current.remove(&kind),
+            };
+        }
+    }
+}
+
+// Test to check the 4-way and 8-way quantization and the hysteresis at the boundaries.
+#[test]
+fn direction_quantize_test() {
+    let eight = DirectionSettings::default();
+    assert_eq!(eight.quantize(Vec2::new(0.1, 0.), None), None);
+    assert_eq!(eight.quantize(Vec2::new(1., 0.), None), Some(Dir8::Right));
+    assert_eq!(eight.quantize(Vec2::new(-0.7, -0.7), None), Some(Dir8::DownLeft));
+    // Just past the boundary between right and up-right.
+    let boundary = Vec2::new((FRAC_PI_4 / 2. + 0.05).cos(), (FRAC_PI_4 / 2. + 0.05).sin());
+    assert_eq!(eight.quantize(boundary, None), Some(Dir8::UpRight));
+    assert_eq!(eight.quantize(boundary, Some(Dir8::Right)), Some(Dir8::Right));
+
+    let four = DirectionSettings {
+        diagonals: false,
+        ..DirectionSettings::default()
+    };
+    assert_eq!(four.quantize(Vec2::new(0.6, 0.8), None), Some(Dir8::Up));
+    assert_eq!(four.quantize(Vec2::new(0.6, 0.8), Some(Dir8::UpRight)), Some(Dir8::Up));
+    assert_eq!(Dir8::DownRight.numpad(), 3);
+}
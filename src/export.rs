@@ -0,0 +1,254 @@
+//! Export and import of bindings as JSON or as a Steam Input-like VDF layout, so players can share control schemes
+//! and tooling can inspect them. Requires the `json_bindings` feature.
+use std::fmt::Write;
+
+use anyhow::{anyhow, bail, Result};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::prelude::*;
+
+impl<Keys> InputBindings<Keys>
+where
+    Keys: BindingTypeView + Serialize + DeserializeOwned,
+{
+    /// Export the bindings as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Import bindings exported with [`InputBindings::to_json`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Export the bindings as a VDF (Valve KeyValues) layout, with a group per action. Actions and receivers are
+    /// stored as JSON strings, and the version of the bindings format as `bindings_version`.
+    pub fn to_vdf(&self) -> Result<String> {
+        let mut vdf = String::new();
+        writeln!(vdf, "\"controller_mappings\"\n{{")?;
+        writeln!(vdf, "\t\"version\"\t\"3\"")?;
+        writeln!(vdf, "\t\"title\"\t\"ezinput\"")?;
+        writeln!(vdf, "\t\"bindings_version\"\t\"{}\"", self.version)?;
+        for description in self.bindings.iter() {
+            writeln!(vdf, "\t\"group\"\n\t{{")?;
+            let action = serde_json::to_string(&description.action)?;
+            writeln!(vdf, "\t\t\"action\"\t\"{}\"", escape(&action))?;
+            writeln!(vdf, "\t\t\"priority\"\t\"{}\"", description.priority)?;
+            for receivers in description.receivers.iter() {
+                let receivers = serde_json::to_string(receivers)?;
+                writeln!(vdf, "\t\t\"binding\"\t\"{}\"", escape(&receivers))?;
+            }
+            for default in description.default_axis_values.iter() {
+                let default = serde_json::to_string(default)?;
+                writeln!(vdf, "\t\t\"default_axis_value\"\t\"{}\"", escape(&default))?;
+            }
+            for held in description.while_held.iter() {
+                let held = serde_json::to_string(held)?;
+                writeln!(vdf, "\t\t\"while_held\"\t\"{}\"", escape(&held))?;
+            }
+            if description.required_modifiers != Modifiers::NONE {
+                writeln!(vdf, "\t\t\"required_modifiers\"\t\"{}\"", description.required_modifiers.0)?;
+            }
+            if description.forbidden_modifiers != Modifiers::NONE {
+                writeln!(vdf, "\t\t\"forbidden_modifiers\"\t\"{}\"", description.forbidden_modifiers.0)?;
+            }
+            if description.action_type != ActionType::Any {
+                let action_type = serde_json::to_string(&description.action_type)?;
+                writeln!(vdf, "\t\t\"action_type\"\t\"{}\"", escape(&action_type))?;
+            }
+            writeln!(vdf, "\t}}")?;
+        }
+        writeln!(vdf, "}}")?;
+        Ok(vdf)
+    }
+
+    /// Import bindings exported with [`InputBindings::to_vdf`].
+    pub fn from_vdf(vdf: &str) -> Result<Self> {
+        let tokens = tokenize(vdf)?;
+        let mut tokens = tokens.into_iter();
+        match (tokens.next(), tokens.next()) {
+            (Some(VdfToken::Str(root)), Some(VdfToken::Open)) if root == "controller_mappings" => {}
+            _ => bail!("expected a \"controller_mappings\" section"),
+        }
+        let mut version = 0;
+        let mut bindings = Vec::new();
+        loop {
+            let key = match tokens.next() {
+                Some(VdfToken::Str(key)) => key,
+                Some(VdfToken::Close) => break,
+                _ => bail!("unexpected end of the \"controller_mappings\" section"),
+            };
+            match tokens.next() {
+                Some(VdfToken::Open) if key == "group" => bindings.push(parse_group(&mut tokens)?),
+                Some(VdfToken::Str(value)) if key == "bindings_version" => version = value.parse()?,
+                Some(VdfToken::Str(_)) => {}
+                _ => bail!("unexpected value for \"{}\"", key),
+            }
+        }
+        Ok(Self { version, bindings })
+    }
+}
+
+/// Parse the content of a group section, up to its closing brace.
+fn parse_group<Keys>(tokens: &mut impl Iterator<Item = VdfToken>) -> Result<BindingDescription<Keys>>
+where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    let mut action = None;
+    let mut receivers = Vec::new();
+    let mut default_axis_values = Vec::new();
+    let mut priority = 0;
+    let mut while_held = Vec::new();
+    let mut required_modifiers = Modifiers::NONE;
+    let mut forbidden_modifiers = Modifiers::NONE;
+    let mut action_type = ActionType::Any;
+    loop {
+        let key = match tokens.next() {
+            Some(VdfToken::Str(key)) => key,
+            Some(VdfToken::Close) => break,
+            _ => bail!("unexpected end of a \"group\" section"),
+        };
+        let value = match tokens.next() {
+            Some(VdfToken::Str(value)) => value,
+            _ => bail!("unexpected value for \"{}\"", key),
+        };
+        match key.as_str() {
+            "action" => action = Some(serde_json::from_str(&value)?),
+            "priority" => priority = value.parse()?,
+            "binding" => receivers.push(serde_json::from_str(&value)?),
+            "default_axis_value" => default_axis_values.push(serde_json::from_str(&value)?),
+            "while_held" => while_held.push(serde_json::from_str(&value)?),
+            "required_modifiers" => required_modifiers = Modifiers(value.parse()?),
+            "forbidden_modifiers" => forbidden_modifiers = Modifiers(value.parse()?),
+            "action_type" => action_type = serde_json::from_str(&value)?,
+            _ => {}
+        }
+    }
+    Ok(BindingDescription {
+        action: action.ok_or_else(|| anyhow!("missing \"action\" in a \"group\" section"))?,
+        receivers,
+        default_axis_values,
+        priority,
+        while_held,
+        required_modifiers,
+        forbidden_modifiers,
+        action_type,
+    })
+}
+
+enum VdfToken {
+    Str(String),
+    Open,
+    Close,
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn tokenize(vdf: &str) -> Result<Vec<VdfToken>> {
+    let mut tokens = Vec::new();
+    let mut chars = vdf.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => value.extend(chars.next()),
+                        Some(c) => value.push(c),
+                        None => bail!("unterminated string"),
+                    }
+                }
+                tokens.push(VdfToken::Str(value));
+            }
+            c if c.is_whitespace() => {}
+            c => bail!("unexpected character '{}'", c),
+        }
+    }
+    Ok(tokens)
+}
+
+// Test to check that bindings survive a VDF round trip.
+#[test]
+fn vdf_round_trip_test() {
+    use bevy::prelude::KeyCode;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    enum Action {
+        Jump,
+        Move,
+    }
+    impl BindingTypeView for Action {}
+
+    let bindings = InputBindings {
+        version: 2,
+        bindings: vec![
+            BindingDescription {
+                action: Action::Jump,
+                receivers: vec![vec![InputReceiver::KeyboardKey(KeyCode::Space)]],
+                default_axis_values: Vec::new(),
+                priority: 2,
+                while_held: vec![Action::Move],
+                required_modifiers: Modifiers::CTRL,
+                forbidden_modifiers: Modifiers::NONE,
+                action_type: ActionType::Button,
+            },
+            BindingDescription {
+                action: Action::Move,
+                receivers: vec![vec![InputReceiver::KeyboardKey(KeyCode::A)]],
+                default_axis_values: vec![(InputReceiver::KeyboardKey(KeyCode::A), -1.)],
+                priority: 0,
+                while_held: Vec::new(),
+                required_modifiers: Modifiers::NONE,
+                forbidden_modifiers: Modifiers::SHIFT,
+                action_type: ActionType::Any,
+            },
+        ],
+    };
+    let vdf = bindings.to_vdf().unwrap();
+    assert_eq!(InputBindings::from_vdf(&vdf).unwrap(), bindings);
+}
+
+// Test to check that bindings exported as JSON load back through action aliases, including nested actions.
+#[test]
+fn json_alias_round_trip_test() {
+    use bevy::prelude::KeyCode;
+    use ezinput_macros::BindingTypeView;
+    use serde::Deserialize;
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    enum Movement {
+        Jump,
+        Crouch,
+    }
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    enum Action {
+        Pause,
+        #[action(nested)]
+        Movement(Movement),
+    }
+
+    let mut view = InputView::new();
+    view.add_binding(ActionBinding::from(Action::Pause).receivers(InputReceiver::KeyboardKey(KeyCode::P).into()));
+    view.add_binding(
+        ActionBinding::from(Action::Movement(Movement::Jump))
+            .receivers(InputReceiver::KeyboardKey(KeyCode::Space).into())
+            .while_held(Action::Movement(Movement::Crouch)),
+    );
+    let bindings = InputBindings::from_view(&view, 0);
+    let names: InputBindings<ActionName> = serde_json::from_str(&bindings.to_json().unwrap()).unwrap();
+    let mut actions: Vec<String> = names
+        .bindings
+        .iter()
+        .map(|description| description.action.0.clone())
+        .collect();
+    actions.sort();
+    assert_eq!(actions, ["Movement::Jump", "Pause"]);
+    assert_eq!(ActionAliases::new().resolve_bindings(names).unwrap(), bindings);
+}
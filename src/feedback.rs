@@ -0,0 +1,175 @@
+//! Per action feedback. The [`ActionFeedback`] resource attaches click sounds, rumble patterns and callbacks to the
+//! press and release of actions declaratively, instead of scattering them through the gameplay systems. Sounds are
+//! sent as [`ActionFeedbackEvent`]s carrying a cue name for the audio system of the application.
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct ActionFeedbackSystem;
+
+/// The edge of an action that fires feedback.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum FeedbackEdge {
+    Press,
+    Release,
+}
+
+/// A callback run when an action is pressed or released, given the entity of the input view and the action.
+pub type FeedbackCallback<Keys> = Arc<dyn Fn(&mut Commands, Entity, Keys) + Send + Sync>;
+
+/// Feedback fired when an action is pressed or released.
+#[derive(Clone)]
+pub enum Feedback<Keys> {
+    /// Send an [`ActionFeedbackEvent`] with the given cue name, usually the name of a sound.
+    Cue(String),
+    /// Play a rumble pattern on the gamepad of the input view.
+    Rumble(RumblePattern),
+    Callback(FeedbackCallback<Keys>),
+}
+
+impl<Keys> std::fmt::Debug for Feedback<Keys> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cue(cue) => f.debug_tuple("Cue").field(cue).finish(),
+            Self::Rumble(pattern) => f.debug_tuple("Rumble").field(pattern).finish(),
+            Self::Callback(_) => f.write_str("Callback"),
+        }
+    }
+}
+
+/// Event sent when an action with a [`Feedback::Cue`] is pressed or released.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ActionFeedbackEvent<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub entity: Entity,
+    pub action: Keys,
+    pub edge: FeedbackEdge,
+    pub cue: String,
+}
+
+/// The feedback of each action. Insert it as a resource to fire it for every input view.
+#[derive(Clone, Debug)]
+pub struct ActionFeedback<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub feedback: HashMap<(Keys, FeedbackEdge), Vec<Feedback<Keys>>>,
+}
+
+impl<Keys> Default for ActionFeedback<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            feedback: HashMap::default(),
+        }
+    }
+}
+
+impl<Keys> ActionFeedback<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fire feedback when an action is pressed.
+    pub fn on_press(self, action: Keys, feedback: Feedback<Keys>) -> Self {
+        self.on(action, FeedbackEdge::Press, feedback)
+    }
+
+    /// Fire feedback when an action is released.
+    pub fn on_release(self, action: Keys, feedback: Feedback<Keys>) -> Self {
+        self.on(action, FeedbackEdge::Release, feedback)
+    }
+
+    /// Fire feedback on an edge of an action.
+    pub fn on(mut self, action: Keys, edge: FeedbackEdge, feedback: Feedback<Keys>) -> Self {
+        self.feedback.entry((action, edge)).or_default().push(feedback);
+        self
+    }
+
+    /// Fire a cue when an action is pressed.
+    pub fn cue_on_press(self, action: Keys, cue: impl Into<String>) -> Self {
+        self.on_press(action, Feedback::Cue(cue.into()))
+    }
+
+    /// Run a callback when an action is pressed.
+    pub fn call_on_press(
+        self,
+        action: Keys,
+        callback: impl Fn(&mut Commands, Entity, Keys) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_press(action, Feedback::Callback(Arc::new(callback)))
+    }
+
+    /// Remove the feedback of an action.
+    pub fn clear(&mut self, action: &Keys) {
+        self.feedback.retain(|(other, _), _| other != action);
+    }
+
+    /// Returns the feedback fired on an edge of an action.
+    pub fn get(&self, action: Keys, edge: FeedbackEdge) -> &[Feedback<Keys>] {
+        self.feedback.get(&(action, edge)).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// System responsible for firing the feedback of the actions pressed or released this frame.
+pub(crate) fn action_feedback_system<Keys>(
+    mut commands: Commands,
+    feedback: Option<Res<ActionFeedback<Keys>>>,
+    mut query: Query<(Entity, &InputView<Keys>, Option<&mut GamepadMarker>)>,
+    mut pressed: Local<HashSet<(Entity, Keys)>>,
+    mut wr: EventWriter<ActionFeedbackEvent<Keys>>,
+) where
+    Keys: BindingTypeView,
+{
+    let feedback = match feedback {
+        Some(feedback) if !feedback.feedback.is_empty() => feedback,
+        _ => return,
+    };
+    let actions: HashSet<Keys> = feedback.feedback.keys().map(|(action, _)| *action).collect();
+    let mut alive = HashSet::default();
+    for (entity, view, mut marker) in query.iter_mut() {
+        alive.insert(entity);
+        for action in actions.iter() {
+            let now = view.key(action).pressed();
+            let edge = match (pressed.contains(&(entity, *action)), now) {
+                (false, true) => {
+                    pressed.insert((entity, *action));
+                    FeedbackEdge::Press
+                }
+                (true, false) => {
+                    pressed.remove(&(entity, *action));
+                    FeedbackEdge::Release
+                }
+                _ => continue,
+            };
+            for item in feedback.get(*action, edge) {
+                match item {
+                    Feedback::Cue(cue) => wr.send(ActionFeedbackEvent {
+                        entity,
+                        action: *action,
+                        edge,
+                        cue: cue.clone(),
+                    }),
+                    Feedback::Rumble(pattern) => {
+                        if let Some(marker) = marker.as_mut() {
+                            marker.play_rumble(pattern.clone());
+                        }
+                    }
+                    Feedback::Callback(callback) => callback(&mut commands, entity, *action),
+                }
+            }
+        }
+    }
+    pressed.retain(|(entity, _)| alive.contains(entity));
+}
@@ -0,0 +1,110 @@
+//! Flick detection for sticks: a stick pushed past a high threshold and released back to rest within a short
+//! window, useful for dash/tilt distinctions.
+use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct FlickSystem;
+
+/// The thresholds used to detect a flick of an action.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct FlickSettings {
+    /// The stick length the action needs to reach.
+    pub threshold: f32,
+    /// The stick length under which the stick is considered back to rest.
+    pub rest: f32,
+    /// The maximum duration between crossing the threshold and returning to rest.
+    pub window: Duration,
+}
+
+impl Default for FlickSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 0.9,
+            rest: 0.2,
+            window: Duration::from_millis(150),
+        }
+    }
+}
+
+/// Per action flick configuration and detection state.
+#[derive(PartialEq, Clone, Debug)]
+pub struct FlickTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub settings: HashMap<Keys, FlickSettings>,
+    /// The instant each action crossed the threshold, along with the furthest stick position since then.
+    pub crossed: HashMap<Keys, (Instant, Vec2)>,
+    /// The actions flicked this frame, along with the furthest stick position.
+    pub flicked: HashMap<Keys, Vec2>,
+}
+
+impl<Keys> Default for FlickTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            settings: HashMap::default(),
+            crossed: HashMap::default(),
+            flicked: HashMap::default(),
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the furthest stick position if the action was flicked this frame.
+    pub fn flicked(&self, kind: &Keys) -> Option<Vec2> {
+        self.flick.flicked.get(kind).copied()
+    }
+
+    /// Enable or disable flick detection for an action.
+    pub fn set_flick_detection(&mut self, kind: Keys, settings: Option<FlickSettings>) {
+        match settings {
+            Some(settings) => self.flick.settings.insert(kind, settings),
+            None => self.flick.settings.remove(&kind),
+        };
+        self.flick.crossed.remove(&kind);
+        self.flick.flicked.remove(&kind);
+    }
+}
+
+/// System responsible for detecting the flicks of each input view.
+pub(crate) fn flick_system<Keys>(mut query: Query<&mut InputView<Keys>>)
+where
+    Keys: BindingTypeView,
+{
+    for mut view in query.iter_mut() {
+        if view.flick.settings.is_empty() {
+            continue;
+        }
+        let mut crossed = view.flick.crossed.clone();
+        let mut flicked = HashMap::default();
+        for (action, settings) in view.flick.settings.iter() {
+            let stick = view.stick(action);
+            let length = stick.length();
+            if length >= settings.threshold {
+                let entry = crossed.entry(*action).or_insert((Instant::now(), stick));
+                if length > entry.1.length() {
+                    entry.1 = stick;
+                }
+            } else if length <= settings.rest {
+                if let Some((instant, peak)) = crossed.remove(action) {
+                    if instant.elapsed() <= settings.window {
+                        flicked.insert(*action, peak);
+                    }
+                }
+            }
+        }
+        if crossed != view.flick.crossed || flicked != view.flick.flicked {
+            view.flick.crossed = crossed;
+            view.flick.flicked = flicked;
+        }
+    }
+}
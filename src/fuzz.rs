@@ -0,0 +1,195 @@
+//! Random input for soak testing. Insert a [`FuzzInput`] on the input view entities to monkey-test and add the
+//! [`FuzzInputPlugin`]: random presses and axis values of their receivers are injected at the given rate. The
+//! generator is seeded and driven by the frame count instead of the clock, so a crash found by a soak test can be
+//! reproduced with the same seed, whatever the frame rate.
+use std::marker::PhantomData;
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct FuzzInputSystem;
+
+/// Injects random input into the input view of its entity.
+#[derive(PartialEq, Clone, Debug, Component)]
+pub struct FuzzInput {
+    pub enabled: bool,
+    /// The average number of presses per second, of [`FuzzInput::frame_time`] frames.
+    pub rate: f32,
+    /// The longest time a receiver is held, rounded to frames.
+    pub max_hold: Duration,
+    /// The nominal duration of a frame, converting the rate and hold time to frames.
+    pub frame_time: Duration,
+    /// The number of frames advanced.
+    pub frame: u64,
+    /// The receivers to press. The receivers bound to the actions of the view are pressed if empty.
+    pub receivers: Vec<InputReceiver>,
+    /// The state of the random generator.
+    pub state: u64,
+    /// The receivers held by the fuzzer and the frame in which they are released.
+    pub held: Vec<(InputReceiver, u64)>,
+}
+
+impl FuzzInput {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            enabled: true,
+            rate: 10.,
+            max_hold: Duration::from_millis(500),
+            frame_time: Duration::from_secs(1) / 60,
+            frame: 0,
+            receivers: Vec::new(),
+            // The generator gets stuck at zero.
+            state: seed.max(1),
+            held: Vec::new(),
+        }
+    }
+
+    pub fn with_rate(mut self, rate: f32) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    pub fn with_max_hold(mut self, max_hold: Duration) -> Self {
+        self.max_hold = max_hold;
+        self
+    }
+
+    /// Only press the given receivers.
+    pub fn with_receivers(mut self, receivers: impl IntoIterator<Item = InputReceiver>) -> Self {
+        self.receivers = receivers.into_iter().collect();
+        self
+    }
+
+    /// Returns the next random number, using xorshift.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Returns a random number from 0 to 1.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Advance by a frame, returning the receiver values to inject and picking the pressed receivers from the
+    /// candidates.
+    pub fn advance(&mut self, candidates: &[InputReceiver]) -> Vec<(InputReceiver, f32)> {
+        self.frame += 1;
+        let now = self.frame;
+        let mut values: Vec<(InputReceiver, f32)> = Vec::new();
+        self.held.retain(|(receiver, until)| {
+            let held = now < *until;
+            if !held {
+                values.push((*receiver, 0.));
+            }
+            held
+        });
+        if !self.enabled || candidates.is_empty() {
+            return values;
+        }
+        let frame_time = self.frame_time.as_secs_f32();
+        let mut presses = self.rate * frame_time;
+        while presses > 0. {
+            // The fractional part of the expected presses is a probability.
+            if presses < 1. && self.next_f32() >= presses {
+                break;
+            }
+            presses -= 1.;
+            let receiver = candidates[self.next_u64() as usize % candidates.len()];
+            if self.held.iter().any(|(held, _)| *held == receiver) {
+                continue;
+            }
+            let value = if receiver.is_axis() {
+                self.next_f32() * 2. - 1.
+            } else {
+                1.
+            };
+            let hold = (self.max_hold.as_secs_f32() / frame_time * self.next_f32()).round() as u64;
+            self.held.push((receiver, now + hold.max(1)));
+            values.push((receiver, value));
+        }
+        values
+    }
+}
+
+/// A [`Plugin`] injecting random input into the views with a [`FuzzInput`].
+pub struct FuzzInputPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    phantom: PhantomData<Keys>,
+}
+
+impl<Keys> Default for FuzzInputPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self { phantom: PhantomData }
+    }
+}
+
+impl<Keys> Plugin for FuzzInputPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            fuzz_input_system::<Keys>
+                .label(FuzzInputSystem)
+                .label(EZInputSet::Collect)
+                .after(InputSystem)
+                .before(InjectedInputHandlingSystem),
+        );
+    }
+}
+
+/// System responsible for injecting the random input of every [`FuzzInput`].
+pub(crate) fn fuzz_input_system<Keys>(
+    mut query: Query<(Entity, &InputView<Keys>, &mut FuzzInput)>,
+    mut injected: EventWriter<InjectedInput>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, view, mut fuzz) in query.iter_mut() {
+        if !fuzz.enabled && fuzz.held.is_empty() {
+            continue;
+        }
+        let candidates: Vec<InputReceiver> = if fuzz.receivers.is_empty() {
+            let mut bound: Vec<InputReceiver> = view.action_index.keys().copied().collect();
+            // Sorted so the same seed presses the same receivers, whatever the hashing of the index.
+            bound.sort_by_cached_key(|receiver| format!("{:?}", receiver));
+            bound
+        } else {
+            fuzz.receivers.clone()
+        };
+        let values = fuzz.advance(&candidates);
+        injected.send_batch(values.into_iter().map(|(receiver, value)| InjectedInput {
+            entity: Some(entity),
+            receiver,
+            value,
+            pressed: value != 0.,
+        }));
+    }
+}
+
+// Test to check that the same seed injects the same input.
+#[test]
+fn fuzz_input_test() {
+    use bevy::prelude::KeyCode;
+
+    let candidates = [KeyCode::A, KeyCode::B, KeyCode::C].map(InputReceiver::KeyboardKey);
+    let mut first = FuzzInput::new(7).with_rate(30.);
+    let mut second = FuzzInput::new(7).with_rate(30.);
+    for _ in 0..120 {
+        assert_eq!(first.advance(&candidates), second.advance(&candidates));
+    }
+    assert_eq!(first.frame, 120);
+}
@@ -1,16 +1,295 @@
 //! Full gamepad support for EZInput.
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::{GamepadAxis, GamepadButton};
 use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap, HashSet, Instant};
+use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
 
 #[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
 pub struct GamepadInputHandlingSystem;
 
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct GamepadSyncSystem;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct GamepadSamplingSystem;
+
+/// Every gamepad button known by Bevy, used when polling the gamepad resources.
+pub const GAMEPAD_BUTTONS: [GamepadButtonType; 19] = [
+    GamepadButtonType::South,
+    GamepadButtonType::East,
+    GamepadButtonType::North,
+    GamepadButtonType::West,
+    GamepadButtonType::C,
+    GamepadButtonType::Z,
+    GamepadButtonType::LeftTrigger,
+    GamepadButtonType::LeftTrigger2,
+    GamepadButtonType::RightTrigger,
+    GamepadButtonType::RightTrigger2,
+    GamepadButtonType::Select,
+    GamepadButtonType::Start,
+    GamepadButtonType::Mode,
+    GamepadButtonType::LeftThumb,
+    GamepadButtonType::RightThumb,
+    GamepadButtonType::DPadUp,
+    GamepadButtonType::DPadDown,
+    GamepadButtonType::DPadLeft,
+    GamepadButtonType::DPadRight,
+];
+
+/// Every gamepad axis known by Bevy, used when polling the gamepad resources.
+pub const GAMEPAD_AXES: [GamepadAxisType; 8] = [
+    GamepadAxisType::LeftStickX,
+    GamepadAxisType::LeftStickY,
+    GamepadAxisType::LeftZ,
+    GamepadAxisType::RightStickX,
+    GamepadAxisType::RightStickY,
+    GamepadAxisType::RightZ,
+    GamepadAxisType::DPadX,
+    GamepadAxisType::DPadY,
+];
+
+/// The axes of the synthetic d-pad stick, fed by the d-pad buttons. Bind `(DPadAxisType::X, DPadAxisType::Y)` next
+/// to the left stick axes to move with either of them using a single binding.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum DPadAxisType {
+    /// Right is positive.
+    X,
+    /// Up is positive.
+    Y,
+}
+
+impl DPadAxisType {
+    /// Returns the d-pad axis moved by a d-pad button.
+    pub fn from_button(button: GamepadButtonType) -> Option<DPadAxisType> {
+        match button {
+            GamepadButtonType::DPadLeft | GamepadButtonType::DPadRight => Some(DPadAxisType::X),
+            GamepadButtonType::DPadDown | GamepadButtonType::DPadUp => Some(DPadAxisType::Y),
+            _ => None,
+        }
+    }
+
+    /// Returns the d-pad buttons in the negative and positive directions of the axis.
+    pub fn buttons(&self) -> (GamepadButtonType, GamepadButtonType) {
+        match self {
+            DPadAxisType::X => (GamepadButtonType::DPadLeft, GamepadButtonType::DPadRight),
+            DPadAxisType::Y => (GamepadButtonType::DPadDown, GamepadButtonType::DPadUp),
+        }
+    }
+}
+
+/// The shape of the dead zone applied to the two axes of a stick.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, Default)]
+pub enum DeadZoneShape {
+    /// Each axis has its own dead zone, snapping the stick to the cardinal directions near the center.
+    #[default]
+    Axial,
+    /// The stick is ignored inside a circle whose radius is the x dead zone, keeping the stick direction intact.
+    Radial,
+    /// A small radial dead zone followed by an axial dead zone growing with the other axis, easing the cardinal
+    /// directions without snapping the diagonals.
+    Bowtie,
+}
+
+impl DeadZoneShape {
+    /// Apply the dead zone to a stick position, saturating it at the outer dead zone. If `rescale` is set, the
+    /// remaining range is rescaled so that the stick still goes from 0 at the inner dead zone to 1 at the outer dead
+    /// zone.
+    pub fn apply(&self, stick: Vec2, inner: Vec2, outer: f32, rescale: bool) -> Vec2 {
+        match self {
+            DeadZoneShape::Axial => Vec2::new(
+                stick.x.signum() * scale(stick.x.abs(), inner.x, outer, rescale),
+                stick.y.signum() * scale(stick.y.abs(), inner.y, outer, rescale),
+            ),
+            DeadZoneShape::Radial => {
+                let length = stick.length();
+                if length <= inner.x {
+                    Vec2::ZERO
+                } else {
+                    stick / length * scale(length, inner.x, outer, rescale)
+                }
+            }
+            DeadZoneShape::Bowtie => {
+                let radius = inner.min_element() * 0.5;
+                if stick.length() <= radius {
+                    return Vec2::ZERO;
+                }
+                let inner = inner * Vec2::new(stick.y.abs(), stick.x.abs());
+                DeadZoneShape::Axial.apply(stick, inner, outer, rescale)
+            }
+        }
+    }
+}
+
+/// Maps the magnitude of an axis to 0 inside the inner dead zone and to 1 past the outer dead zone, rescaling
+/// `inner..outer` to `0..1` if `rescale` is set.
+fn scale(value: f32, inner: f32, outer: f32, rescale: bool) -> f32 {
+    if value <= inner {
+        0.
+    } else if value >= outer {
+        1.
+    } else if rescale {
+        (value - inner) / (outer - inner)
+    } else {
+        value
+    }
+}
+
+/// Returns the X and Y axes of the stick the given axis belongs to, if any.
+fn stick_axes(axis: GamepadAxisType) -> Option<(GamepadAxisType, GamepadAxisType)> {
+    match axis {
+        GamepadAxisType::LeftStickX | GamepadAxisType::LeftStickY => {
+            Some((GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY))
+        }
+        GamepadAxisType::RightStickX | GamepadAxisType::RightStickY => {
+            Some((GamepadAxisType::RightStickX, GamepadAxisType::RightStickY))
+        }
+        _ => None,
+    }
+}
+
+/// A running stick calibration, sampling the resting value of every stick axis.
+#[derive(PartialEq, Clone, Debug)]
+pub struct StickCalibration {
+    pub started: Instant,
+    pub duration: Duration,
+    /// The sum and the number of the samples of each stick axis.
+    pub samples: HashMap<GamepadAxisType, (f32, u32)>,
+}
+
+/// Event sent when the stick calibration of a [`GamepadMarker`] finishes.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GamepadCalibrated {
+    pub entity: Entity,
+    pub gamepad: Gamepad,
+}
+
 // Marker responsible for allowing systems to listen to gamepad input.
 #[derive(PartialEq, Debug, Component, Clone)]
 pub struct GamepadMarker {
     pub gamepad: Gamepad,
+    /// The inner dead zone of the sticks for each axis.
     pub dead_zone: Vec2,
+    /// The shape of the inner dead zone of the sticks.
+    pub dead_zone_shape: DeadZoneShape,
+    /// The value at which an axis saturates to 1. Useful for worn sticks that can't reach the edge anymore.
+    pub outer_dead_zone: f32,
+    /// Whether the range left between the inner and outer dead zones is rescaled to `0..1`. Disabled by default, in
+    /// which case the values outside the dead zones are passed through and the axial dead zone ignores the values
+    /// inside it, as [`GamepadMarker::is_ignored`].
+    pub rescale_dead_zone: bool,
+    /// The last raw value of each stick axis, needed to apply the dead zone to both axes of a stick.
+    pub raw_axes: HashMap<GamepadAxisType, f32>,
+    /// The resting value of each stick axis, subtracted before applying the dead zone. Set by the calibration.
+    pub center_offsets: HashMap<GamepadAxisType, f32>,
+    /// The running stick calibration, if any.
+    pub calibration: Option<StickCalibration>,
+    pub motion: MotionSettings,
+    pub connected: bool,
+    /// The battery status, reported through the [`GamepadBatteryStatus`] resource.
+    pub battery: GamepadBattery,
+    pub mapping: Option<GamepadMapping>,
+    pub trigger_rumble: HashMap<GamepadButtonType, TriggerRumble>,
+    /// The thresholds at which the buttons and axes are pressed and released.
+    pub press_thresholds: PressThresholds,
+    /// The thresholds of specific buttons or axes, overriding the thresholds above.
+    pub element_thresholds: HashMap<GamepadElement, PressThresholds>,
+    /// The rumble pattern being played, if any.
+    pub rumble: Option<PlayingRumble>,
+    /// The LED color set with [`GamepadMarker::set_led`], if any.
+    pub led: Option<Color>,
+    /// Whether the LED color needs to be sent to the gamepad backend.
+    pub led_pending: bool,
+    /// The adaptive trigger effects set with [`GamepadMarker::set_trigger_effect`].
+    pub trigger_effects: HashMap<GamepadButtonType, TriggerEffect>,
+    /// The triggers whose effect needs to be sent to the gamepad backend.
+    pub pending_trigger_effects: HashSet<GamepadButtonType>,
+}
+
+/// How often the gamepad state is sampled into the input views.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum GamepadSamplingMode {
+    /// Apply the gamepad events and poll the gamepad state every frame.
+    #[default]
+    EveryFrame,
+    /// Apply the gamepad events and poll the gamepad state at most the given number of times per second. The polling
+    /// catches up with the events of the skipped frames, except for presses shorter than the sampling interval.
+    FixedRate(f32),
+    /// Only apply the gamepad events, without polling the gamepad state. The cheapest mode, but a view can desync
+    /// when an event is missed.
+    OnEvent,
+}
+
+/// The gamepad sampling configuration, set with [`EZInputPlugin::with_gamepad_sampling`].
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct GamepadSampling {
+    pub mode: GamepadSamplingMode,
+    /// The instant of the last sample, if any.
+    pub last_sample: Option<Instant>,
+    /// Whether the gamepad state is sampled this frame.
+    pub due: bool,
+}
+
+impl GamepadSampling {
+    pub fn new(mode: GamepadSamplingMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Decide whether the gamepad state is sampled in the frame starting at the given instant.
+    pub fn update(&mut self, now: Instant) {
+        self.due = match self.mode {
+            GamepadSamplingMode::FixedRate(rate) if rate > 0. => self
+                .last_sample
+                .is_none_or(|last| now.duration_since(last).as_secs_f32() >= 1. / rate),
+            _ => true,
+        };
+        if self.due {
+            self.last_sample = Some(now);
+        }
+    }
+
+    /// Returns whether the gamepad state is polled this frame.
+    pub fn polls(&self) -> bool {
+        self.due && self.mode != GamepadSamplingMode::OnEvent
+    }
+}
+
+/// The values at which a button or axis is pressed and released. Releasing under a lower value than the press avoids
+/// the press state flickering when the value hovers around a single threshold.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct PressThresholds {
+    /// The value a released element needs to exceed to be pressed.
+    pub press: f32,
+    /// The value a pressed element needs to fall to, or under, to be released.
+    pub release: f32,
+}
+
+impl Default for PressThresholds {
+    fn default() -> Self {
+        Self {
+            press: 0.1,
+            release: 0.05,
+        }
+    }
+}
+
+impl PressThresholds {
+    /// Returns the press state for a value, given whether the element is currently pressed.
+    pub fn press_state(&self, value: f32, pressed: bool) -> PressState {
+        let threshold = if pressed { self.release } else { self.press };
+        if value.abs() <= threshold {
+            PressState::Released
+        } else {
+            PressState::Pressed {
+                started_pressing_instant: None,
+            }
+        }
+    }
 }
 
 impl Default for GamepadMarker {
@@ -24,101 +303,550 @@ impl GamepadMarker {
         Self {
             gamepad: Gamepad(id),
             dead_zone: Vec2::ZERO,
+            dead_zone_shape: DeadZoneShape::Axial,
+            outer_dead_zone: 1.,
+            rescale_dead_zone: false,
+            raw_axes: HashMap::default(),
+            center_offsets: HashMap::default(),
+            calibration: None,
+            motion: MotionSettings::default(),
+            connected: false,
+            battery: GamepadBattery::Unknown,
+            mapping: None,
+            trigger_rumble: HashMap::default(),
+            press_thresholds: PressThresholds::default(),
+            element_thresholds: HashMap::default(),
+            rumble: None,
+            led: None,
+            led_pending: false,
+            trigger_effects: HashMap::default(),
+            pending_trigger_effects: HashSet::default(),
         }
     }
     pub fn with_dead_zone(id: usize, dead_zone: (f32, f32)) -> Self {
         Self {
-            gamepad: Gamepad(id),
             dead_zone: Vec2::new(dead_zone.0, dead_zone.1),
+            ..Self::with_id(id)
+        }
+    }
+    /// Creates a marker with the given dead zone shape, inner dead zone and outer dead zone.
+    pub fn with_dead_zone_shape(id: usize, shape: DeadZoneShape, inner: Vec2, outer: f32) -> Self {
+        Self {
+            dead_zone: inner,
+            dead_zone_shape: shape,
+            outer_dead_zone: outer,
+            ..Self::with_id(id)
         }
     }
+    pub fn with_mapping(id: usize, mapping: GamepadMapping) -> Self {
+        Self {
+            mapping: Some(mapping),
+            ..Self::with_id(id)
+        }
+    }
+}
+
+/// Event sent when the gamepad of a [`GamepadMarker`] is connected or disconnected, so games can pause.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct GamepadConnectionEvent {
+    pub entity: Entity,
+    pub gamepad: Gamepad,
+    pub connected: bool,
 }
 
 impl GamepadMarker {
+    /// Returns whether the gamepad is currently connected.
+    pub fn connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Change the connection status of the gamepad. When disconnected, all gamepad receivers are released.
+    pub fn set_connected<S>(&mut self, view: &mut S, connected: bool)
+    where
+        S: InputSink,
+    {
+        if connected && !self.connected {
+            self.led_pending = self.led.is_some();
+            self.pending_trigger_effects = self.trigger_effects.keys().copied().collect();
+        }
+        self.connected = connected;
+        if !connected {
+            view.release_source(InputSource::Gamepad);
+        }
+    }
+
+    /// Returns the standard element for a button or axis reported by Bevy, applying the remaps between standard
+    /// elements of the gamepad mapping if any.
+    pub fn remap(&self, element: GamepadElement) -> Option<GamepadElement> {
+        match self.mapping {
+            Some(ref mapping) => mapping.remap(element),
+            None => Some(element),
+        }
+    }
+
+    /// Returns the press state for a raw button or axis value, without hysteresis.
+    pub fn press_state(&self, value: f32) -> PressState {
+        if value.abs() <= 0.1 {
+            PressState::Released
+        } else {
+            PressState::Pressed {
+                started_pressing_instant: None,
+            }
+        }
+    }
+
+    /// Returns the press thresholds of a button or axis.
+    pub fn thresholds(&self, element: GamepadElement) -> PressThresholds {
+        self.element_thresholds
+            .get(&element)
+            .copied()
+            .unwrap_or(self.press_thresholds)
+    }
+
+    /// Set the press thresholds of a specific button or axis, or reset them to the thresholds of the gamepad if none.
+    pub fn set_thresholds(&mut self, element: GamepadElement, thresholds: Option<PressThresholds>) {
+        match thresholds {
+            Some(thresholds) => self.element_thresholds.insert(element, thresholds),
+            None => self.element_thresholds.remove(&element),
+        };
+    }
+
+    /// Returns the press state for a processed button or axis value, applying the hysteresis of its thresholds
+    /// depending on whether it's currently pressed in the view.
+    pub fn element_press_state<S>(&self, view: &S, element: GamepadElement, value: f32) -> PressState
+    where
+        S: InputSink,
+    {
+        let pressed = view.receiver_state(&element.into()).press.pressed();
+        self.thresholds(element).press_state(value, pressed)
+    }
+
+    /// Returns whether the given axis value is inside the inner dead zone, ignoring the other axis of the stick.
+    pub fn is_in_dead_zone(&self, axis: GamepadAxisType, value: f32) -> bool {
+        match axis {
+            GamepadAxisType::LeftStickX | GamepadAxisType::RightStickX => {
+                value.abs() < self.dead_zone.x
+            }
+            GamepadAxisType::LeftStickY | GamepadAxisType::RightStickY => {
+                value.abs() < self.dead_zone.y
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether a raw value of a standard button or axis is ignored because it presses the axis inside its
+    /// axial dead zone, leaving the axis at its last value.
+    pub fn is_ignored(&self, element: GamepadElement, value: f32) -> bool {
+        match element {
+            GamepadElement::Axis(kind) => self.press_state(value).pressed() && self.is_in_dead_zone(kind, value),
+            GamepadElement::Button(_) => false,
+        }
+    }
+
+    /// Apply the dead zones to a raw value of a standard button or axis, returning every element to update. Moving
+    /// one axis of a stick updates both of its axes, since the dead zone shape depends on the whole stick, unless
+    /// the dead zone is axial and not rescaled.
+    pub fn process_element(&mut self, element: GamepadElement, value: f32) -> Vec<(GamepadElement, f32)> {
+        let kind = match element {
+            GamepadElement::Button(_) => return vec![(element, value)],
+            GamepadElement::Axis(kind) => kind,
+        };
+        let (x, y) = match stick_axes(kind) {
+            Some(axes) => axes,
+            None => {
+                let value = value.signum() * scale(value.abs(), 0., self.outer_dead_zone, self.rescale_dead_zone);
+                return vec![(element, value)];
+            }
+        };
+        if let Some(ref mut calibration) = self.calibration {
+            let (sum, count) = calibration.samples.entry(kind).or_default();
+            *sum += value;
+            *count += 1;
+        }
+        let offset = self.center_offsets.get(&kind).copied().unwrap_or_default();
+        let value = (value - offset).clamp(-1., 1.);
+        self.raw_axes.insert(kind, value);
+        if self.dead_zone_shape == DeadZoneShape::Axial && !self.rescale_dead_zone {
+            if self.is_ignored(element, value) {
+                return Vec::new();
+            }
+            let value = value.signum() * scale(value.abs(), 0., self.outer_dead_zone, false);
+            return vec![(element, value)];
+        }
+        let raw = Vec2::new(
+            self.raw_axes.get(&x).copied().unwrap_or_default(),
+            self.raw_axes.get(&y).copied().unwrap_or_default(),
+        );
+        let stick = self
+            .dead_zone_shape
+            .apply(raw, self.dead_zone, self.outer_dead_zone, self.rescale_dead_zone);
+        vec![
+            (GamepadElement::Axis(x), stick.x),
+            (GamepadElement::Axis(y), stick.y),
+        ]
+    }
+
+    /// Start sampling the resting value of the sticks for the given duration, replacing the center offsets once done.
+    /// The sticks shouldn't be touched meanwhile.
+    pub fn recalibrate(&mut self, duration: Duration) {
+        self.calibration = Some(StickCalibration {
+            started: Instant::now(),
+            duration,
+            samples: HashMap::default(),
+        });
+    }
+
+    /// Returns whether a stick calibration is running.
+    pub fn is_calibrating(&self) -> bool {
+        self.calibration.is_some()
+    }
+
+    /// Finish the running calibration if its duration elapsed, storing the average resting value of each sampled
+    /// axis as its center offset. Returns whether the calibration finished.
+    pub fn finish_calibration(&mut self, now: Instant) -> bool {
+        match self.calibration {
+            Some(ref calibration) if now.duration_since(calibration.started) >= calibration.duration => {}
+            _ => return false,
+        }
+        if let Some(calibration) = self.calibration.take() {
+            self.center_offsets = calibration
+                .samples
+                .into_iter()
+                .filter(|(_, (_, count))| *count > 0)
+                .map(|(kind, (sum, count))| (kind, sum / count as f32))
+                .collect();
+        }
+        true
+    }
+
     /// Change the current button state for the given button and set the last input source to Gamepad.
-    pub fn set_gamepad_button_state<Keys>(
+    pub fn set_gamepad_button_state<S>(
         &mut self,
-        view: &mut InputView<Keys>,
+        view: &mut S,
         button: GamepadButtonType,
         state: PressState,
         duration: f32,
     ) where
-        Keys: BindingTypeView,
+        S: InputSink,
     {
-        view.last_input_source = Some(InputSource::Gamepad);
+        view.set_input_source(InputSource::Gamepad);
         view.set_axis_value(InputReceiver::GamepadButton(button), duration, state);
+        if let Some(axis) = DPadAxisType::from_button(button) {
+            Self::set_dpad_axis_state(view, axis);
+        }
+    }
+
+    /// Update a d-pad axis from the d-pad buttons, keeping the pressing instant while it stays pressed.
+    fn set_dpad_axis_state<S>(view: &mut S, axis: DPadAxisType)
+    where
+        S: InputSink,
+    {
+        let (negative, positive) = axis.buttons();
+        let value = |button| view.receiver_state(&InputReceiver::GamepadButton(button)).value.clamp(0., 1.);
+        let value = value(positive) - value(negative);
+        let receiver = InputReceiver::DPadAxis(axis);
+        let previous = view.receiver_state(&receiver).press;
+        let state = match (value != 0., previous.pressed()) {
+            (true, true) => previous,
+            (true, false) => PressState::Pressed {
+                started_pressing_instant: None,
+            },
+            (false, _) => PressState::Released,
+        };
+        view.set_axis_value(receiver, value, state);
     }
 
     /// Change the current axis state for the given axis and set the last input source to Gamepad.
-    pub fn set_gamepad_axis_state<Keys>(
+    pub fn set_gamepad_axis_state<S>(
         &mut self,
-        view: &mut InputView<Keys>,
+        view: &mut S,
         axis: GamepadAxisType,
         state: PressState,
         duration: f32,
     ) where
-        Keys: BindingTypeView,
+        S: InputSink,
     {
-        view.last_input_source = Some(InputSource::Gamepad);
+        view.set_input_source(InputSource::Gamepad);
         view.set_axis_value(InputReceiver::GamepadAxis(axis), duration, state);
     }
 }
 
-/// Input system responsible for handling gamepad input and setting the button state for each updated button and axis.
+impl GamepadMarker {
+    /// Returns the press state needed to reconcile a standard button or axis with a polled value, keeping the
+    /// pressing instant. Returns none if nothing changed.
+    pub fn synced_press_state<S>(&self, view: &S, element: GamepadElement, value: f32) -> Option<PressState>
+    where
+        S: InputSink,
+    {
+        let press = self.element_press_state(view, element, value);
+        let state = view.receiver_state(&element.into());
+        if state.press.pressed() == press.pressed() && state.value == value {
+            return None;
+        }
+        if state.press.pressed() && press.pressed() {
+            Some(state.press)
+        } else {
+            Some(press)
+        }
+    }
+
+    /// Change the current state for the given standard button or axis and set the last input source to Gamepad.
+    pub fn set_gamepad_element_state<S>(
+        &mut self,
+        view: &mut S,
+        element: GamepadElement,
+        state: PressState,
+        value: f32,
+    ) where
+        S: InputSink,
+    {
+        match element {
+            GamepadElement::Button(kind) => self.set_gamepad_button_state(view, kind, state, value),
+            GamepadElement::Axis(kind) => self.set_gamepad_axis_state(view, kind, state, value),
+        }
+    }
+}
+
+/// The gamepad events of a frame, coalesced by [`coalesce_gamepad_events`].
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct CoalescedGamepadEvents {
+    /// The connection changes, in order.
+    pub connections: Vec<(Gamepad, bool)>,
+    /// The button and axis changes of each gamepad.
+    pub changes: HashMap<Gamepad, Vec<(GamepadElement, f32)>>,
+}
+
+/// Coalesce the gamepad events of a frame, keeping only the latest value of each axis and the last value of each
+/// button press or release, so high-rate pads sending hundreds of events per frame are applied in a single pass.
+pub fn coalesce_gamepad_events<'a>(events: impl Iterator<Item = &'a GamepadEvent>) -> CoalescedGamepadEvents {
+    let mut connections = Vec::new();
+    let mut changes: HashMap<Gamepad, Vec<(GamepadElement, f32)>> = HashMap::default();
+    for ev in events {
+        let (element, value) = match ev.1 {
+            GamepadEventType::Connected => {
+                connections.push((ev.0, true));
+                continue;
+            }
+            GamepadEventType::Disconnected => {
+                connections.push((ev.0, false));
+                continue;
+            }
+            GamepadEventType::ButtonChanged(kind, value) => (GamepadElement::Button(kind), value),
+            GamepadEventType::AxisChanged(kind, value) => (GamepadElement::Axis(kind), value),
+        };
+        let changes = changes.entry(ev.0).or_default();
+        let last = changes.iter().rposition(|(other, _)| *other == element);
+        match (element, last) {
+            (GamepadElement::Button(_), Some(last)) if (changes[last].1 > 0.) != (value > 0.) => {
+                changes.push((element, value))
+            }
+            (_, Some(last)) => changes[last].1 = value,
+            (_, None) => changes.push((element, value)),
+        }
+    }
+    CoalescedGamepadEvents {
+        connections,
+        changes,
+    }
+}
+
+/// Input system responsible for handling gamepad input and staging the button state for each updated button and axis.
+#[allow(clippy::type_complexity)]
 pub(crate) fn gamepad_input_system<Keys>(
-    mut query: Query<(&mut InputView<Keys>, &mut GamepadMarker)>,
+    mut query: Query<(
+        Entity,
+        &InputView<Keys>,
+        &mut GamepadMarker,
+        &mut StagedInput<Keys, GamepadMarker>,
+    )>,
     mut rd: EventReader<GamepadEvent>,
+    mut wr: EventWriter<GamepadConnectionEvent>,
+    sampling: Res<GamepadSampling>,
 ) where
     Keys: BindingTypeView,
 {
-    for ev in rd.iter() {
-        match ev.1 {
-            GamepadEventType::ButtonChanged(kind, duration) => {
-                for (mut view, mut svc) in query.iter_mut() {
-                    if ev.0 != svc.gamepad {
-                        continue;
-                    }
-                    let state = if duration.abs() <= 0.1 {
-                        PressState::Released
-                    } else {
-                        PressState::Pressed {
-                            started_pressing_instant: None,
-                        }
-                    };
-                    svc.set_gamepad_button_state::<Keys>(view.as_mut(), kind, state, duration);
-                    break;
+    let CoalescedGamepadEvents {
+        connections,
+        mut changes,
+    } = coalesce_gamepad_events(rd.iter());
+    for (gamepad, connected) in connections {
+        for (entity, view, mut svc, mut staged) in query.iter_mut() {
+            if gamepad != svc.gamepad || svc.connected == connected {
+                continue;
+            }
+            svc.set_connected(&mut StagedView::new(view, &mut staged), connected);
+            wr.send(GamepadConnectionEvent {
+                entity,
+                gamepad,
+                connected,
+            });
+        }
+    }
+    // The changes of the skipped frames are caught up by the polling of the next sample.
+    if changes.is_empty() || !sampling.due {
+        return;
+    }
+    for (_, view, mut svc, mut staged) in query.iter_mut() {
+        // Only the first entity of each gamepad handles its changes.
+        let changes = match changes.remove(&svc.gamepad) {
+            Some(changes) => changes,
+            None => continue,
+        };
+        let view = &mut StagedView::new(view, &mut staged);
+        for (element, value) in changes {
+            if let Some(element) = svc.remap(element) {
+                for (element, value) in svc.process_element(element, value) {
+                    let state = svc.element_press_state(view, element, value);
+                    svc.set_gamepad_element_state(view, element, state, value);
                 }
             }
-            GamepadEventType::AxisChanged(kind, value) => {
-                for (mut view, mut svc) in query.iter_mut() {
-                    if ev.0 != svc.gamepad {
-                        continue;
-                    }
-                    let state = if value.abs() <= 0.1 {
-                        PressState::Released
-                    } else {
-                        PressState::Pressed {
-                            started_pressing_instant: None,
+        }
+    }
+}
+
+/// System responsible for deciding whether the gamepad state is sampled this frame.
+pub(crate) fn gamepad_sampling_system(mut sampling: ResMut<GamepadSampling>) {
+    sampling.update(Instant::now());
+}
+
+/// The events sent by the [`gamepad_sync_system`].
+#[derive(SystemParam)]
+pub(crate) struct GamepadSyncEvents<'w, 's> {
+    connections: EventWriter<'w, 's, GamepadConnectionEvent>,
+    calibrated: EventWriter<'w, 's, GamepadCalibrated>,
+}
+
+/// Polling fallback that reconciles every input view with the gamepad resources maintained by Bevy, so a view
+/// never desyncs when an event is missed (e.g. the entity was spawned mid-frame).
+#[allow(clippy::type_complexity)]
+pub(crate) fn gamepad_sync_system<Keys>(
+    mut query: Query<(
+        Entity,
+        &InputView<Keys>,
+        &mut GamepadMarker,
+        &mut StagedInput<Keys, GamepadMarker>,
+    )>,
+    gamepads: Res<Gamepads>,
+    compat: Res<BrowserInputCompat>,
+    buttons: Res<Axis<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    sampling: Res<GamepadSampling>,
+    mut events: GamepadSyncEvents,
+) where
+    Keys: BindingTypeView,
+{
+    let now = Instant::now();
+    for (entity, view, mut svc, mut staged) in query.iter_mut() {
+        let view = &mut StagedView::new(view, &mut staged);
+        if svc.is_calibrating() && svc.finish_calibration(now) {
+            events.calibrated.send(GamepadCalibrated {
+                entity,
+                gamepad: svc.gamepad,
+            });
+        }
+        let connected = gamepads.contains(&svc.gamepad)
+            || (compat.poll_gamepads
+                && GAMEPAD_BUTTONS
+                    .iter()
+                    .any(|kind| buttons.get(GamepadButton(svc.gamepad, *kind)).is_some()));
+        if svc.connected != connected {
+            let gamepad = svc.gamepad;
+            svc.set_connected(view, connected);
+            events.connections.send(GamepadConnectionEvent {
+                entity,
+                gamepad,
+                connected,
+            });
+        }
+        if !connected || !sampling.polls() {
+            continue;
+        }
+        for kind in GAMEPAD_BUTTONS {
+            if let Some(value) = buttons.get(GamepadButton(svc.gamepad, kind)) {
+                if let Some(element) = svc.remap(GamepadElement::Button(kind)) {
+                    for (element, value) in svc.process_element(element, value) {
+                        if let Some(state) = svc.synced_press_state(view, element, value) {
+                            svc.set_gamepad_element_state(view, element, state, value);
                         }
-                    };
-                    if state.pressed()
-                        && match kind {
-                            GamepadAxisType::LeftStickX | GamepadAxisType::RightStickX => {
-                                value.abs() < svc.dead_zone.x
-                            }
-                            GamepadAxisType::LeftStickY | GamepadAxisType::RightStickY => {
-                                value.abs() < svc.dead_zone.y
-                            }
-                            _ => false,
+                    }
+                }
+            }
+        }
+        for kind in GAMEPAD_AXES {
+            if let Some(value) = axes.get(GamepadAxis(svc.gamepad, kind)) {
+                if let Some(element) = svc.remap(GamepadElement::Axis(kind)) {
+                    for (element, value) in svc.process_element(element, value) {
+                        if let Some(state) = svc.synced_press_state(view, element, value) {
+                            svc.set_gamepad_element_state(view, element, state, value);
                         }
-                    {
-                        continue;
-                    };
-                    svc.set_gamepad_axis_state::<Keys>(view.as_mut(), kind, state, value);
-                    break;
+                    }
                 }
             }
-            _ => {}
         }
     }
 }
+
+// Test to check the dead zone shapes and the outer dead zone.
+#[test]
+fn dead_zone_shape_test() {
+    let inner = Vec2::splat(0.2);
+    let diagonal = Vec2::new(0.15, 0.15);
+    assert_eq!(DeadZoneShape::Axial.apply(diagonal, inner, 1., true), Vec2::ZERO);
+    assert!(DeadZoneShape::Radial.apply(diagonal, inner, 1., true).x > 0.);
+    let cardinal = DeadZoneShape::Bowtie.apply(Vec2::new(0.05, 0.9), inner, 1., true);
+    assert_eq!(cardinal.x, 0.);
+    assert!((cardinal.y - 0.89 / 0.99).abs() < 1e-6);
+    let saturated = DeadZoneShape::Radial.apply(Vec2::new(0.9, 0.), inner, 0.9, true);
+    assert_eq!(saturated, Vec2::new(1., 0.));
+    assert_eq!(DeadZoneShape::Axial.apply(Vec2::new(0.5, 0.1), inner, 1., false), Vec2::new(0.5, 0.));
+
+    let stick = GamepadElement::Axis(GamepadAxisType::LeftStickX);
+    let mut marker = GamepadMarker::with_dead_zone(0, (0.2, 0.2));
+    assert!(marker.process_element(stick, 0.15).is_empty());
+    assert_eq!(marker.process_element(stick, 0.5), vec![(stick, 0.5)]);
+}
+
+// Test to check that the calibrated center offsets are removed before the dead zone.
+#[test]
+fn stick_calibration_test() {
+    let mut marker = GamepadMarker::with_dead_zone(0, (0.05, 0.05));
+    marker.recalibrate(Duration::ZERO);
+    marker.process_element(GamepadElement::Axis(GamepadAxisType::LeftStickX), 0.12);
+    marker.process_element(GamepadElement::Axis(GamepadAxisType::LeftStickX), 0.08);
+    assert!(marker.finish_calibration(Instant::now()));
+    assert!(!marker.is_calibrating());
+    let values = marker.process_element(GamepadElement::Axis(GamepadAxisType::LeftStickX), 0.1);
+    assert_eq!(values[0].0, GamepadElement::Axis(GamepadAxisType::LeftStickX));
+    assert!(values[0].1.abs() < 1e-6);
+}
+
+// Test to check that axis events are coalesced while button presses and releases are kept.
+#[test]
+fn coalesce_gamepad_events_test() {
+    let gamepad = Gamepad(0);
+    let events = [
+        GamepadEvent(gamepad, GamepadEventType::AxisChanged(GamepadAxisType::LeftStickX, 0.2)),
+        GamepadEvent(gamepad, GamepadEventType::ButtonChanged(GamepadButtonType::South, 1.)),
+        GamepadEvent(gamepad, GamepadEventType::AxisChanged(GamepadAxisType::LeftStickX, 0.5)),
+        GamepadEvent(gamepad, GamepadEventType::ButtonChanged(GamepadButtonType::South, 0.)),
+    ];
+    let coalesced = coalesce_gamepad_events(events.iter());
+    assert_eq!(
+        coalesced.changes[&gamepad],
+        vec![
+            (GamepadElement::Axis(GamepadAxisType::LeftStickX), 0.5),
+            (GamepadElement::Button(GamepadButtonType::South), 1.),
+            (GamepadElement::Button(GamepadButtonType::South), 0.),
+        ]
+    );
+}
+
+// Test to check that the press state only changes when crossing the threshold of the current state.
+#[test]
+fn press_hysteresis_test() {
+    let thresholds = PressThresholds::default();
+    assert!(!thresholds.press_state(0.08, false).pressed());
+    assert!(thresholds.press_state(0.12, false).pressed());
+    assert!(thresholds.press_state(0.08, true).pressed());
+    assert!(!thresholds.press_state(0.05, true).pressed());
+}
@@ -0,0 +1,223 @@
+//! Multi-touch gesture recognition (pinch, rotate and swipe), sent as events and bindable as receivers.
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap, Instant};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct TouchGestureSystem;
+
+/// A continuous gesture bindable as an axis receiver, see [`InputReceiver::TouchGesture`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum GestureAxis {
+    /// The change of the distance between two fingers this frame, positive when spreading them.
+    Pinch,
+    /// The rotation of two fingers this frame in radians, positive when rotating counterclockwise.
+    Rotate,
+}
+
+/// The direction of a swipe, see [`InputReceiver::Swipe`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Returns the direction of the dominant axis of a vector, with the Y axis pointing up.
+    pub fn from_vec(vec: Vec2) -> Self {
+        if vec.x.abs() > vec.y.abs() {
+            if vec.x > 0. {
+                Direction::Right
+            } else {
+                Direction::Left
+            }
+        } else if vec.y > 0. {
+            Direction::Up
+        } else {
+            Direction::Down
+        }
+    }
+}
+
+/// A recognized touch gesture.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Gesture {
+    /// Two fingers moved apart or together. The scale is relative to the previous frame.
+    Pinch { scale: f32 },
+    /// Two fingers rotated around each other since the previous frame.
+    Rotate { radians: f32 },
+    /// A finger moved quickly in a direction and was released. The velocity is in pixels per second.
+    Swipe { direction: Vec2, velocity: f32 },
+}
+
+/// Event sent for each touch gesture recognized for an entity with a [`TouchMarker`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct TouchGestureEvent {
+    pub entity: Entity,
+    pub gesture: Gesture,
+}
+
+/// Thresholds used to recognize the touch gestures.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GestureSettings {
+    /// The minimum scale change in a frame to be recognized as a pinch.
+    pub pinch_threshold: f32,
+    /// The minimum rotation in a frame, in radians, to be recognized as a rotation.
+    pub rotate_threshold: f32,
+    /// The minimum distance travelled by a finger, in pixels, to be recognized as a swipe.
+    pub swipe_distance: f32,
+    /// The maximum duration of a swipe.
+    pub swipe_duration: Duration,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            pinch_threshold: 0.01,
+            rotate_threshold: 0.01,
+            swipe_distance: 50.,
+            swipe_duration: Duration::from_millis(300),
+        }
+    }
+}
+
+/// Marker responsible for allowing systems to recognize touch gestures for a specific entity.
+#[derive(PartialEq, Debug, Component, Clone, Default)]
+pub struct TouchMarker {
+    pub settings: GestureSettings,
+    /// The instant each touch started, used to measure the swipe duration.
+    pub started: HashMap<u64, Instant>,
+}
+
+impl GestureSettings {
+    /// Returns the swipe for a movement, or none if it is too short or too slow.
+    pub fn swipe(&self, distance: Vec2, duration: Duration) -> Option<Gesture> {
+        if distance.length() < self.swipe_distance || duration > self.swipe_duration {
+            return None;
+        }
+        Some(Gesture::Swipe {
+            direction: distance.normalize(),
+            velocity: distance.length() / duration.as_secs_f32().max(f32::EPSILON),
+        })
+    }
+}
+
+impl TouchMarker {
+    pub fn with_settings(settings: GestureSettings) -> Self {
+        Self {
+            settings,
+            ..Self::default()
+        }
+    }
+
+    /// Recognize the gestures for the current touches.
+    pub fn recognize(&mut self, touches: &Touches) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+        for touch in touches.iter_just_pressed() {
+            self.started.insert(touch.id(), Instant::now());
+        }
+
+        let pressed: Vec<_> = touches.iter().collect();
+        if let [a, b] = pressed.as_slice() {
+            let previous = b.previous_position() - a.previous_position();
+            let current = b.position() - a.position();
+            if previous.length() > 0. && current.length() > 0. {
+                let scale = current.length() / previous.length();
+                if (scale - 1.).abs() >= self.settings.pinch_threshold {
+                    gestures.push(Gesture::Pinch { scale });
+                }
+                let radians = previous.angle_between(current);
+                if radians.abs() >= self.settings.rotate_threshold {
+                    gestures.push(Gesture::Rotate { radians });
+                }
+            }
+        }
+
+        for touch in touches.iter_just_released() {
+            let started = match self.started.remove(&touch.id()) {
+                Some(started) => started,
+                None => continue,
+            };
+            let distance = touch.position() - touch.start_position();
+            gestures.extend(self.settings.swipe(distance, started.elapsed()));
+        }
+        for touch in touches.iter_just_cancelled() {
+            self.started.remove(&touch.id());
+        }
+        gestures
+    }
+}
+
+/// Input system responsible for recognizing touch gestures, sending them as events and setting the gesture
+/// receivers.
+#[allow(clippy::type_complexity)]
+pub(crate) fn touch_gesture_system<Keys>(
+    mut query: Query<(
+        Entity,
+        &InputView<Keys>,
+        &mut TouchMarker,
+        &mut StagedInput<Keys, TouchMarker>,
+    )>,
+    touches: Res<Touches>,
+    mut wr: EventWriter<TouchGestureEvent>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, view, mut svc, mut staged) in query.iter_mut() {
+        let view = &mut StagedView::new(view, &mut staged);
+        if view.has_stale_swipes() {
+            view.release_stale_swipes();
+        }
+        let gestures = if touches.iter().next().is_some()
+            || touches.iter_just_released().next().is_some()
+            || touches.iter_just_cancelled().next().is_some()
+        {
+            svc.recognize(&touches)
+        } else {
+            Vec::new()
+        };
+
+        let mut pinch = 0.;
+        let mut rotate = 0.;
+        for gesture in gestures {
+            match gesture {
+                Gesture::Pinch { scale } => pinch = scale - 1.,
+                Gesture::Rotate { radians } => rotate = radians,
+                Gesture::Swipe { direction, .. } => {
+                    view.set_swipe(Direction::from_vec(direction), InputSource::Touch)
+                }
+            }
+            wr.send(TouchGestureEvent { entity, gesture });
+        }
+        for (axis, value) in [(GestureAxis::Pinch, pinch), (GestureAxis::Rotate, rotate)] {
+            let receiver = InputReceiver::TouchGesture(axis);
+            let state = view.receiver_state(&receiver);
+            if state.value == value {
+                continue;
+            }
+            let press = if value == 0. {
+                PressState::Released
+            } else if state.press.pressed() {
+                state.press
+            } else {
+                PressState::Pressed {
+                    started_pressing_instant: None,
+                }
+            };
+            view.set_input_source(InputSource::Touch);
+            view.set_axis_value(receiver, value, press);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Keyboard ghosting and rollover diagnostics, for settings screens telling the player that their keyboard can't
+//! register a combination.
+use bevy::utils::Duration;
+
+use crate::prelude::*;
+
+/// A keyboard chord of an action where some keys are held but the others never arrived.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct GhostingReport<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub action: Keys,
+    pub pressed: Vec<InputReceiver>,
+    pub missing: Vec<InputReceiver>,
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the keyboard chords with at least `min_pressed` keys held for longer than `window` while the rest of
+    /// the chord is released, which probably means the keyboard can't register the whole combination.
+    pub fn ghosting_reports(
+        &self,
+        min_pressed: usize,
+        window: Duration,
+    ) -> Vec<GhostingReport<Keys>> {
+        let mut reports = Vec::new();
+        for binding in self.bindings.values() {
+            for receivers in binding.input_receivers.iter() {
+                let is_keyboard_chord = receivers.0.len() > min_pressed
+                    && receivers.0.iter().all(|rcv| rcv.source().is_keyboard());
+                if !is_keyboard_chord {
+                    continue;
+                }
+                let (pressed, missing): (Vec<InputReceiver>, Vec<InputReceiver>) = receivers
+                    .0
+                    .iter()
+                    .partition(|rcv| self.state(rcv).press.pressed());
+                if pressed.len() < min_pressed
+                    || missing.is_empty()
+                    || !pressed
+                        .iter()
+                        .all(|rcv| self.state(rcv).press.is_pressed_for(window))
+                {
+                    continue;
+                }
+                reports.push(GhostingReport {
+                    action: binding.key,
+                    pressed,
+                    missing,
+                });
+            }
+        }
+        reports
+    }
+}
@@ -0,0 +1,74 @@
+//! Action groups with exclusive activation, where at most one member can be pressed at a time (e.g. weapon slots).
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct ActionGroupSystem;
+
+/// A group of actions where pressing a member releases all the others.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ActionGroup<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub members: Vec<Keys>,
+    pub active: Option<Keys>,
+}
+
+impl<Keys> ActionGroup<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn new(members: Vec<Keys>) -> Self {
+        Self {
+            members,
+            active: None,
+        }
+    }
+
+    /// Returns whether the given action is a member of this group.
+    pub fn contains(&self, key: &Keys) -> bool {
+        self.members.contains(key)
+    }
+}
+
+/// Event sent when another member of an action group is activated.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct GroupSwitched<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub entity: Entity,
+    pub group: usize,
+    pub previous: Option<Keys>,
+    pub current: Keys,
+}
+
+/// System responsible for activating the last pressed member of each action group.
+pub(crate) fn action_group_system<Keys>(
+    mut query: Query<(Entity, &mut InputView<Keys>)>,
+    mut wr: EventWriter<GroupSwitched<Keys>>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, mut view) in query.iter_mut() {
+        for index in 0..view.groups.len() {
+            let group = &view.groups[index];
+            let pressed = group
+                .members
+                .iter()
+                .find(|key| Some(**key) != group.active && view.raw_key(key).just_pressed())
+                .copied();
+            if let Some(current) = pressed {
+                let previous = view.groups[index].active.replace(current);
+                wr.send(GroupSwitched {
+                    entity,
+                    group: index,
+                    previous,
+                    current,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! An optional timeline of the last state transitions of each action, useful to debug reports such as "my double
+//! jump sometimes doesn't trigger" with real data.
+use std::collections::VecDeque;
+
+use bevy::utils::{HashMap, Instant};
+
+use crate::prelude::*;
+
+/// A state transition of an action.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct ActionTransition {
+    /// The state of the action after the transition.
+    pub snapshot: ActionSnapshot,
+    /// The last input source used when the transition happened.
+    pub source: Option<InputSource>,
+    pub instant: Instant,
+}
+
+/// Ring buffers holding the last transitions of each action. Disabled while the capacity is zero.
+#[derive(PartialEq, Clone, Debug)]
+pub struct HistoryTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The number of transitions kept per action.
+    pub capacity: usize,
+    pub transitions: HashMap<Keys, VecDeque<ActionTransition>>,
+}
+
+impl<Keys> Default for HistoryTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            transitions: HashMap::default(),
+        }
+    }
+}
+
+impl<Keys> HistoryTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Record a transition of an action, dropping the oldest one if the buffer is full.
+    pub fn record(&mut self, kind: Keys, transition: ActionTransition) {
+        if self.capacity == 0 {
+            return;
+        }
+        let transitions = self.transitions.entry(kind).or_default();
+        while transitions.len() >= self.capacity {
+            transitions.pop_front();
+        }
+        transitions.push_back(transition);
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the recorded transitions of an action, from the oldest to the newest.
+    pub fn history(&self, kind: &Keys) -> impl Iterator<Item = &ActionTransition> + '_ {
+        self.history.transitions.get(kind).into_iter().flatten()
+    }
+
+    /// Set the number of transitions kept per action. Zero disables the history and clears it.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history.capacity = capacity;
+        for transitions in self.history.transitions.values_mut() {
+            while transitions.len() > capacity {
+                transitions.pop_front();
+            }
+        }
+        self.history.transitions.retain(|_, transitions| !transitions.is_empty());
+    }
+
+    /// Remove every recorded transition, keeping the capacity.
+    pub fn clear_history(&mut self) {
+        self.history.transitions.clear();
+    }
+}
+
+// Test to check that the history keeps the last transitions only.
+#[test]
+fn history_ring_buffer_test() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let mut history = HistoryTracker::<Jump>::default();
+    let transition = |pressed| ActionTransition {
+        snapshot: ActionSnapshot { pressed, value: 0. },
+        source: None,
+        instant: Instant::now(),
+    };
+    history.record(Jump, transition(true));
+    assert!(history.transitions.is_empty());
+    history.capacity = 2;
+    history.record(Jump, transition(true));
+    history.record(Jump, transition(false));
+    history.record(Jump, transition(true));
+    let pressed: Vec<bool> = history.transitions[&Jump]
+        .iter()
+        .map(|transition| transition.snapshot.pressed)
+        .collect();
+    assert_eq!(pressed, vec![false, true]);
+}
@@ -0,0 +1,204 @@
+//! Hold-related mechanics for actions, such as charging an action by holding it.
+use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap, HashSet, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct HoldSystem;
+
+/// Per action hold configuration and the state needed to send hold events exactly once.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct HoldTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The duration needed to fully charge an action, sending a [`FullyCharged`] event.
+    pub charge: HashMap<Keys, Duration>,
+    /// Actions already fully charged for the current press.
+    pub charged: HashSet<Keys>,
+    /// The duration an action needs to be held to send a [`LongPress`] event.
+    pub long_press: HashMap<Keys, Duration>,
+    /// Actions already long pressed for the current press.
+    pub long_pressed: HashSet<Keys>,
+    /// The minimum duration an action needs to be held to send a [`ChargedRelease`] event when released.
+    pub charged_release: HashMap<Keys, Duration>,
+    /// The instant in which each action with a charged release started being held.
+    pub holding: HashMap<Keys, Instant>,
+}
+
+impl<Keys> Default for HoldTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            charge: HashMap::default(),
+            charged: HashSet::default(),
+            long_press: HashMap::default(),
+            long_pressed: HashSet::default(),
+            charged_release: HashMap::default(),
+            holding: HashMap::default(),
+        }
+    }
+}
+
+/// Event sent once per press when an action has been held for its whole charge duration.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct FullyCharged<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub entity: Entity,
+    pub action: Keys,
+}
+
+/// Event sent once per press when an action has been held past its long press duration.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct LongPress<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub entity: Entity,
+    pub action: Keys,
+    pub held_for: Duration,
+}
+
+/// Event sent when an action is released after being held for at least its minimum charge duration (e.g. firing a
+/// drawn bow).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ChargedRelease<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub entity: Entity,
+    pub action: Keys,
+    pub held_for: Duration,
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Set the minimum duration an action needs to be held to send a [`ChargedRelease`] event when released, or
+    /// disable it if none.
+    pub fn set_charged_release(&mut self, kind: Keys, min: Option<Duration>) {
+        match min {
+            Some(min) => self.hold.charged_release.insert(kind, min),
+            None => self.hold.charged_release.remove(&kind),
+        };
+        self.hold.holding.remove(&kind);
+    }
+}
+
+/// Returns the charge progress (0.0 to 1.0) of a press state toward a maximum duration.
+pub fn charge_progress(press: &PressState, max: Duration) -> f32 {
+    match press.elapsed() {
+        Some(_) if max.is_zero() => 1.,
+        Some(elapsed) => (elapsed.as_secs_f32() / max.as_secs_f32()).min(1.),
+        None => 0.,
+    }
+}
+
+/// System responsible for sending the hold events for each input view.
+pub(crate) fn hold_system<Keys>(
+    mut query: Query<(Entity, &mut InputView<Keys>)>,
+    mut charged_wr: EventWriter<FullyCharged<Keys>>,
+    mut long_press_wr: EventWriter<LongPress<Keys>>,
+    mut release_wr: EventWriter<ChargedRelease<Keys>>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, mut view) in query.iter_mut() {
+        let mut charged = Vec::new();
+        let mut discharged = Vec::new();
+        for (action, max) in view.hold.charge.iter() {
+            let is_charged = view.hold.charged.contains(action);
+            let progress = view.charge(action, *max);
+            if !is_charged && progress >= 1. {
+                charged.push(*action);
+            } else if is_charged && view.key(action).released() {
+                discharged.push(*action);
+            }
+        }
+        for action in charged {
+            view.hold.charged.insert(action);
+            charged_wr.send(FullyCharged { entity, action });
+        }
+        for action in discharged {
+            view.hold.charged.remove(&action);
+        }
+
+        let mut long_pressed = Vec::new();
+        let mut long_released = Vec::new();
+        for (action, duration) in view.hold.long_press.iter() {
+            let is_long_pressed = view.hold.long_pressed.contains(action);
+            let press = view.key(action);
+            if !is_long_pressed && press.is_pressed_for(*duration) {
+                long_pressed.push((*action, press.elapsed().unwrap_or_default()));
+            } else if is_long_pressed && press.released() {
+                long_released.push(*action);
+            }
+        }
+        for (action, held_for) in long_pressed {
+            view.hold.long_pressed.insert(action);
+            long_press_wr.send(LongPress {
+                entity,
+                action,
+                held_for,
+            });
+        }
+        for action in long_released {
+            view.hold.long_pressed.remove(&action);
+        }
+
+        let mut started = Vec::new();
+        let mut released = Vec::new();
+        for (action, min) in view.hold.charged_release.iter() {
+            let holding = view.hold.holding.get(action);
+            match (view.key(action), holding) {
+                (
+                    PressState::Pressed {
+                        started_pressing_instant: Some(instant),
+                    },
+                    None,
+                ) => started.push((*action, instant)),
+                (PressState::Released, Some(instant)) => {
+                    released.push((*action, instant.elapsed(), *min))
+                }
+                _ => {}
+            }
+        }
+        for (action, instant) in started {
+            view.hold.holding.insert(action, instant);
+        }
+        for (action, held_for, min) in released {
+            view.hold.holding.remove(&action);
+            if held_for >= min {
+                release_wr.send(ChargedRelease {
+                    entity,
+                    action,
+                    held_for,
+                });
+            }
+        }
+    }
+}
+
+// Test to check the charge progress around the first tick, when the pressing instant is not known yet.
+#[test]
+fn charge_progress_test() {
+    use bevy::utils::Instant;
+
+    let max = Duration::from_secs(1);
+    assert_eq!(charge_progress(&PressState::Released, max), 0.);
+    let just_pressed = PressState::Pressed {
+        started_pressing_instant: None,
+    };
+    assert_eq!(charge_progress(&just_pressed, max), 0.);
+    assert_eq!(charge_progress(&just_pressed, Duration::ZERO), 1.);
+    let held = PressState::Pressed {
+        started_pressing_instant: Instant::now().checked_sub(Duration::from_secs(2)),
+    };
+    assert_eq!(charge_progress(&held, max), 1.);
+}
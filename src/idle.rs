@@ -0,0 +1,67 @@
+//! Dead-man/idle detection, useful for attract modes and AFK detection.
+use bevy::prelude::*;
+use bevy::utils::{Duration, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct IdleDetectionSystem;
+
+/// Keeps track of the last time any bound receiver of an input view was active.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct IdleTracker {
+    /// Whether any bound receiver is currently active.
+    pub active: bool,
+    /// The instant in which the input view stopped being active.
+    pub last_activity: Option<Instant>,
+    /// The idle duration needed to send an [`InputIdle`] event. No event is sent if none.
+    pub threshold: Option<Duration>,
+    /// Whether the [`InputIdle`] event was already sent for the current idle period.
+    pub notified: bool,
+}
+
+impl IdleTracker {
+    /// Returns how long the input view has been idle.
+    pub fn idle_time(&self) -> Duration {
+        if self.active {
+            return Duration::ZERO;
+        }
+        self.last_activity
+            .map(|instant| instant.elapsed())
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+/// Event sent once when an input view has been idle for longer than its configured threshold.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct InputIdle {
+    pub entity: Entity,
+    pub idle_for: Duration,
+}
+
+/// System responsible for tracking the activity of each input view and sending [`InputIdle`] events.
+pub(crate) fn idle_detection_system<Keys>(
+    mut query: Query<(Entity, &mut InputView<Keys>)>,
+    mut wr: EventWriter<InputIdle>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, mut view) in query.iter_mut() {
+        let active = view.is_active();
+        if active != view.idle.active || view.idle.last_activity.is_none() {
+            let idle = &mut view.idle;
+            idle.active = active;
+            idle.last_activity = Some(Instant::now());
+            idle.notified = false;
+        }
+
+        let idle = &view.idle;
+        if let Some(threshold) = idle.threshold {
+            let idle_for = idle.idle_time();
+            if !idle.notified && !idle.active && idle_for >= threshold {
+                view.idle.notified = true;
+                wr.send(InputIdle { entity, idle_for });
+            }
+        }
+    }
+}
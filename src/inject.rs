@@ -0,0 +1,94 @@
+//! Injection of synthetic input (e.g. speech commands, MIDI controllers, network input) through the same
+//! binding and state machinery used by the built-in input sources.
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct InjectedInputHandlingSystem;
+
+/// Event carrying a synthetic receiver state. Sent through the [`InputInjector`] system parameter.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct InjectedInput {
+    /// The input view entity receiving the input. Every input view receives the input if none.
+    pub entity: Option<Entity>,
+    pub receiver: InputReceiver,
+    pub value: f32,
+    pub pressed: bool,
+}
+
+/// A system parameter for feeding synthetic receivers, usually [`InputReceiver::Custom`], to the input views.
+#[derive(SystemParam)]
+pub struct InputInjector<'w, 's> {
+    events: EventWriter<'w, 's, InjectedInput>,
+}
+
+impl<'w, 's> InputInjector<'w, 's> {
+    /// Press a receiver with a value of 1 in every input view.
+    pub fn press(&mut self, receiver: InputReceiver) {
+        self.set(None, receiver, 1.);
+    }
+
+    /// Release a receiver in every input view.
+    pub fn release(&mut self, receiver: InputReceiver) {
+        self.set(None, receiver, 0.);
+    }
+
+    /// Set the value of a receiver in every input view. A value of zero releases the receiver.
+    pub fn set_axis(&mut self, receiver: InputReceiver, value: f32) {
+        self.set(None, receiver, value);
+    }
+
+    /// Set the value of a receiver for a specific input view entity, or every input view if none.
+    pub fn set(&mut self, entity: Option<Entity>, receiver: InputReceiver, value: f32) {
+        self.events.send(InjectedInput {
+            entity,
+            receiver,
+            value,
+            pressed: value != 0.,
+        });
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Apply an injected receiver state, keeping the pressing instant if the receiver was already pressed.
+    pub fn inject(&mut self, input: &InjectedInput) {
+        let state = self.state(&input.receiver);
+        if state.value == input.value && state.press.pressed() == input.pressed {
+            return;
+        }
+        let press = match (state.press, input.pressed) {
+            (press @ PressState::Pressed { .. }, true) => press,
+            (_, true) => PressState::Pressed {
+                started_pressing_instant: None,
+            },
+            (_, false) => PressState::Released,
+        };
+        self.set_input_source(input.receiver.source());
+        self.set_axis_value(input.receiver, input.value, press);
+    }
+}
+
+/// Input system responsible for applying every [`InjectedInput`] event to the input views.
+pub(crate) fn injected_input_system<Keys>(
+    mut query: Query<(Entity, &mut InputView<Keys>)>,
+    mut rd: EventReader<InjectedInput>,
+) where
+    Keys: BindingTypeView,
+{
+    for ev in rd.iter() {
+        for (entity, mut view) in query.iter_mut() {
+            if ev.entity.is_some_and(|target| target != entity) {
+                continue;
+            }
+            let state = view.state(&ev.receiver);
+            if state.value != ev.value || state.press.pressed() != ev.pressed {
+                view.inject(ev);
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! An optional journal of the receiver changes of every input view, stamped with the [`InputFrame`] they happened in.
+//! Rollback frameworks rewind the input views to an arbitrary tick with [`InputJournal::rewind`] and replay the
+//! following ticks with [`InputJournal::apply`]. Add the [`InputJournalPlugin`] to record it.
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct InputJournalSystem;
+
+/// A receiver change recorded in the journal.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct JournalEntry {
+    /// The input frame the change happened in.
+    pub tick: u64,
+    pub entity: Entity,
+    pub receiver: InputReceiver,
+    pub pressed: bool,
+    pub value: f32,
+}
+
+/// The receiver changes of every input view, from the oldest to the newest tick.
+#[derive(PartialEq, Clone, Debug)]
+pub struct InputJournal {
+    /// The number of ticks kept in the journal, or `None` to keep every tick until [`InputJournal::prune_before`].
+    pub max_ticks: Option<u64>,
+    pub entries: VecDeque<JournalEntry>,
+    /// The state of the receivers before the oldest entry, folded from the pruned entries.
+    pub baseline: HashMap<(Entity, InputReceiver), (bool, f32)>,
+    /// The last recorded state of each receiver.
+    last: HashMap<(Entity, InputReceiver), (bool, f32)>,
+}
+
+impl Default for InputJournal {
+    fn default() -> Self {
+        Self::new(Some(600))
+    }
+}
+
+impl InputJournal {
+    pub fn new(max_ticks: Option<u64>) -> Self {
+        Self {
+            max_ticks,
+            entries: VecDeque::new(),
+            baseline: HashMap::default(),
+            last: HashMap::default(),
+        }
+    }
+
+    /// Record the state of a receiver at a tick, if it changed since the last record.
+    pub fn record(&mut self, tick: u64, entity: Entity, receiver: InputReceiver, pressed: bool, value: f32) {
+        let state = (pressed, value);
+        let previous = self.last.insert((entity, receiver), state);
+        if previous == Some(state) || (previous.is_none() && state == (false, 0.)) {
+            return;
+        }
+        self.entries.push_back(JournalEntry {
+            tick,
+            entity,
+            receiver,
+            pressed,
+            value,
+        });
+    }
+
+    /// Returns the oldest tick in the journal, if any.
+    pub fn oldest_tick(&self) -> Option<u64> {
+        self.entries.front().map(|entry| entry.tick)
+    }
+
+    /// Returns the changes recorded at a tick.
+    pub fn entries_at(&self, tick: u64) -> impl Iterator<Item = &JournalEntry> + '_ {
+        self.entries.iter().filter(move |entry| entry.tick == tick)
+    }
+
+    /// Returns the changes recorded after a tick, from the oldest to the newest.
+    pub fn entries_since(&self, tick: u64) -> impl Iterator<Item = &JournalEntry> + '_ {
+        self.entries.iter().filter(move |entry| entry.tick > tick)
+    }
+
+    /// Returns the state of the receivers of an input view at the end of a tick, as `(receiver, pressed, value)`.
+    /// Receivers that never changed are left out.
+    pub fn state_at(&self, entity: Entity, tick: u64) -> Vec<(InputReceiver, bool, f32)> {
+        let mut state: HashMap<InputReceiver, (bool, f32)> = self
+            .baseline
+            .iter()
+            .filter(|((other, _), _)| *other == entity)
+            .map(|((_, receiver), state)| (*receiver, *state))
+            .collect();
+        for entry in self.entries.iter().filter(|entry| entry.entity == entity) {
+            if entry.tick > tick {
+                break;
+            }
+            state.insert(entry.receiver, (entry.pressed, entry.value));
+        }
+        state
+            .into_iter()
+            .map(|(receiver, (pressed, value))| (receiver, pressed, value))
+            .collect()
+    }
+
+    /// Apply the changes recorded at a tick for an entity to its input view.
+    pub fn apply<Keys>(&self, tick: u64, entity: Entity, view: &mut InputView<Keys>)
+    where
+        Keys: BindingTypeView,
+    {
+        for entry in self.entries_at(tick).filter(|entry| entry.entity == entity) {
+            view.set_axis_value(entry.receiver, entry.value, press_state(entry.pressed));
+        }
+    }
+
+    /// Rewind an input view to the state of its receivers at the end of a tick.
+    pub fn rewind<Keys>(&self, tick: u64, entity: Entity, view: &mut InputView<Keys>)
+    where
+        Keys: BindingTypeView,
+    {
+        for (receiver, pressed, value) in self.state_at(entity, tick) {
+            view.set_axis_value(receiver, value, press_state(pressed));
+        }
+    }
+
+    /// Drop the entries older than a tick, e.g. once a rollback framework confirmed it.
+    pub fn prune_before(&mut self, tick: u64) {
+        while self.entries.front().is_some_and(|entry| entry.tick < tick) {
+            if let Some(entry) = self.entries.pop_front() {
+                self.baseline.insert((entry.entity, entry.receiver), (entry.pressed, entry.value));
+            }
+        }
+    }
+
+    /// Forget the receivers of a despawned input view.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.entries.retain(|entry| entry.entity != entity);
+        self.baseline.retain(|(other, _), _| *other != entity);
+        self.last.retain(|(other, _), _| *other != entity);
+    }
+}
+
+fn press_state(pressed: bool) -> PressState {
+    if pressed {
+        PressState::Pressed {
+            started_pressing_instant: None,
+        }
+    } else {
+        PressState::Released
+    }
+}
+
+/// A [`Plugin`] recording the receiver changes of the input views into the [`InputJournal`] resource.
+pub struct InputJournalPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The number of ticks kept in the journal, or `None` to prune it manually.
+    pub max_ticks: Option<u64>,
+    phantom: PhantomData<Keys>,
+}
+
+impl<Keys> Default for InputJournalPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            max_ticks: InputJournal::default().max_ticks,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Keys> InputJournalPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn with_max_ticks(max_ticks: Option<u64>) -> Self {
+        Self {
+            max_ticks,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Keys> Plugin for InputJournalPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<InputJournal>() {
+            app.insert_resource(InputJournal::new(self.max_ticks));
+        }
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            input_journal_system::<Keys>
+                .label(InputJournalSystem)
+                .label(EZInputSet::Resolve)
+                .label(InputViewResolutionSystem)
+                .after(InputHandlingTickSystem),
+        );
+    }
+}
+
+/// System responsible for recording the receiver changes of each input view into the journal. The values are recorded
+/// as written by the input sources, so replaying them goes through the same inversion and processing.
+pub(crate) fn input_journal_system<Keys>(
+    query: Query<(Entity, &InputView<Keys>)>,
+    mut journal: ResMut<InputJournal>,
+    frame: Res<InputFrame>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, view) in query.iter() {
+        for descriptor in view.descriptors.iter() {
+            let state = view.raw_state(&descriptor.input);
+            journal.record(frame.0, entity, descriptor.input, state.press.pressed(), state.value);
+        }
+    }
+    if let Some(max_ticks) = journal.max_ticks {
+        journal.prune_before(frame.0.saturating_sub(max_ticks));
+    }
+}
+
+// Test to check that the journal keeps the state of the pruned ticks to rewind to a later tick.
+#[test]
+fn journal_rewind_test() {
+    use bevy::prelude::KeyCode;
+
+    let entity = Entity::from_raw(0);
+    let key = InputReceiver::KeyboardKey(KeyCode::Space);
+    let mut journal = InputJournal::new(None);
+    journal.record(1, entity, key, true, 1.);
+    journal.record(2, entity, key, true, 1.);
+    journal.record(3, entity, key, false, 0.);
+    assert_eq!(journal.entries.len(), 2);
+    journal.prune_before(2);
+    assert_eq!(journal.state_at(entity, 2), vec![(key, true, 1.)]);
+    assert_eq!(journal.state_at(entity, 3), vec![(key, false, 0.)]);
+}
@@ -1,39 +1,232 @@
 //! Full keyboard support for EZInput.
+use std::ops::BitOr;
+
 use crate::prelude::*;
-use bevy::{input::keyboard::KeyboardInput, prelude::*};
+use bevy::{input::keyboard::KeyboardInput, prelude::*, utils::HashSet, window::WindowFocused};
+use serde::{Deserialize, Serialize};
 
 #[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
 pub struct KeyboardInputHandlingSystem;
 
+/// A set of keyboard modifiers (Shift, Ctrl, Alt and Logo), where the left and right keys are treated the same.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct Modifiers(pub u8);
+
+impl Modifiers {
+    pub const NONE: Self = Self(0);
+    pub const SHIFT: Self = Self(1);
+    pub const CTRL: Self = Self(1 << 1);
+    pub const ALT: Self = Self(1 << 2);
+    pub const LOGO: Self = Self(1 << 3);
+
+    /// Returns the modifier for the given key, if the key is a modifier.
+    pub fn from_key(key: KeyCode) -> Option<Self> {
+        match key {
+            KeyCode::LShift | KeyCode::RShift => Some(Self::SHIFT),
+            KeyCode::LControl | KeyCode::RControl => Some(Self::CTRL),
+            KeyCode::LAlt | KeyCode::RAlt => Some(Self::ALT),
+            KeyCode::LWin | KeyCode::RWin => Some(Self::LOGO),
+            _ => None,
+        }
+    }
+
+    /// Returns whether there are no modifiers in this set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns whether all modifiers of the other set are in this set.
+    pub fn contains(&self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns whether any modifier of the other set is in this set.
+    pub fn intersects(&self, other: Modifiers) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Add or remove the given modifiers from this set.
+    pub fn set(&mut self, other: Modifiers, value: bool) {
+        if value {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Whether the key repeat events forwarded by the OS are dropped, so holding a key doesn't press it again. Enabled by
+/// default.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SuppressKeyRepeat(pub bool);
+
+impl Default for SuppressKeyRepeat {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// The keys held at the OS level for each keyboard, used to recognize the key repeat events.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct KeyRepeatFilter {
+    pub held: HashSet<(Option<DeviceId>, KeyCode)>,
+}
+
+impl KeyRepeatFilter {
+    /// Returns whether the event is a repeat of a key already held on the same keyboard.
+    pub fn is_repeat(&mut self, device: Option<DeviceId>, key: KeyCode, state: PressState) -> bool {
+        if state.pressed() {
+            !self.held.insert((device, key))
+        } else {
+            self.held.remove(&(device, key));
+            false
+        }
+    }
+
+    /// Forget every held key, e.g. when the application loses focus and the release events are lost.
+    pub fn release_all(&mut self) {
+        self.held.clear();
+    }
+}
+
 /// Service responsible for allowing EZInput to handle keyboard input for a specific entity.
 #[derive(PartialEq, Eq, Debug, Component, Clone, Copy, Default)]
 pub struct KeyboardMarker;
 
 impl KeyboardMarker {
     /// Change the current button and axis state for the given key for and set the last input source to Keyboard.
-    pub fn set_keyboard_key_state<Keys>(
-        &mut self,
-        view: &mut InputView<Keys>,
-        key: KeyCode,
-        state: PressState,
-    ) where
-        Keys: BindingTypeView,
+    pub fn set_keyboard_key_state<S>(&mut self, view: &mut S, key: KeyCode, state: PressState)
+    where
+        S: InputSink,
     {
-        view.last_input_source = Some(InputSource::Keyboard);
+        view.set_input_source(InputSource::Keyboard);
         view.set_key_receiver_state(InputReceiver::KeyboardKey(key), state);
     }
+
+    /// Change the current depth of the given analog key and set the last input source to Keyboard.
+    pub fn set_keyboard_analog_state<S>(&mut self, view: &mut S, key: KeyCode, depth: f32)
+    where
+        S: InputSink,
+    {
+        let state = if depth > 0. {
+            PressState::Pressed {
+                started_pressing_instant: None,
+            }
+        } else {
+            PressState::Released
+        };
+        let current = view.receiver_state(&InputReceiver::KeyboardAnalog(key));
+        let state = match (current.press, state) {
+            (current @ PressState::Pressed { .. }, PressState::Pressed { .. }) => current,
+            (_, state) => state,
+        };
+        view.set_input_source(InputSource::Keyboard);
+        view.set_axis_value(InputReceiver::KeyboardAnalog(key), depth, state);
+    }
 }
 
-/// Input system responsible for handling keyboard input and setting the button state for each updated button and axis.
+/// Input system responsible for handling keyboard input and staging the button state for each updated key.
+#[allow(clippy::type_complexity)]
 pub(crate) fn keyboard_input_system<Keys: BindingTypeView>(
-    mut query: Query<(&mut InputView<Keys>, &mut KeyboardMarker)>,
+    mut query: Query<(
+        &InputView<Keys>,
+        &mut KeyboardMarker,
+        &mut StagedInput<Keys, KeyboardMarker>,
+        Option<&mut AccessibilitySettings<Keys>>,
+        Option<&InputDevice>,
+    )>,
     mut rd: EventReader<KeyboardInput>,
+    mut device_rd: EventReader<DeviceKeyboardInput>,
+    mut analog_rd: EventReader<AnalogKeyInput>,
+    mut repeats: Local<KeyRepeatFilter>,
+    suppress_repeat: Option<Res<SuppressKeyRepeat>>,
+    mut focused_rd: EventReader<WindowFocused>,
 ) {
-    for (mut view, mut keyboard_svc) in query.iter_mut() {
-        for ev in rd.iter() {
-            if let Some(key) = ev.key_code {
-                keyboard_svc.set_keyboard_key_state::<Keys>(&mut view, key, ev.state.into());
+    if focused_rd.iter().fold(None, |_, ev| Some(ev.focused)) == Some(false) {
+        repeats.release_all();
+    }
+    let suppress_repeat = suppress_repeat.as_deref().copied().unwrap_or_default().0;
+    let events: Vec<(KeyCode, PressState)> = rd
+        .iter()
+        .filter_map(|ev| ev.key_code.map(|key| (key, ev.state.into())))
+        .filter(|&(key, state)| !repeats.is_repeat(None, key, state) || !suppress_repeat)
+        .collect();
+    let device_events: Vec<DeviceKeyboardInput> = device_rd
+        .iter()
+        .filter(|ev| !repeats.is_repeat(Some(ev.device), ev.key, ev.state) || !suppress_repeat)
+        .copied()
+        .collect();
+    let analog_events: Vec<AnalogKeyInput> = analog_rd.iter().copied().collect();
+    if events.is_empty() && device_events.is_empty() && analog_events.is_empty() {
+        return;
+    }
+    for (view, mut keyboard_svc, mut staged, mut accessibility, device) in query.iter_mut() {
+        let mut view = StagedView::new(view, &mut staged);
+        let keyboard = device.and_then(|device| device.keyboard);
+        let events: Vec<(KeyCode, PressState)> = match keyboard {
+            Some(device) => device_events
+                .iter()
+                .filter(|ev| ev.device == device)
+                .map(|ev| (ev.key, ev.state))
+                .collect(),
+            None => events.clone(),
+        };
+        for &(key, state) in events.iter() {
+            let mut state = state;
+            if let Some(ref mut settings) = accessibility {
+                if settings.filters(key, state) {
+                    let (filtered, unlatched) = settings.filter_key(key, state);
+                    for modifier in unlatched {
+                        keyboard_svc.set_keyboard_key_state(&mut view, modifier, PressState::Released);
+                    }
+                    state = match filtered {
+                        Some(state) => state,
+                        None => continue,
+                    };
+                }
+            }
+            if view.receiver_state(&InputReceiver::KeyboardKey(key)).press.pressed() == state.pressed() {
+                continue;
             }
+            keyboard_svc.set_keyboard_key_state(&mut view, key, state);
+        }
+        for ev in analog_events.iter().filter(|ev| ev.device == keyboard) {
+            keyboard_svc.set_keyboard_analog_state(&mut view, ev.key, ev.depth);
         }
     }
 }
+
+// Test to check that only the first press of a held key passes the key repeat filter.
+#[test]
+fn key_repeat_filter_test() {
+    let pressed = PressState::Pressed {
+        started_pressing_instant: None,
+    };
+    let mut filter = KeyRepeatFilter::default();
+    assert!(!filter.is_repeat(None, KeyCode::W, pressed));
+    assert!(filter.is_repeat(None, KeyCode::W, pressed));
+    assert!(!filter.is_repeat(Some(DeviceId(1)), KeyCode::W, pressed));
+    assert!(!filter.is_repeat(None, KeyCode::W, PressState::Released));
+    assert!(!filter.is_repeat(None, KeyCode::W, pressed));
+}
+
+// Test to check that a key held when the focus was lost isn't taken for a repeat when pressed again.
+#[test]
+fn key_repeat_focus_loss_test() {
+    let pressed = PressState::Pressed {
+        started_pressing_instant: None,
+    };
+    let mut filter = KeyRepeatFilter::default();
+    assert!(!filter.is_repeat(None, KeyCode::W, pressed));
+    filter.release_all();
+    assert!(!filter.is_repeat(None, KeyCode::W, pressed));
+    assert!(filter.is_repeat(None, KeyCode::W, pressed));
+}
@@ -0,0 +1,175 @@
+//! Opt-in input latency diagnostics, measuring for each input source the time between the start of the frame in
+//! which an input was handed to the app and the moment its actions become queryable. Useful to verify that the input
+//! pipeline doesn't add frames of delay. Bevy doesn't expose the timestamps of the OS events, so the time the events
+//! spent queued by the OS and the windowing backend before the frame started isn't measured.
+use std::collections::VecDeque;
+
+use bevy::diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy::ecs::event::Events;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct InputLatencySystem;
+
+/// The instant in which the current frame started, used as the receipt instant of the events handed to the app for
+/// this frame.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct InputReceiptInstant(pub Instant);
+
+impl Default for InputReceiptInstant {
+    fn default() -> Self {
+        Self(Instant::now())
+    }
+}
+
+/// The input latency of an input view, in milliseconds, for every input source. Insert it on the entities of the
+/// input views to measure; a measurement is added for every frame in which a receiver of the view was written.
+#[derive(PartialEq, Clone, Debug, Component)]
+pub struct InputLatency {
+    pub history: HashMap<InputSource, VecDeque<f64>>,
+    /// The number of measurements kept for each input source.
+    pub max_history: usize,
+}
+
+impl Default for InputLatency {
+    fn default() -> Self {
+        Self {
+            history: HashMap::default(),
+            max_history: 60,
+        }
+    }
+}
+
+impl InputLatency {
+    /// Add a measurement for the given input source, dropping the oldest one if the history is full.
+    pub fn add_measurement(&mut self, source: InputSource, latency: f64) {
+        let history = self.history.entry(source).or_default();
+        if history.len() >= self.max_history {
+            history.pop_front();
+        }
+        history.push_back(latency);
+    }
+
+    /// Returns the last latency measured for the given input source.
+    pub fn latest(&self, source: InputSource) -> Option<f64> {
+        self.history.get(&source)?.back().copied()
+    }
+
+    /// Returns the average latency of the given input source over the history.
+    pub fn average(&self, source: InputSource) -> Option<f64> {
+        let history = self.history.get(&source).filter(|history| !history.is_empty())?;
+        Some(history.iter().sum::<f64>() / history.len() as f64)
+    }
+
+    /// Returns the maximum latency of the given input source over the history.
+    pub fn max(&self, source: InputSource) -> Option<f64> {
+        self.history.get(&source)?.iter().copied().reduce(f64::max)
+    }
+}
+
+/// Adds the input latency diagnostics, in milliseconds, for every input source, over every input view. A
+/// measurement is added for every frame in which a receiver of the source was written. The latency of specific input
+/// views is measured in their [`InputLatency`] component.
+#[derive(Default)]
+pub struct InputLatencyDiagnostics;
+
+impl Plugin for InputLatencyDiagnostics {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<Events<RawInputEvent>>() {
+            app.add_event::<RawInputEvent>();
+        }
+        app.init_resource::<Diagnostics>();
+        app.init_resource::<InputReceiptInstant>();
+        app.add_startup_system(Self::setup_system);
+        app.add_system_to_stage(CoreStage::First, Self::receipt_system.exclusive_system().at_start());
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            Self::diagnostic_system
+                .label(InputLatencySystem)
+                .after(EZInputSet::Resolve),
+        );
+    }
+}
+
+impl InputLatencyDiagnostics {
+    pub const KEYBOARD: DiagnosticId = DiagnosticId::from_u128(171048826307415316843395806546379215937);
+    pub const MOUSE: DiagnosticId = DiagnosticId::from_u128(29380148937528624780463227003717543617);
+    pub const GAMEPAD: DiagnosticId = DiagnosticId::from_u128(262913560837815209826187839478614006221);
+    pub const TOUCH: DiagnosticId = DiagnosticId::from_u128(103937468402569377592744716468935240179);
+    pub const CUSTOM: DiagnosticId = DiagnosticId::from_u128(226517206469931815406493546094498117093);
+
+    /// Returns the diagnostic measuring the latency of the given input source.
+    pub fn id(source: InputSource) -> DiagnosticId {
+        match source {
+            InputSource::Keyboard | InputSource::KeyboardMouse => Self::KEYBOARD,
+            InputSource::Mouse => Self::MOUSE,
+            InputSource::Gamepad => Self::GAMEPAD,
+            InputSource::Touch => Self::TOUCH,
+            InputSource::Custom => Self::CUSTOM,
+        }
+    }
+
+    /// Returns the average latency of the given input source over the diagnostic history.
+    pub fn average(diagnostics: &Diagnostics, source: InputSource) -> Option<f64> {
+        diagnostics.get(Self::id(source))?.average()
+    }
+
+    /// Returns the maximum latency of the given input source over the diagnostic history.
+    pub fn max(diagnostics: &Diagnostics, source: InputSource) -> Option<f64> {
+        diagnostics
+            .get(Self::id(source))?
+            .values()
+            .copied()
+            .reduce(f64::max)
+    }
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        let sources = [
+            (Self::KEYBOARD, "input_latency_keyboard"),
+            (Self::MOUSE, "input_latency_mouse"),
+            (Self::GAMEPAD, "input_latency_gamepad"),
+            (Self::TOUCH, "input_latency_touch"),
+            (Self::CUSTOM, "input_latency_custom"),
+        ];
+        for (id, name) in sources {
+            diagnostics.add(Diagnostic::new(id, name, 60).with_suffix("ms"));
+        }
+    }
+
+    fn receipt_system(mut receipt: ResMut<InputReceiptInstant>) {
+        receipt.0 = Instant::now();
+    }
+
+    fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        receipt: Res<InputReceiptInstant>,
+        mut rd: EventReader<RawInputEvent>,
+        mut query: Query<&mut InputLatency>,
+    ) {
+        let mut written: Vec<(Entity, InputSource)> = Vec::new();
+        for ev in rd.iter() {
+            if !written.contains(&(ev.entity, ev.source)) {
+                written.push((ev.entity, ev.source));
+            }
+        }
+        if written.is_empty() {
+            return;
+        }
+        let latency = receipt.0.elapsed().as_secs_f64() * 1000.;
+        let mut sources: Vec<InputSource> = Vec::new();
+        for (entity, source) in written {
+            if let Ok(mut view_latency) = query.get_mut(entity) {
+                view_latency.add_measurement(source, latency);
+            }
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        for source in sources {
+            diagnostics.add_measurement(Self::id(source), latency);
+        }
+    }
+}
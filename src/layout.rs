@@ -0,0 +1,182 @@
+//! The active keyboard layout, used to display the character printed on a physical key. The same scan code is "Z" on
+//! QWERTY and "W" on AZERTY, so rebinding UIs should label the keys through [`KeyboardLayout::receiver_display_char`]
+//! instead of assuming QWERTY.
+use bevy::input::keyboard::KeyboardInput;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct KeyboardLayoutSystem;
+
+/// The scan codes of the character keys of a US QWERTY keyboard, used until a key is pressed on the active layout.
+const QWERTY_SCAN_CODES: [(u32, KeyCode); 48] = [
+    (0x02, KeyCode::Key1),
+    (0x03, KeyCode::Key2),
+    (0x04, KeyCode::Key3),
+    (0x05, KeyCode::Key4),
+    (0x06, KeyCode::Key5),
+    (0x07, KeyCode::Key6),
+    (0x08, KeyCode::Key7),
+    (0x09, KeyCode::Key8),
+    (0x0A, KeyCode::Key9),
+    (0x0B, KeyCode::Key0),
+    (0x0C, KeyCode::Minus),
+    (0x0D, KeyCode::Equals),
+    (0x10, KeyCode::Q),
+    (0x11, KeyCode::W),
+    (0x12, KeyCode::E),
+    (0x13, KeyCode::R),
+    (0x14, KeyCode::T),
+    (0x15, KeyCode::Y),
+    (0x16, KeyCode::U),
+    (0x17, KeyCode::I),
+    (0x18, KeyCode::O),
+    (0x19, KeyCode::P),
+    (0x1A, KeyCode::LBracket),
+    (0x1B, KeyCode::RBracket),
+    (0x1E, KeyCode::A),
+    (0x1F, KeyCode::S),
+    (0x20, KeyCode::D),
+    (0x21, KeyCode::F),
+    (0x22, KeyCode::G),
+    (0x23, KeyCode::H),
+    (0x24, KeyCode::J),
+    (0x25, KeyCode::K),
+    (0x26, KeyCode::L),
+    (0x27, KeyCode::Semicolon),
+    (0x28, KeyCode::Apostrophe),
+    (0x29, KeyCode::Grave),
+    (0x2B, KeyCode::Backslash),
+    (0x2C, KeyCode::Z),
+    (0x2D, KeyCode::X),
+    (0x2E, KeyCode::C),
+    (0x2F, KeyCode::V),
+    (0x30, KeyCode::B),
+    (0x31, KeyCode::N),
+    (0x32, KeyCode::M),
+    (0x33, KeyCode::Comma),
+    (0x34, KeyCode::Period),
+    (0x35, KeyCode::Slash),
+    (0x39, KeyCode::Space),
+];
+
+/// Returns the character printed on the key producing a key code, if it is a character key.
+pub fn key_code_char(key: KeyCode) -> Option<char> {
+    let letters = [
+        KeyCode::A,
+        KeyCode::B,
+        KeyCode::C,
+        KeyCode::D,
+        KeyCode::E,
+        KeyCode::F,
+        KeyCode::G,
+        KeyCode::H,
+        KeyCode::I,
+        KeyCode::J,
+        KeyCode::K,
+        KeyCode::L,
+        KeyCode::M,
+        KeyCode::N,
+        KeyCode::O,
+        KeyCode::P,
+        KeyCode::Q,
+        KeyCode::R,
+        KeyCode::S,
+        KeyCode::T,
+        KeyCode::U,
+        KeyCode::V,
+        KeyCode::W,
+        KeyCode::X,
+        KeyCode::Y,
+        KeyCode::Z,
+    ];
+    if let Some(index) = letters.iter().position(|letter| *letter == key) {
+        return Some((b'A' + index as u8) as char);
+    }
+    let char = match key {
+        KeyCode::Key1 => '1',
+        KeyCode::Key2 => '2',
+        KeyCode::Key3 => '3',
+        KeyCode::Key4 => '4',
+        KeyCode::Key5 => '5',
+        KeyCode::Key6 => '6',
+        KeyCode::Key7 => '7',
+        KeyCode::Key8 => '8',
+        KeyCode::Key9 => '9',
+        KeyCode::Key0 => '0',
+        KeyCode::Minus => '-',
+        KeyCode::Equals => '=',
+        KeyCode::LBracket => '[',
+        KeyCode::RBracket => ']',
+        KeyCode::Semicolon => ';',
+        KeyCode::Apostrophe => '\'',
+        KeyCode::Grave => '`',
+        KeyCode::Backslash => '\\',
+        KeyCode::Comma => ',',
+        KeyCode::Period => '.',
+        KeyCode::Slash => '/',
+        KeyCode::Space => ' ',
+        _ => return None,
+    };
+    Some(char)
+}
+
+/// The key code produced by each scan code on the active keyboard layout, learned from the keyboard events. Bevy
+/// doesn't expose the layout, so the keys not pressed yet fall back to US QWERTY.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct KeyboardLayout {
+    pub keys: HashMap<u32, KeyCode>,
+}
+
+impl KeyboardLayout {
+    /// Returns the key code produced by a scan code on the active layout.
+    pub fn key_code(&self, scan_code: u32) -> Option<KeyCode> {
+        self.keys.get(&scan_code).copied().or_else(|| {
+            QWERTY_SCAN_CODES
+                .iter()
+                .find(|(other, _)| *other == scan_code)
+                .map(|(_, key)| *key)
+        })
+    }
+
+    /// Returns the scan code of the physical key producing a key code on the active layout.
+    pub fn scan_code(&self, key: KeyCode) -> Option<u32> {
+        self.keys
+            .iter()
+            .find(|(_, other)| **other == key)
+            .map(|(scan_code, _)| *scan_code)
+            .or_else(|| {
+                QWERTY_SCAN_CODES
+                    .iter()
+                    .filter(|(scan_code, _)| !self.keys.contains_key(scan_code))
+                    .find(|(_, other)| *other == key)
+                    .map(|(scan_code, _)| *scan_code)
+            })
+    }
+
+    /// Returns the character printed on the physical key with the given scan code on the active layout.
+    pub fn receiver_display_char(&self, scan_code: u32) -> Option<char> {
+        self.key_code(scan_code).and_then(key_code_char)
+    }
+}
+
+/// System responsible for learning the key code produced by each scan code.
+pub(crate) fn keyboard_layout_system(mut layout: ResMut<KeyboardLayout>, mut rd: EventReader<KeyboardInput>) {
+    for ev in rd.iter() {
+        if let Some(key) = ev.key_code {
+            if layout.keys.get(&ev.scan_code) != Some(&key) {
+                layout.keys.insert(ev.scan_code, key);
+            }
+        }
+    }
+}
+
+// Test to check that a learned AZERTY key is displayed with its own character instead of the QWERTY one.
+#[test]
+fn keyboard_layout_display_test() {
+    let mut layout = KeyboardLayout::default();
+    assert_eq!(layout.receiver_display_char(0x2C), Some('Z'));
+    layout.keys.insert(0x2C, KeyCode::W);
+    assert_eq!(layout.receiver_display_char(0x2C), Some('W'));
+    assert_eq!(layout.scan_code(KeyCode::W), Some(0x2C));
+}
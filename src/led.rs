@@ -0,0 +1,51 @@
+//! Gamepad LED and lightbar control. Bevy doesn't drive the controller LEDs, so the color set with
+//! [`GamepadMarker::set_led`] is sent as a [`GamepadLedRequest`] event to be applied by the gamepad backend of the
+//! application, on the controllers supporting it (e.g. the lightbar of DualShock 4 and DualSense pads). Useful to show
+//! the color of each player in couch co-op.
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct GamepadLedSystem;
+
+/// Event requesting a LED color on a gamepad. Backends ignore it for gamepads without a LED.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GamepadLedRequest {
+    pub gamepad: Gamepad,
+    /// The color of the LED, or `None` to restore the default color of the gamepad.
+    pub color: Option<Color>,
+}
+
+impl GamepadMarker {
+    /// Set the LED color of the gamepad. The color is sent again whenever the gamepad reconnects.
+    pub fn set_led(&mut self, color: Color) {
+        self.led = Some(color);
+        self.led_pending = true;
+    }
+
+    /// Restore the default LED color of the gamepad.
+    pub fn clear_led(&mut self) {
+        self.led = None;
+        self.led_pending = true;
+    }
+}
+
+/// System responsible for sending a [`GamepadLedRequest`] for every connected gamepad whose LED color changed.
+pub(crate) fn gamepad_led_system<Keys>(
+    mut query: Query<&mut GamepadMarker, With<InputView<Keys>>>,
+    mut wr: EventWriter<GamepadLedRequest>,
+) where
+    Keys: BindingTypeView,
+{
+    for mut svc in query.iter_mut() {
+        if !svc.led_pending || !svc.connected {
+            continue;
+        }
+        svc.led_pending = false;
+        wr.send(GamepadLedRequest {
+            gamepad: svc.gamepad,
+            color: svc.led,
+        });
+    }
+}
@@ -1,29 +1,157 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(any(feature = "ron_bindings", feature = "toml_bindings", feature = "json_bindings"))]
+pub mod asset;
+pub mod accessibility;
+pub mod adaptive;
+pub mod actions;
+pub mod alias;
+pub mod analog;
+pub mod automation;
+pub mod battery;
 pub mod binding;
+pub mod bitset;
+pub mod browser;
+pub mod builder;
 pub mod bundle;
+#[cfg(feature = "json_bindings")]
+pub mod export;
+pub mod context;
+pub mod delay;
+pub mod device;
+pub mod direction;
+pub mod feedback;
+pub mod flick;
+pub mod fuzz;
 pub mod gamepad;
+pub mod gesture;
+pub mod ghosting;
+pub mod group;
+pub mod history;
+pub mod hold;
+pub mod idle;
+pub mod inject;
+pub mod journal;
 pub mod keyboard;
+pub mod latency;
+pub mod layout;
+pub mod led;
+pub mod look;
 pub mod macros;
+pub mod metadata;
+#[cfg(any(feature = "ron_bindings", feature = "toml_bindings", feature = "json_bindings"))]
+pub mod migration;
+pub mod mirror;
+#[cfg(feature = "midi")]
+pub mod midi;
+pub mod motion;
 pub mod mouse;
+pub mod navigation;
+pub mod occlusion;
+pub mod player;
 pub mod plugin;
+pub mod pointer;
+pub mod processor;
+pub mod prompt;
+pub mod ramp;
+pub mod schedule;
+pub mod socd;
+pub mod source;
+pub mod staging;
 pub mod state;
+pub mod stats;
+pub mod stepped;
+pub mod text;
+pub mod token;
+pub mod trigger;
+pub mod rebind;
 pub mod receiver;
+pub mod registry;
+pub mod remap;
+pub mod replay;
+pub mod report;
+pub mod replication;
+pub mod rumble;
 pub mod view;
+pub mod virtual_gamepad;
+pub mod window;
 pub use ezinput_macros::*;
 
 pub mod prelude {
+    #[cfg(any(feature = "ron_bindings", feature = "toml_bindings", feature = "json_bindings"))]
+    pub use crate::asset::*;
+    pub use crate::accessibility::*;
+    pub use crate::adaptive::*;
+    pub use crate::actions::*;
+    pub use crate::alias::*;
+    pub use crate::analog::*;
+    pub use crate::automation::*;
+    pub use crate::battery::*;
     pub use crate::binding::*;
+    pub use crate::bitset::*;
+    pub use crate::browser::*;
+    pub use crate::builder::*;
     pub use crate::bundle::*;
+    pub use crate::context::*;
+    pub use crate::delay::*;
+    pub use crate::device::*;
+    pub use crate::direction::*;
+    pub use crate::feedback::*;
+    pub use crate::flick::*;
+    pub use crate::fuzz::*;
     pub use crate::gamepad::*;
+    pub use crate::gesture::*;
+    pub use crate::ghosting::*;
+    pub use crate::group::*;
+    pub use crate::history::*;
+    pub use crate::hold::*;
+    pub use crate::idle::*;
+    pub use crate::inject::*;
+    pub use crate::journal::*;
     pub use crate::keyboard::*;
+    pub use crate::latency::*;
+    pub use crate::layout::*;
+    pub use crate::led::*;
+    pub use crate::look::*;
     pub use crate::macros::*;
+    pub use crate::metadata::*;
+    #[cfg(any(feature = "ron_bindings", feature = "toml_bindings", feature = "json_bindings"))]
+    pub use crate::migration::*;
+    pub use crate::mirror::*;
+    #[cfg(feature = "midi")]
+    pub use crate::midi::*;
+    pub use crate::motion::*;
     pub use crate::mouse::*;
+    pub use crate::navigation::*;
+    pub use crate::occlusion::*;
+    pub use crate::player::*;
     pub use crate::plugin::*;
+    pub use crate::pointer::*;
+    pub use crate::processor::*;
+    pub use crate::prompt::*;
+    pub use crate::ramp::*;
+    pub use crate::schedule::*;
+    pub use crate::socd::*;
+    pub use crate::source::*;
+    pub use crate::staging::*;
     pub use crate::state::*;
+    pub use crate::stats::*;
+    pub use crate::stepped::*;
+    pub use crate::text::*;
+    pub use crate::token::*;
+    pub use crate::trigger::*;
+    pub use crate::rebind::*;
     pub use crate::receiver::*;
+    pub use crate::registry::*;
+    pub use crate::remap::*;
+    pub use crate::replay::*;
+    pub use crate::report::*;
+    pub use crate::replication::*;
+    pub use crate::rumble::*;
     pub use crate::view::*;
+    pub use crate::virtual_gamepad::*;
+    pub use crate::window::*;
     pub use crate::BindingTypeView;
     pub use crate::receiver::InputReceiver::*;
     pub use bevy::prelude::{GamepadAxisType, GamepadButtonType, KeyCode, MouseButton};
@@ -0,0 +1,120 @@
+//! Mouse-look accumulation and smoothing. The mouse motions of a frame are summed instead of keeping the last one,
+//! and optionally smoothed over time with a frame-rate independent filter, so the camera feels the same at 30, 60 or
+//! 144 fps. Add the [`MouseLookPlugin`] and a [`MouseLook`] component to the camera controller.
+use bevy::input::mouse::MouseMotion;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct MouseLookSystem;
+
+/// The accumulated and smoothed mouse motion of the current frame.
+#[derive(PartialEq, Clone, Copy, Debug, Component)]
+pub struct MouseLook {
+    /// The time constant of the smoothing: the time the smoothed motion takes to cover about two thirds of a change.
+    /// The motion isn't smoothed if none.
+    pub smoothing: Option<Duration>,
+    /// The factor applied to the smoothed motion.
+    pub sensitivity: f32,
+    /// The sum of the raw mouse motions of the current frame.
+    pub accumulated: Vec2,
+    /// The smoothed mouse motion in pixels per second.
+    pub velocity: Vec2,
+    /// The smoothed mouse motion of the current frame, scaled by the sensitivity.
+    pub delta: Vec2,
+}
+
+impl Default for MouseLook {
+    fn default() -> Self {
+        Self {
+            smoothing: None,
+            sensitivity: 1.,
+            accumulated: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            delta: Vec2::ZERO,
+        }
+    }
+}
+
+impl MouseLook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Smooth the motion with the given time constant.
+    pub fn with_smoothing(mut self, smoothing: Duration) -> Self {
+        self.smoothing = Some(smoothing);
+        self
+    }
+
+    pub fn with_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// Update the smoothed motion with the raw motion of a frame lasting the given time.
+    pub fn update(&mut self, accumulated: Vec2, frame_time: Duration) {
+        let dt = frame_time.as_secs_f32();
+        self.accumulated = accumulated;
+        if dt <= 0. {
+            self.delta = accumulated * self.sensitivity;
+            return;
+        }
+        let raw_velocity = accumulated / dt;
+        let alpha = match self.smoothing {
+            Some(smoothing) if !smoothing.is_zero() => 1. - (-dt / smoothing.as_secs_f32()).exp(),
+            _ => 1.,
+        };
+        self.velocity += (raw_velocity - self.velocity) * alpha;
+        // Settle once the motion is negligible, so resting mice don't update the component every frame.
+        if accumulated == Vec2::ZERO && self.velocity.length() < 0.01 {
+            self.velocity = Vec2::ZERO;
+        }
+        self.delta = self.velocity * dt * self.sensitivity;
+    }
+}
+
+/// A [`Plugin`] updating every [`MouseLook`] component each frame.
+#[derive(Default)]
+pub struct MouseLookPlugin;
+
+impl Plugin for MouseLookPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            mouse_look_system.label(MouseLookSystem).after(InputSystem),
+        );
+    }
+}
+
+/// System responsible for summing the mouse motions of the frame and smoothing them.
+pub(crate) fn mouse_look_system(
+    mut query: Query<&mut MouseLook>,
+    mut rd: EventReader<MouseMotion>,
+    time: Res<Time>,
+) {
+    let accumulated = rd.iter().fold(Vec2::ZERO, |sum, ev| sum + ev.delta);
+    for mut look in query.iter_mut() {
+        if accumulated == Vec2::ZERO && look.velocity == Vec2::ZERO && look.delta == Vec2::ZERO {
+            continue;
+        }
+        look.update(accumulated, time.delta());
+    }
+}
+
+// Test to check that the smoothed motion covers the same distance regardless of the frame rate.
+#[test]
+fn mouse_look_frame_rate_test() {
+    let distance = |fps: u32| {
+        let mut look = MouseLook::new().with_smoothing(Duration::from_millis(50));
+        let frame = Duration::from_secs(1) / fps;
+        let mut total = Vec2::ZERO;
+        for _ in 0..fps {
+            look.update(Vec2::new(600. / fps as f32, 0.), frame);
+            total += look.delta;
+        }
+        total.x
+    };
+    assert!((distance(30) - distance(144)).abs() < 10.);
+}
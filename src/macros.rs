@@ -19,6 +19,7 @@
 /// ```rust
 /// #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// pub enum EnumeratedBinding {
+///     #[action(nested)]
 ///     Movement(EnumeratedMovementBinding),
 /// }
 
@@ -59,7 +60,7 @@ macro_rules! input {
 
         #[derive(ezinput::BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum $name {
-            $($category($category_enum)),*
+            $(#[action(nested)] $category($category_enum)),*
         }
         $(
             #[derive(ezinput::BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -0,0 +1,106 @@
+//! Action categories and display order, for building settings screens without listing the actions twice. The
+//! metadata comes from the `#[action(category = "...", order = N)]` attributes of the `BindingTypeView` macro, and
+//! can be set at runtime with [`InputView::set_action_metadata`] (e.g. for actions of an [`ActionRegistry`]).
+use crate::prelude::*;
+
+/// The category of an action and its display order.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct ActionMetadata {
+    pub category: String,
+    /// The position of the action in its category. Categories are sorted by the lowest order of their actions.
+    pub order: i32,
+}
+
+/// The actions of a category, sorted by display order.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ActionCategory<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub name: String,
+    pub actions: Vec<Keys>,
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Set the metadata of an action, overriding the metadata derived for it.
+    pub fn set_action_metadata(&mut self, kind: Keys, metadata: ActionMetadata) {
+        self.metadata.insert(kind, metadata);
+    }
+
+    /// Returns the metadata of an action, or the default metadata if it has none.
+    pub fn action_metadata(&self, kind: &Keys) -> ActionMetadata {
+        self.metadata
+            .get(kind)
+            .cloned()
+            .or_else(|| kind.metadata())
+            .unwrap_or_default()
+    }
+
+    /// Returns every bound action or action with metadata, grouped by category. Categories and their actions are
+    /// sorted by display order, then by name.
+    pub fn iter_actions_grouped(&self) -> impl Iterator<Item = ActionCategory<Keys>> {
+        let mut actions: Vec<(ActionMetadata, Keys)> = self
+            .bindings
+            .keys()
+            .chain(self.metadata.keys().filter(|kind| !self.bindings.contains_key(kind)))
+            .map(|kind| (self.action_metadata(kind), *kind))
+            .collect();
+        actions.sort_by(|(a, a_kind), (b, b_kind)| {
+            a.order
+                .cmp(&b.order)
+                .then_with(|| format!("{:?}", a_kind).cmp(&format!("{:?}", b_kind)))
+        });
+        let mut categories: Vec<(i32, ActionCategory<Keys>)> = Vec::new();
+        for (metadata, kind) in actions {
+            match categories.iter_mut().find(|(_, category)| category.name == metadata.category) {
+                Some((_, category)) => category.actions.push(kind),
+                None => categories.push((
+                    metadata.order,
+                    ActionCategory {
+                        name: metadata.category,
+                        actions: vec![kind],
+                    },
+                )),
+            }
+        }
+        categories.sort_by(|(a_order, a), (b_order, b)| a_order.cmp(b_order).then_with(|| a.name.cmp(&b.name)));
+        categories.into_iter().map(|(_, category)| category)
+    }
+}
+
+// Test to check that actions are grouped by their derived or runtime category in display order.
+#[test]
+fn iter_actions_grouped_test() {
+    use ezinput_macros::BindingTypeView;
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        #[action(category = "Movement", order = 2)]
+        Crouch,
+        #[action(category = "Movement", order = 1)]
+        Jump,
+        #[action(category = "Menu", order = 10)]
+        Pause,
+        Interact,
+    }
+
+    let mut view = InputView::new();
+    for action in [Action::Crouch, Action::Jump, Action::Pause] {
+        view.add_binding(&mut ActionBinding::from(action));
+    }
+    view.set_action_metadata(
+        Action::Interact,
+        ActionMetadata {
+            category: "Movement".to_string(),
+            order: 3,
+        },
+    );
+    let categories: Vec<ActionCategory<Action>> = view.iter_actions_grouped().collect();
+    assert_eq!(categories.len(), 2);
+    assert_eq!(categories[0].name, "Movement");
+    assert_eq!(categories[0].actions, vec![Action::Jump, Action::Crouch, Action::Interact]);
+    assert_eq!(categories[1].actions, vec![Action::Pause]);
+}
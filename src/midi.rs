@@ -0,0 +1,163 @@
+//! MIDI controller backend, mapping note and control change messages to [`InputReceiver::Custom`] receivers.
+//! Requires the `midi` feature.
+use std::sync::{Arc, Mutex};
+
+use bevy::ecs::event::Events;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use midir::{MidiInput, MidiInputConnection};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct MidiInputHandlingSystem;
+
+/// Prefix of every custom receiver created by the MIDI backend, avoiding clashes with user-defined receivers.
+const MIDI_RECEIVER_PREFIX: u64 = 0x4d49_4449 << 32;
+
+/// A message received from a MIDI controller.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum MidiMessage {
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+    NoteOff { channel: u8, note: u8 },
+    ControlChange { channel: u8, control: u8, value: u8 },
+}
+
+impl MidiMessage {
+    /// Parse a raw MIDI message. Returns none for unsupported messages.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let (status, data1, data2) = match *bytes {
+            [status, data1, data2, ..] => (status, data1, data2),
+            _ => return None,
+        };
+        let channel = status & 0x0f;
+        match status & 0xf0 {
+            0x90 if data2 > 0 => Some(MidiMessage::NoteOn {
+                channel,
+                note: data1,
+                velocity: data2,
+            }),
+            0x80 | 0x90 => Some(MidiMessage::NoteOff {
+                channel,
+                note: data1,
+            }),
+            0xb0 => Some(MidiMessage::ControlChange {
+                channel,
+                control: data1,
+                value: data2,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the receiver affected by this message and its normalized value.
+    pub fn receiver(&self) -> (InputReceiver, f32) {
+        match *self {
+            MidiMessage::NoteOn {
+                channel,
+                note,
+                velocity,
+            } => (InputReceiver::midi_note(channel, note), velocity as f32 / 127.),
+            MidiMessage::NoteOff { channel, note } => (InputReceiver::midi_note(channel, note), 0.),
+            MidiMessage::ControlChange {
+                channel,
+                control,
+                value,
+            } => (
+                InputReceiver::midi_control(channel, control),
+                value as f32 / 127.,
+            ),
+        }
+    }
+}
+
+impl InputReceiver {
+    /// Returns the custom receiver for a MIDI note. The value is the note velocity, from 0 to 1.
+    pub fn midi_note(channel: u8, note: u8) -> InputReceiver {
+        InputReceiver::Custom(MIDI_RECEIVER_PREFIX | (channel as u64) << 8 | note as u64)
+    }
+
+    /// Returns the custom receiver for a MIDI control (CC). The value is the control value, from 0 to 1.
+    pub fn midi_control(channel: u8, control: u8) -> InputReceiver {
+        InputReceiver::Custom(MIDI_RECEIVER_PREFIX | 1 << 16 | (channel as u64) << 8 | control as u64)
+    }
+}
+
+/// Messages received by the MIDI connection and not yet handled.
+#[derive(Clone, Default)]
+pub struct MidiMessageQueue(pub Arc<Mutex<Vec<MidiMessage>>>);
+
+/// The open MIDI connection. Dropping it closes the connection.
+pub struct MidiConnection(pub MidiInputConnection<()>);
+
+/// A [`Plugin`] connecting to a MIDI input port and feeding its messages to every input view as custom receivers.
+/// The first port whose name contains `port_name` is used, or the first available port if none.
+#[derive(Clone, Debug, Default)]
+pub struct MidiInputPlugin {
+    pub port_name: Option<String>,
+}
+
+impl Plugin for MidiInputPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<Events<InjectedInput>>() {
+            app.add_event::<InjectedInput>();
+        }
+        let queue = MidiMessageQueue::default();
+        app.insert_resource(queue.clone());
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            midi_input_system
+                .label(MidiInputHandlingSystem)
+                .label(EZInputSet::Collect)
+                .after(InputSystem)
+                .before(InjectedInputHandlingSystem),
+        );
+
+        let input = match MidiInput::new("ezinput") {
+            Ok(input) => input,
+            Err(err) => {
+                warn!("Unable to initialize MIDI input: {}", err);
+                return;
+            }
+        };
+        let port = input.ports().into_iter().find(|port| {
+            self.port_name.as_ref().is_none_or(|name| {
+                input
+                    .port_name(port)
+                    .is_ok_and(|port_name| port_name.contains(name.as_str()))
+            })
+        });
+        let port = match port {
+            Some(port) => port,
+            None => {
+                warn!("No MIDI input port found");
+                return;
+            }
+        };
+        let connection = input.connect(
+            &port,
+            "ezinput",
+            move |_, bytes, _| {
+                if let Some(message) = MidiMessage::parse(bytes) {
+                    queue.0.lock().unwrap().push(message);
+                }
+            },
+            (),
+        );
+        match connection {
+            Ok(connection) => {
+                app.insert_non_send_resource(MidiConnection(connection));
+            }
+            Err(err) => warn!("Unable to connect to the MIDI input port: {}", err),
+        }
+    }
+}
+
+/// Input system responsible for injecting every received MIDI message into the input views.
+pub(crate) fn midi_input_system(queue: Res<MidiMessageQueue>, mut injector: InputInjector) {
+    let messages = std::mem::take(&mut *queue.0.lock().unwrap());
+    for message in messages {
+        let (receiver, value) = message.receiver();
+        injector.set_axis(receiver, value);
+    }
+}
@@ -0,0 +1,179 @@
+//! Versioned bindings files. When a game adds actions or changes its bindings format, [`BindingMigrations`] upgrade
+//! the files saved by previous versions step by step, fill the new actions with their defaults, and report the
+//! bindings that couldn't be migrated instead of failing to load the whole file.
+use serde::de::DeserializeOwned;
+
+use crate::prelude::*;
+
+/// A migration step, upgrading bindings from a version to the next one.
+pub type MigrationStep = Box<dyn Fn(&mut InputBindings<ActionName>) + Send + Sync>;
+
+/// A binding dropped while migrating.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct UnmigratableBinding {
+    /// The name of the action in the migrated file.
+    pub action: String,
+    pub reason: String,
+}
+
+/// The outcome of a migration.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// The bindings whose action doesn't exist anymore, even through the aliases.
+    pub unmigratable: Vec<UnmigratableBinding>,
+    /// The actions missing from the file, bound to their defaults.
+    pub defaulted: Vec<String>,
+}
+
+impl MigrationReport {
+    /// Returns whether every binding was migrated.
+    pub fn is_clean(&self) -> bool {
+        self.unmigratable.is_empty()
+    }
+}
+
+/// The current version of the bindings format, the steps upgrading old files to it and the default bindings.
+pub struct BindingMigrations<Keys> {
+    pub version: u32,
+    /// The steps upgrading each version to the next one, in order.
+    pub steps: Vec<(u32, MigrationStep)>,
+    /// The aliases of the renamed actions, applied after the steps.
+    pub aliases: ActionAliases,
+    /// The bindings of the actions missing from a migrated file.
+    pub defaults: Vec<BindingDescription<Keys>>,
+}
+
+impl<Keys> BindingMigrations<Keys>
+where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    /// Creates the migrations to the given version of the bindings format.
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            steps: Vec::new(),
+            aliases: ActionAliases::default(),
+            defaults: Vec::new(),
+        }
+    }
+
+    /// Add a step upgrading the bindings of the given version to the next one.
+    pub fn step(mut self, from: u32, step: impl Fn(&mut InputBindings<ActionName>) + Send + Sync + 'static) -> Self {
+        self.steps.push((from, Box::new(step)));
+        self
+    }
+
+    /// Set the aliases of the renamed actions.
+    pub fn aliases(mut self, aliases: ActionAliases) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Set the default bindings, usually described from the default input view with [`InputBindings::from_view`].
+    pub fn defaults(mut self, defaults: InputBindings<Keys>) -> Self {
+        self.defaults = defaults.bindings;
+        self
+    }
+
+    /// Upgrade bindings to the current version, returning the migrated bindings and a report of the changes.
+    pub fn migrate(&self, mut bindings: InputBindings<ActionName>) -> (InputBindings<Keys>, MigrationReport) {
+        let mut report = MigrationReport {
+            from_version: bindings.version,
+            to_version: self.version,
+            ..Default::default()
+        };
+        for version in bindings.version..self.version {
+            for (_, step) in self.steps.iter().filter(|(from, _)| *from == version) {
+                step(&mut bindings);
+            }
+        }
+        let mut migrated: Vec<BindingDescription<Keys>> = Vec::with_capacity(bindings.bindings.len());
+        for description in bindings.bindings {
+            let while_held: Result<Vec<Keys>, _> = description
+                .while_held
+                .iter()
+                .map(|held| self.aliases.action(&held.0))
+                .collect();
+            match self.aliases.action(&description.action.0).and_then(|action| Ok((action, while_held?))) {
+                Ok((action, while_held)) => migrated.push(BindingDescription {
+                    action,
+                    receivers: description.receivers,
+                    default_axis_values: description.default_axis_values,
+                    priority: description.priority,
+                    while_held,
+                    required_modifiers: description.required_modifiers,
+                    forbidden_modifiers: description.forbidden_modifiers,
+                    action_type: description.action_type,
+                }),
+                Err(error) => report.unmigratable.push(UnmigratableBinding {
+                    action: description.action.0,
+                    reason: error.to_string(),
+                }),
+            }
+        }
+        for default in self.defaults.iter() {
+            if !migrated.iter().any(|description| description.action == default.action) {
+                report.defaulted.push(format!("{:?}", default.action));
+                migrated.push(default.clone());
+            }
+        }
+        let bindings = InputBindings {
+            version: self.version,
+            bindings: migrated,
+        };
+        (bindings, report)
+    }
+}
+
+// Test to check that old files are upgraded step by step, with the new actions defaulted and unknown ones reported.
+#[test]
+fn binding_migration_test() {
+    use bevy::prelude::KeyCode;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+    enum Action {
+        Jump,
+        Interact,
+    }
+    impl BindingTypeView for Action {}
+
+    fn describe<Keys>(action: Keys, key: KeyCode) -> BindingDescription<Keys> {
+        BindingDescription {
+            action,
+            receivers: vec![vec![InputReceiver::KeyboardKey(key)]],
+            default_axis_values: Vec::new(),
+            priority: 0,
+            while_held: Vec::new(),
+            required_modifiers: Modifiers::NONE,
+            forbidden_modifiers: Modifiers::NONE,
+            action_type: ActionType::Any,
+        }
+    }
+    let migrations = BindingMigrations::new(2)
+        .step(1, |bindings| {
+            for description in bindings.bindings.iter_mut().filter(|description| description.action.0 == "Use") {
+                description.action = ActionName("Interact".to_string());
+            }
+        })
+        .defaults(InputBindings {
+            version: 2,
+            bindings: vec![describe(Action::Jump, KeyCode::Space)],
+        });
+    let old = InputBindings {
+        version: 1,
+        bindings: vec![
+            describe(ActionName("Use".to_string()), KeyCode::F),
+            describe(ActionName("Fly".to_string()), KeyCode::G),
+        ],
+    };
+    let (bindings, report) = migrations.migrate(old);
+    assert_eq!(bindings.version, 2);
+    assert_eq!(bindings.bindings[0], describe(Action::Interact, KeyCode::F));
+    assert_eq!(bindings.bindings[1].action, Action::Jump);
+    assert_eq!(report.defaulted, vec!["Jump".to_string()]);
+    assert_eq!(report.unmigratable.len(), 1);
+    assert_eq!(report.unmigratable[0].action, "Fly");
+}
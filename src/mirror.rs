@@ -0,0 +1,215 @@
+//! Mirroring of binding profiles between the left and the right hand, so "southpaw" and left-handed options are
+//! derived from the default bindings instead of being maintained as duplicate tables.
+use bevy::prelude::{GamepadAxisType, GamepadButtonType, KeyCode};
+
+use crate::prelude::*;
+
+/// The keyboard clusters swapped when mirroring.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum KeyMirror {
+    /// Keep the keyboard bindings.
+    #[default]
+    None,
+    /// Swap WASD with IJKL.
+    WasdIjkl,
+    /// Swap WASD with the arrow keys.
+    WasdArrows,
+}
+
+impl KeyMirror {
+    /// Returns the pairs of keys swapped.
+    pub fn pairs(&self) -> &'static [(KeyCode, KeyCode)] {
+        match self {
+            KeyMirror::None => &[],
+            KeyMirror::WasdIjkl => &[
+                (KeyCode::W, KeyCode::I),
+                (KeyCode::A, KeyCode::J),
+                (KeyCode::S, KeyCode::K),
+                (KeyCode::D, KeyCode::L),
+            ],
+            KeyMirror::WasdArrows => &[
+                (KeyCode::W, KeyCode::Up),
+                (KeyCode::A, KeyCode::Left),
+                (KeyCode::S, KeyCode::Down),
+                (KeyCode::D, KeyCode::Right),
+            ],
+        }
+    }
+}
+
+/// What is swapped between the hands when mirroring a binding profile.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct MirrorOptions {
+    /// Swap the sticks, including their buttons.
+    pub sticks: bool,
+    /// Swap the shoulder buttons and triggers.
+    pub shoulders: bool,
+    pub keys: KeyMirror,
+}
+
+impl Default for MirrorOptions {
+    fn default() -> Self {
+        Self::left_handed()
+    }
+}
+
+impl MirrorOptions {
+    /// Only swap the sticks, the usual "southpaw" option.
+    pub fn southpaw() -> Self {
+        Self {
+            sticks: true,
+            shoulders: false,
+            keys: KeyMirror::None,
+        }
+    }
+
+    /// Swap the sticks, the shoulders and the WASD cluster with IJKL.
+    pub fn left_handed() -> Self {
+        Self {
+            sticks: true,
+            shoulders: true,
+            keys: KeyMirror::WasdIjkl,
+        }
+    }
+
+    /// Returns the receiver on the other hand, or the same receiver if it isn't swapped.
+    pub fn mirror(&self, receiver: InputReceiver) -> InputReceiver {
+        use GamepadAxisType as Axis;
+        use GamepadButtonType as Button;
+
+        match receiver {
+            InputReceiver::GamepadAxis(axis) => {
+                let axis = match axis {
+                    Axis::LeftStickX if self.sticks => Axis::RightStickX,
+                    Axis::LeftStickY if self.sticks => Axis::RightStickY,
+                    Axis::RightStickX if self.sticks => Axis::LeftStickX,
+                    Axis::RightStickY if self.sticks => Axis::LeftStickY,
+                    Axis::LeftZ if self.shoulders => Axis::RightZ,
+                    Axis::RightZ if self.shoulders => Axis::LeftZ,
+                    axis => axis,
+                };
+                InputReceiver::GamepadAxis(axis)
+            }
+            InputReceiver::GamepadButton(button) => {
+                let button = match button {
+                    Button::LeftThumb if self.sticks => Button::RightThumb,
+                    Button::RightThumb if self.sticks => Button::LeftThumb,
+                    Button::LeftTrigger if self.shoulders => Button::RightTrigger,
+                    Button::RightTrigger if self.shoulders => Button::LeftTrigger,
+                    Button::LeftTrigger2 if self.shoulders => Button::RightTrigger2,
+                    Button::RightTrigger2 if self.shoulders => Button::LeftTrigger2,
+                    button => button,
+                };
+                InputReceiver::GamepadButton(button)
+            }
+            InputReceiver::KeyboardKey(key) | InputReceiver::KeyboardAnalog(key) => {
+                let mirrored = self.keys.pairs().iter().find_map(|(left, right)| {
+                    if *left == key {
+                        Some(*right)
+                    } else if *right == key {
+                        Some(*left)
+                    } else {
+                        None
+                    }
+                });
+                match (receiver, mirrored) {
+                    (InputReceiver::KeyboardKey(_), Some(key)) => InputReceiver::KeyboardKey(key),
+                    (InputReceiver::KeyboardAnalog(_), Some(key)) => InputReceiver::KeyboardAnalog(key),
+                    _ => receiver,
+                }
+            }
+            receiver => receiver,
+        }
+    }
+
+    /// Returns the collection of receivers on the other hand.
+    pub fn mirror_all(&self, receivers: &InputReceivers) -> InputReceivers {
+        InputReceivers(receivers.0.iter().map(|rcv| self.mirror(*rcv)).collect())
+    }
+}
+
+impl<Keys> ActionBinding<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the binding with its receivers mirrored to the other hand.
+    pub fn mirrored(&self, options: &MirrorOptions) -> Self {
+        let mut binding = self.clone();
+        binding.input_receivers = self
+            .input_receivers
+            .iter()
+            .map(|receivers| options.mirror_all(receivers))
+            .collect();
+        binding.default_axis_value = self
+            .default_axis_value
+            .iter()
+            .map(|(rcv, value)| (options.mirror(*rcv), *value))
+            .collect();
+        binding
+    }
+}
+
+#[cfg(any(feature = "ron_bindings", feature = "toml_bindings", feature = "json_bindings"))]
+impl<Keys> InputBindings<Keys>
+where
+    Keys: Clone,
+{
+    /// Returns a new binding profile with every receiver mirrored to the other hand.
+    pub fn mirrored(&self, options: &MirrorOptions) -> Self {
+        let mut bindings = self.clone();
+        for description in bindings.bindings.iter_mut() {
+            for receivers in description.receivers.iter_mut() {
+                receivers.iter_mut().for_each(|rcv| *rcv = options.mirror(*rcv));
+            }
+            for (rcv, _) in description.default_axis_values.iter_mut() {
+                *rcv = options.mirror(*rcv);
+            }
+        }
+        bindings
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Store the active bindings mirrored to the other hand as a named binding set, to be activated with
+    /// [`InputView::switch_set`].
+    pub fn add_mirrored_set(&mut self, name: impl Into<String>, options: &MirrorOptions) {
+        let bindings = self
+            .bindings
+            .values()
+            .map(|binding| binding.mirrored(options))
+            .collect();
+        self.add_binding_set(name, bindings);
+    }
+}
+
+// Test to check that a left-handed profile swaps the sticks, the shoulders and the movement keys.
+#[test]
+fn mirrored_binding_test() {
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    enum Action {
+        Move,
+        Shoot,
+    }
+    impl BindingTypeView for Action {}
+
+    let options = MirrorOptions::left_handed();
+    let movement = ActionBinding::from(Action::Move)
+        .receivers(InputReceiver::GamepadAxis(GamepadAxisType::LeftStickX).into())
+        .receivers(InputReceiver::KeyboardKey(KeyCode::D).into())
+        .default_axis_value(KeyCode::A.into(), -1.)
+        .mirrored(&options);
+    let bound = |binding: &ActionBinding<Action>, receiver: InputReceiver| {
+        binding.input_receivers.contains(&InputReceivers::from(receiver))
+    };
+    assert!(bound(&movement, GamepadAxisType::RightStickX.into()));
+    assert!(bound(&movement, KeyCode::L.into()));
+    assert_eq!(movement.default_axis_value.get(&KeyCode::J.into()), Some(&-1.));
+
+    let shoot = ActionBinding::from(Action::Shoot)
+        .receivers(InputReceiver::GamepadButton(GamepadButtonType::RightTrigger2).into())
+        .mirrored(&MirrorOptions::southpaw());
+    assert!(bound(&shoot, GamepadButtonType::RightTrigger2.into()));
+}
@@ -0,0 +1,123 @@
+//! Gyro and accelerometer support for EZInput. Bevy doesn't expose motion sensors yet, so the data needs to be fed by
+//! a backend (e.g. gilrs extensions or a platform SDK) through [`GamepadMotionEvent`]s.
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct GamepadMotionHandlingSystem;
+
+/// All axis that can be reported by a motion sensor.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum MotionAxisType {
+    X,
+    Y,
+    Z,
+}
+
+/// The motion sensor that reported a value.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum MotionSensor {
+    Gyro,
+    Accelerometer,
+}
+
+impl MotionSensor {
+    /// Returns the input receiver for the given axis of this sensor.
+    pub fn receiver(&self, axis: MotionAxisType) -> InputReceiver {
+        match self {
+            MotionSensor::Gyro => InputReceiver::GamepadGyro(axis),
+            MotionSensor::Accelerometer => InputReceiver::GamepadAccelerometer(axis),
+        }
+    }
+}
+
+/// Event to be sent by backends whenever a motion sensor of a gamepad reports a new value.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GamepadMotionEvent {
+    pub gamepad: Gamepad,
+    pub sensor: MotionSensor,
+    pub axis: MotionAxisType,
+    pub value: f32,
+}
+
+/// Sensitivity and calibration of the motion sensors of a gamepad.
+#[derive(PartialEq, Clone, Debug)]
+pub struct MotionSettings {
+    pub gyro_sensitivity: f32,
+    pub accelerometer_sensitivity: f32,
+    /// The offsets subtracted from the raw values, set by calibrating.
+    pub offsets: HashMap<InputReceiver, f32>,
+    /// The last raw values reported for each motion receiver.
+    pub raw: HashMap<InputReceiver, f32>,
+}
+
+impl Default for MotionSettings {
+    fn default() -> Self {
+        Self {
+            gyro_sensitivity: 1.,
+            accelerometer_sensitivity: 1.,
+            offsets: HashMap::default(),
+            raw: HashMap::default(),
+        }
+    }
+}
+
+impl MotionSettings {
+    /// Returns the calibrated value for a raw sensor value, with the sensitivity applied.
+    pub fn apply(&self, sensor: MotionSensor, axis: MotionAxisType, value: f32) -> f32 {
+        let offset = self
+            .offsets
+            .get(&sensor.receiver(axis))
+            .copied()
+            .unwrap_or(0.);
+        let sensitivity = match sensor {
+            MotionSensor::Gyro => self.gyro_sensitivity,
+            MotionSensor::Accelerometer => self.accelerometer_sensitivity,
+        };
+        (value - offset) * sensitivity
+    }
+}
+
+impl GamepadMarker {
+    /// Change the current motion axis state for the given sensor and set the last input source to Gamepad.
+    pub fn set_gamepad_motion_state<Keys>(
+        &mut self,
+        view: &mut InputView<Keys>,
+        sensor: MotionSensor,
+        axis: MotionAxisType,
+        value: f32,
+    ) where
+        Keys: BindingTypeView,
+    {
+        let receiver = sensor.receiver(axis);
+        self.motion.raw.insert(receiver, value);
+        let value = self.motion.apply(sensor, axis, value);
+        view.set_input_source(InputSource::Gamepad);
+        view.set_axis_value(receiver, value, self.press_state(value));
+    }
+
+    /// Calibrate the motion sensors to zero, using the last reported values as the resting position.
+    pub fn calibrate_motion(&mut self) {
+        self.motion.offsets = self.motion.raw.clone();
+    }
+}
+
+/// Input system responsible for handling motion sensor events and setting the axis state for each view.
+pub(crate) fn gamepad_motion_system<Keys>(
+    mut query: Query<(&mut InputView<Keys>, &mut GamepadMarker)>,
+    mut rd: EventReader<GamepadMotionEvent>,
+) where
+    Keys: BindingTypeView,
+{
+    for ev in rd.iter() {
+        for (mut view, mut svc) in query.iter_mut() {
+            if ev.gamepad != svc.gamepad {
+                continue;
+            }
+            svc.set_gamepad_motion_state::<Keys>(view.as_mut(), ev.sensor, ev.axis, ev.value);
+        }
+    }
+}
@@ -3,11 +3,13 @@
 use std::hash::Hash;
 
 use crate::prelude::*;
+use bevy::utils::{Duration, Instant};
 use bevy::{
+    ecs::system::SystemParam,
     input::mouse::{MouseButtonInput, MouseMotion, MouseWheel},
     math::Vec2,
-    prelude::{Component, EventReader, MouseButton, Query, SystemLabel},
-    window::CursorMoved,
+    prelude::{Component, EventReader, MouseButton, Query, Res, SystemLabel},
+    window::{CursorMoved, WindowId},
 };
 use serde::{Deserialize, Serialize};
 
@@ -23,23 +25,45 @@ pub enum MouseAxisType {
 }
 
 /// Mouse button, location and delta support for EZInput.
-#[derive(PartialEq, Debug, Component, Clone, Default)]
+#[derive(PartialEq, Debug, Component, Clone)]
 pub struct MouseMarker {
     pub mouse_position: Option<Vec2>,
     pub mouse_delta: Option<Vec2>,
     pub does_mouse_location_changed_this_tick: bool,
     pub does_mouse_wheel_changed_this_tick: bool,
+    /// The thresholds used to recognize a left button drag as a swipe.
+    pub swipe: GestureSettings,
+    /// The position and instant in which the left button started dragging.
+    pub drag_start: Option<(Vec2, Instant)>,
+    /// The maximum time between two presses of a button to be recognized as a double click.
+    pub double_click_interval: Duration,
+    /// The last pressed button and the instant in which it was pressed.
+    pub last_click: Option<(MouseButton, Instant)>,
+    /// The button whose double click receiver was pressed this frame, released on the next frame.
+    pub double_clicked: Option<MouseButton>,
+}
+
+impl Default for MouseMarker {
+    fn default() -> Self {
+        Self {
+            mouse_position: None,
+            mouse_delta: None,
+            does_mouse_location_changed_this_tick: false,
+            does_mouse_wheel_changed_this_tick: false,
+            swipe: GestureSettings::default(),
+            drag_start: None,
+            double_click_interval: Duration::from_millis(500),
+            last_click: None,
+            double_clicked: None,
+        }
+    }
 }
 
 impl MouseMarker {
     /// Change the current mouse location and delta and set the last input source to Mouse.
-    pub fn set_mouse_location<Keys>(
-        &mut self,
-        view: &mut InputView<Keys>,
-        position: Vec2,
-        delta: Vec2,
-    ) where
-        Keys: BindingTypeView,
+    pub fn set_mouse_location<S>(&mut self, view: &mut S, position: Vec2, delta: Vec2)
+    where
+        S: InputSink,
     {
         let state = PressState::Pressed {
             started_pressing_instant: None,
@@ -69,20 +93,40 @@ impl MouseMarker {
         self.mouse_delta = Some(delta);
         self.mouse_position = Some(position);
         self.does_mouse_location_changed_this_tick = true;
-        view.last_input_source = Some(InputSource::Mouse);
+        view.set_input_source(InputSource::Mouse);
+    }
+
+    /// Returns whether the mouse needs to be ticked, avoiding unnecessary changes to the input view.
+    pub fn needs_tick<S>(&self, view: &S) -> bool
+    where
+        S: InputSink,
+    {
+        self.does_mouse_location_changed_this_tick
+            || self.does_mouse_wheel_changed_this_tick
+            || self.mouse_delta.is_some()
+            || [
+                InputReceiver::MouseAxis(MouseAxisType::X),
+                InputReceiver::MouseAxis(MouseAxisType::Y),
+                InputReceiver::MouseAxis(MouseAxisType::Wheel),
+                InputReceiver::MouseAxisDelta(MouseAxisType::X),
+                InputReceiver::MouseAxisDelta(MouseAxisType::Y),
+                InputReceiver::MouseAxisDelta(MouseAxisType::Wheel),
+            ]
+            .iter()
+            .any(|rcv| {
+                let state = view.receiver_state(rcv);
+                state.press.pressed()
+                    || (*rcv != InputReceiver::MouseAxis(MouseAxisType::X) && state.value != 0.)
+            })
     }
 
     /// Tick the mouse by stop moving the axis when released.
-    pub fn tick_mouse<Keys>(&mut self, view: &mut InputView<Keys>)
+    pub fn tick_mouse<S>(&mut self, view: &mut S)
     where
-        Keys: BindingTypeView,
+        S: InputSink,
     {
-        view.descriptor_or_insert(InputReceiver::MouseAxis(MouseAxisType::X))
-            .axis
-            .press = PressState::Released;
-        view.descriptor_or_insert(InputReceiver::MouseAxis(MouseAxisType::Y))
-            .axis
-            .press = PressState::Released;
+        view.release_receiver(InputReceiver::MouseAxis(MouseAxisType::X));
+        view.release_receiver(InputReceiver::MouseAxis(MouseAxisType::Y));
         view.set_axis_value(
             InputReceiver::MouseAxis(MouseAxisType::Y),
             0.,
@@ -114,63 +158,190 @@ impl MouseMarker {
     }
 
     /// Set the mouse button state for the given button and set the last input source to Mouse.
-    pub fn set_mouse_button_state<Keys>(
-        &mut self,
-        view: &mut InputView<Keys>,
-        button: MouseButton,
-        state: PressState,
-    ) where
-        Keys: BindingTypeView,
+    pub fn set_mouse_button_state<S>(&mut self, view: &mut S, button: MouseButton, state: PressState)
+    where
+        S: InputSink,
     {
-        view.last_input_source = Some(InputSource::Mouse);
+        view.set_input_source(InputSource::Mouse);
         view.set_key_receiver_state(InputReceiver::MouseButton(button), state);
     }
 
+    /// Track left button drags, pressing the matching swipe receiver when a drag is recognized as a swipe.
+    pub fn track_drag<S>(&mut self, view: &mut S, button: MouseButton, state: PressState)
+    where
+        S: InputSink,
+    {
+        if button != MouseButton::Left {
+            return;
+        }
+        let position = match self.mouse_position {
+            Some(position) => position,
+            None => return,
+        };
+        if state.pressed() {
+            self.drag_start = Some((position, Instant::now()));
+        } else if let Some((start, instant)) = self.drag_start.take() {
+            let swipe = self.swipe.swipe(position - start, instant.elapsed());
+            if let Some(Gesture::Swipe { direction, .. }) = swipe {
+                view.set_swipe(Direction::from_vec(direction), InputSource::Mouse);
+            }
+        }
+    }
+
+    /// Press the double click receiver of a button for a single frame when it's pressed twice within the double
+    /// click interval.
+    pub fn track_double_click<S>(&mut self, view: &mut S, button: MouseButton, state: PressState)
+    where
+        S: InputSink,
+    {
+        if !state.pressed() {
+            return;
+        }
+        let now = Instant::now();
+        match self.last_click {
+            Some((last, instant))
+                if last == button && now.duration_since(instant) <= self.double_click_interval =>
+            {
+                view.set_key_receiver_state(InputReceiver::MouseDoubleClick(button), state);
+                self.double_clicked = Some(button);
+                self.last_click = None;
+            }
+            _ => self.last_click = Some((button, now)),
+        }
+    }
+
+    /// Release the double click receiver pressed in the previous frame, if any.
+    pub fn release_double_click<S>(&mut self, view: &mut S)
+    where
+        S: InputSink,
+    {
+        if let Some(button) = self.double_clicked.take() {
+            view.set_key_receiver_state(InputReceiver::MouseDoubleClick(button), PressState::Released);
+        }
+    }
+
     /// Set the mouse wheel state and set the last input source to Mouse.
-    pub fn set_mouse_wheel_state<Keys>(
-        &mut self,
-        view: &mut InputView<Keys>,
-        y: f32,
-        state: PressState,
-    ) where
-        Keys: BindingTypeView,
+    pub fn set_mouse_wheel_state<S>(&mut self, view: &mut S, y: f32, state: PressState)
+    where
+        S: InputSink,
     {
-        view.last_input_source = Some(InputSource::Mouse);
+        view.set_input_source(InputSource::Mouse);
         view.set_axis_value(InputReceiver::MouseAxis(MouseAxisType::Wheel), y, state);
         self.does_mouse_wheel_changed_this_tick = true;
     }
 }
 
-/// Input system responsible for handling mouse input and setting the button state for each updated button and axis.
+/// The mouse events of specific devices, see [`InputDevice`].
+#[derive(SystemParam)]
+pub(crate) struct DeviceMouseEvents<'w, 's> {
+    buttons: EventReader<'w, 's, DeviceMouseButtonInput>,
+    motions: EventReader<'w, 's, DeviceMouseMotion>,
+    wheels: EventReader<'w, 's, DeviceMouseWheel>,
+}
+
+/// Input system responsible for handling mouse input and staging the button state for each updated button and axis.
+#[allow(clippy::type_complexity)]
 pub(crate) fn mouse_input_system<Keys>(
-    mut query: Query<(&mut InputView<Keys>, &mut MouseMarker)>,
+    mut query: Query<(
+        &InputView<Keys>,
+        &mut MouseMarker,
+        &mut StagedInput<Keys, MouseMarker>,
+        Option<&InputDevice>,
+        Option<&WindowFilter>,
+    )>,
+    windows: Res<MouseWindows>,
     mut cursor_rd: EventReader<CursorMoved>,
     mut btn_rd: EventReader<MouseButtonInput>,
     mut mtn_rd: EventReader<MouseMotion>,
     mut wheel_rd: EventReader<MouseWheel>,
+    mut device_rd: DeviceMouseEvents,
 ) where
     Keys: BindingTypeView,
 {
-    for (mut view, mut mouse_svc) in query.iter_mut() {
-        let view = view.as_mut();
-        let mouse_svc = mouse_svc.as_mut();
-        mouse_svc.tick_mouse(view);
+    let locations: Vec<(WindowId, Vec2, Vec2)> = cursor_rd
+        .iter()
+        .zip(mtn_rd.iter())
+        .map(|(abs_position, delta)| (abs_position.id, abs_position.position, delta.delta))
+        .collect();
+    let buttons: Vec<(MouseButton, PressState)> =
+        btn_rd.iter().map(|ev| (ev.button, ev.state.into())).collect();
+    let wheels: Vec<f32> = wheel_rd.iter().map(|ev| ev.y).collect();
+    let device_buttons: Vec<DeviceMouseButtonInput> = device_rd.buttons.iter().copied().collect();
+    let device_motions: Vec<DeviceMouseMotion> = device_rd.motions.iter().copied().collect();
+    let device_wheels: Vec<DeviceMouseWheel> = device_rd.wheels.iter().copied().collect();
 
-        for (abs_position, delta) in cursor_rd.iter().zip(mtn_rd.iter()) {
-            mouse_svc.set_mouse_location(view, abs_position.position, delta.delta);
+    for (view, mut mouse_svc, mut staged, device, filter) in query.iter_mut() {
+        let view = &mut StagedView::new(view, &mut staged);
+        if mouse_svc.needs_tick(view) {
+            mouse_svc.tick_mouse(view);
+        }
+        let (locations, buttons, wheels) = match device.and_then(|device| device.mouse) {
+            Some(device) => {
+                let mut position = mouse_svc.mouse_position.unwrap_or_default();
+                let locations: Vec<(Vec2, Vec2)> = device_motions
+                    .iter()
+                    .filter(|ev| ev.device == device)
+                    .map(|ev| {
+                        position += ev.delta;
+                        (position, ev.delta)
+                    })
+                    .collect();
+                let buttons: Vec<(MouseButton, PressState)> = device_buttons
+                    .iter()
+                    .filter(|ev| ev.device == device)
+                    .map(|ev| (ev.button, ev.state))
+                    .collect();
+                let wheels: Vec<f32> = device_wheels
+                    .iter()
+                    .filter(|ev| ev.device == device)
+                    .map(|ev| ev.y)
+                    .collect();
+                (locations, buttons, wheels)
+            }
+            None => match filter {
+                Some(filter) => {
+                    let locations = locations
+                        .iter()
+                        .filter(|(window, ..)| *window == filter.window)
+                        .map(|&(_, position, delta)| (position, delta))
+                        .collect();
+                    if filter.accepts(&windows) {
+                        (locations, buttons.clone(), wheels.clone())
+                    } else {
+                        (locations, Vec::new(), Vec::new())
+                    }
+                }
+                None => (
+                    locations.iter().map(|&(_, position, delta)| (position, delta)).collect(),
+                    buttons.clone(),
+                    wheels.clone(),
+                ),
+            },
+        };
+
+        for &(position, delta) in locations.iter() {
+            mouse_svc.set_mouse_location(view, position, delta);
+        }
+        if view.has_stale_swipes() {
+            view.release_stale_swipes();
+        }
+        if mouse_svc.double_clicked.is_some() {
+            mouse_svc.release_double_click(view);
         }
-        for ev in btn_rd.iter() {
-            mouse_svc.set_mouse_button_state(view, ev.button, ev.state.into());
+        for &(button, state) in buttons.iter() {
+            mouse_svc.set_mouse_button_state(view, button, state);
+            mouse_svc.track_drag(view, button, state);
+            mouse_svc.track_double_click(view, button, state);
         }
-        for ev in wheel_rd.iter() {
-            let state = if ev.y > 0. {
+        for &y in wheels.iter() {
+            let state = if y > 0. {
                 PressState::Pressed {
                     started_pressing_instant: None,
                 }
             } else {
                 PressState::Released    
             };
-            mouse_svc.set_mouse_wheel_state(view, ev.y, state);
+            mouse_svc.set_mouse_wheel_state(view, y, state);
         }
     }
 }
@@ -0,0 +1,221 @@
+//! UI navigation driven by actions, so menus become navigable with a gamepad or the keyboard by adding
+//! [`UiNavigationPlugin`].
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::utils::{Duration, Instant};
+
+use crate::gesture::Direction;
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct UiNavigationSystem;
+
+/// Marker for the UI nodes that can be focused by the navigation.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Copy, Default)]
+pub struct Focusable;
+
+/// Marker inserted on the focused UI node.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Copy, Default)]
+pub struct Focused;
+
+/// Event sent by the UI navigation.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum UiNavigationEvent {
+    /// The focus moved to the given node.
+    Focused(Entity),
+    /// The confirm action was pressed while the given node was focused.
+    Confirmed(Entity),
+    /// The cancel action was pressed.
+    Cancelled,
+}
+
+/// The actions used to navigate the UI, and the key-repeat timing of the directions.
+#[derive(PartialEq, Clone, Debug)]
+pub struct UiNavigationActions<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub up: Keys,
+    pub down: Keys,
+    pub left: Keys,
+    pub right: Keys,
+    pub confirm: Keys,
+    pub cancel: Keys,
+    /// The time a direction needs to be held before it repeats.
+    pub repeat_delay: Duration,
+    /// The time between repeats while a direction is held.
+    pub repeat_interval: Duration,
+}
+
+impl<Keys> UiNavigationActions<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn new(up: Keys, down: Keys, left: Keys, right: Keys, confirm: Keys, cancel: Keys) -> Self {
+        Self {
+            up,
+            down,
+            left,
+            right,
+            confirm,
+            cancel,
+            repeat_delay: Duration::from_millis(400),
+            repeat_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+/// The focused node and the direction being held, used for the key-repeat.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct UiNavigationState {
+    pub focused: Option<Entity>,
+    /// The direction held, the instant it was pressed and the instant it last moved the focus.
+    pub held: Option<(Direction, Instant, Instant)>,
+}
+
+/// A [`Plugin`] moving the focus between the [`Focusable`] UI nodes when the navigation actions are pressed in any
+/// input view.
+pub struct UiNavigationPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub actions: UiNavigationActions<Keys>,
+    phantom_keys: PhantomData<Keys>,
+}
+
+impl<Keys> UiNavigationPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn new(actions: UiNavigationActions<Keys>) -> Self {
+        Self {
+            actions,
+            phantom_keys: PhantomData,
+        }
+    }
+}
+
+impl<Keys> Plugin for UiNavigationPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.actions.clone());
+        app.init_resource::<UiNavigationState>();
+        app.add_event::<UiNavigationEvent>();
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            ui_navigation_system::<Keys>
+                .label(UiNavigationSystem)
+                .after(EZInputSet::Resolve),
+        );
+    }
+}
+
+/// Returns the closest node in the given direction, favoring the nodes aligned with the current one.
+pub fn nearest_focusable(from: Vec2, direction: Direction, nodes: impl Iterator<Item = (Entity, Vec2)>) -> Option<Entity> {
+    let axis = match direction {
+        Direction::Up => Vec2::Y,
+        Direction::Down => -Vec2::Y,
+        Direction::Left => -Vec2::X,
+        Direction::Right => Vec2::X,
+    };
+    nodes
+        .filter_map(|(entity, position)| {
+            let offset = position - from;
+            let along = offset.dot(axis);
+            let across = (offset - axis * along).length();
+            (along > 0.).then_some((entity, along + across * 2.))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// System responsible for moving the focus and sending the [`UiNavigationEvent`]s.
+pub(crate) fn ui_navigation_system<Keys>(
+    mut commands: Commands,
+    global: Option<Res<InputView<Keys>>>,
+    views: Query<&InputView<Keys>, Without<GlobalInputView>>,
+    nodes: Query<(Entity, &GlobalTransform), With<Focusable>>,
+    actions: Res<UiNavigationActions<Keys>>,
+    mut state: ResMut<UiNavigationState>,
+    mut wr: EventWriter<UiNavigationEvent>,
+) where
+    Keys: BindingTypeView,
+{
+    let pressed = |kind: &Keys| {
+        global.as_ref().is_some_and(|view| view.key(kind).pressed())
+            || views.iter().any(|view| view.key(kind).pressed())
+    };
+    let just_pressed = |kind: &Keys| {
+        let just_pressed = |view: &InputView<Keys>| view.has_changed(kind) && view.key(kind).pressed();
+        global.as_ref().is_some_and(|view| just_pressed(view)) || views.iter().any(just_pressed)
+    };
+
+    let focused = state.focused.filter(|entity| nodes.get(*entity).is_ok());
+    if focused != state.focused {
+        state.focused = focused;
+    }
+
+    let held = [
+        (Direction::Up, &actions.up),
+        (Direction::Down, &actions.down),
+        (Direction::Left, &actions.left),
+        (Direction::Right, &actions.right),
+    ]
+    .into_iter()
+    .find(|(_, kind)| pressed(kind))
+    .map(|(direction, _)| direction);
+
+    let now = Instant::now();
+    let step = match (held, state.held) {
+        (None, None) => None,
+        (None, Some(_)) => {
+            state.held = None;
+            None
+        }
+        (Some(direction), Some((previous, pressed_at, moved_at))) if direction == previous => {
+            let repeat = now.duration_since(pressed_at) >= actions.repeat_delay
+                && now.duration_since(moved_at) >= actions.repeat_interval;
+            if repeat {
+                state.held = Some((direction, pressed_at, now));
+            }
+            repeat.then_some(direction)
+        }
+        (Some(direction), _) => {
+            state.held = Some((direction, now, now));
+            Some(direction)
+        }
+    };
+
+    if let Some(direction) = step {
+        let next = match focused.and_then(|entity| nodes.get(entity).ok()) {
+            Some((_, transform)) => nearest_focusable(
+                transform.translation.truncate(),
+                direction,
+                nodes
+                    .iter()
+                    .map(|(entity, transform)| (entity, transform.translation.truncate())),
+            ),
+            None => nodes.iter().next().map(|(entity, _)| entity),
+        };
+        if let Some(next) = next.filter(|next| Some(*next) != focused) {
+            if let Some(focused) = focused {
+                commands.entity(focused).remove::<Focused>();
+            }
+            commands.entity(next).insert(Focused);
+            state.focused = Some(next);
+            wr.send(UiNavigationEvent::Focused(next));
+        }
+    }
+
+    if let Some(focused) = state.focused {
+        if just_pressed(&actions.confirm) {
+            wr.send(UiNavigationEvent::Confirmed(focused));
+        }
+    }
+    if just_pressed(&actions.cancel) {
+        wr.send(UiNavigationEvent::Cancelled);
+    }
+}
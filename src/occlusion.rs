@@ -0,0 +1,134 @@
+//! Suppression of the "world" actions (e.g. clicking to move or dragging the camera) pressed while the pointer is
+//! over a Bevy UI node, so clicks on the HUD don't go through it. Enabled by adding [`UiOcclusionPlugin`].
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct UiOcclusionSystem;
+
+/// The world actions of a view and the ones currently occluded by the UI.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct OcclusionTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The actions suppressed when pressed over a UI node.
+    pub world: HashSet<Keys>,
+    /// The world actions whose current press started over a UI node.
+    pub occluded: HashSet<Keys>,
+}
+
+impl<Keys> Default for OcclusionTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            world: HashSet::default(),
+            occluded: HashSet::default(),
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Mark an action as a world action, suppressing it while its press started over a UI node.
+    pub fn set_world_action(&mut self, kind: Keys, world: bool) {
+        if world {
+            self.occlusion.world.insert(kind);
+        } else {
+            self.occlusion.world.remove(&kind);
+            self.occlusion.occluded.remove(&kind);
+        }
+    }
+
+    /// Returns whether the action is a world action pressed over a UI node.
+    pub fn is_occluded(&self, kind: &Keys) -> bool {
+        self.occlusion.occluded.contains(kind)
+    }
+}
+
+/// A [`Plugin`] suppressing the world actions of every input view pressed while the pointer is over a UI node with
+/// an [`Interaction`]. A press started in the world keeps working when dragged over the UI.
+pub struct UiOcclusionPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    phantom_keys: PhantomData<Keys>,
+}
+
+impl<Keys> Default for UiOcclusionPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            phantom_keys: PhantomData,
+        }
+    }
+}
+
+impl<Keys> Plugin for UiOcclusionPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            ui_occlusion_system::<Keys>
+                .label(UiOcclusionSystem)
+                .label(EZInputSet::Collect)
+                .after(UiSystem::Focus)
+                .after(StagedInputSystem)
+                .after(InjectedInputHandlingSystem)
+                .before(ActionGroupSystem)
+                .before(InputHandlingTickSystem),
+        );
+    }
+}
+
+/// System responsible for occluding the world actions pressed over the UI, and releasing the occlusion once the
+/// actions are released.
+pub(crate) fn ui_occlusion_system<Keys>(
+    interactions: Query<&Interaction>,
+    mut query: Query<&mut InputView<Keys>>,
+) where
+    Keys: BindingTypeView,
+{
+    let over_ui = interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    for mut view in query.iter_mut() {
+        let changed: Vec<(Keys, bool)> = view
+            .occlusion
+            .world
+            .iter()
+            .filter_map(|kind| {
+                let pressed = view.raw_key(kind).pressed();
+                let was_pressed = view.snapshots.get(kind).is_some_and(|snapshot| snapshot.pressed);
+                let occluded = view.is_occluded(kind);
+                if occluded && !pressed {
+                    Some((*kind, false))
+                } else if !occluded && pressed && !was_pressed && over_ui {
+                    Some((*kind, true))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for (kind, occluded) in changed {
+            if occluded {
+                view.occlusion.occluded.insert(kind);
+            } else {
+                view.occlusion.occluded.remove(&kind);
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Player slots for couch co-op lobbies. The [`PlayerSlots`] resource assigns a device to each player and sends the
+//! [`PlayerJoined`], [`PlayerLeft`] and [`PlayerDeviceSwapped`] events, so lobby screens can be driven directly by
+//! them. Add the [`PlayerSlotsPlugin`] to use it.
+use bevy::input::gamepad::GamepadEventType;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct PlayerSlotsSystem;
+
+/// The button layout of a device, used to pick the glyphs shown in the lobby.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum DeviceLayout {
+    KeyboardMouse,
+    Xbox,
+    PlayStation,
+    Nintendo,
+    Generic,
+}
+
+impl DeviceLayout {
+    /// Guess the layout of a gamepad from its name.
+    pub fn from_gamepad_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if ["xbox", "xinput", "x-box"].iter().any(|hint| name.contains(hint)) {
+            Self::Xbox
+        } else if ["playstation", "dualshock", "dualsense", "ps3", "ps4", "ps5"]
+            .iter()
+            .any(|hint| name.contains(hint))
+        {
+            Self::PlayStation
+        } else if ["nintendo", "switch", "joy-con", "pro controller"]
+            .iter()
+            .any(|hint| name.contains(hint))
+        {
+            Self::Nintendo
+        } else {
+            Self::Generic
+        }
+    }
+}
+
+/// A device assigned to a player slot.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct DeviceInfo {
+    /// The gamepad of the player, or none for the keyboard and mouse.
+    pub gamepad: Option<Gamepad>,
+    pub name: String,
+    pub layout: DeviceLayout,
+}
+
+impl DeviceInfo {
+    pub fn keyboard_mouse() -> Self {
+        Self {
+            gamepad: None,
+            name: "Keyboard and mouse".to_string(),
+            layout: DeviceLayout::KeyboardMouse,
+        }
+    }
+
+    /// Describe a gamepad. The name is usually the name of its [`GamepadMapping`].
+    pub fn gamepad(gamepad: Gamepad, name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            gamepad: Some(gamepad),
+            layout: DeviceLayout::from_gamepad_name(&name),
+            name,
+        }
+    }
+}
+
+/// Event sent when a player joins a slot.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct PlayerJoined {
+    pub slot: usize,
+    pub device: DeviceInfo,
+}
+
+/// Event sent when a player leaves a slot, or its gamepad disconnects.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct PlayerLeft {
+    pub slot: usize,
+    pub device: DeviceInfo,
+}
+
+/// Event sent when the device of a player changes.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct PlayerDeviceSwapped {
+    pub slot: usize,
+    pub previous: DeviceInfo,
+    pub device: DeviceInfo,
+}
+
+/// A change of the player slots, sent as an event by the [`PlayerSlotsPlugin`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum PlayerSlotChange {
+    Joined(PlayerJoined),
+    Left(PlayerLeft),
+    Swapped(PlayerDeviceSwapped),
+}
+
+/// The device of each player slot.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct PlayerSlots {
+    pub slots: Vec<Option<DeviceInfo>>,
+    /// The button joining the first free slot when pressed on a gamepad without a slot, if any.
+    pub join_button: Option<GamepadButtonType>,
+    /// The changes not sent as events yet.
+    pub pending: Vec<PlayerSlotChange>,
+}
+
+impl PlayerSlots {
+    pub fn new(max_players: usize) -> Self {
+        Self {
+            slots: vec![None; max_players],
+            ..Default::default()
+        }
+    }
+
+    /// Returns the device of a slot.
+    pub fn device(&self, slot: usize) -> Option<&DeviceInfo> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    /// Returns the slot of a gamepad.
+    pub fn slot_of(&self, gamepad: Gamepad) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|device| device.as_ref().and_then(|device| device.gamepad) == Some(gamepad))
+    }
+
+    /// Assign a device to the first free slot, returning the slot. Returns none if every slot is taken or the device
+    /// already has a slot.
+    pub fn join(&mut self, device: DeviceInfo) -> Option<usize> {
+        if self.slots.iter().flatten().any(|other| other.gamepad == device.gamepad) {
+            return None;
+        }
+        let slot = self.slots.iter().position(Option::is_none)?;
+        self.slots[slot] = Some(device.clone());
+        self.pending.push(PlayerSlotChange::Joined(PlayerJoined { slot, device }));
+        Some(slot)
+    }
+
+    /// Free a slot, returning its device.
+    pub fn leave(&mut self, slot: usize) -> Option<DeviceInfo> {
+        let device = self.slots.get_mut(slot)?.take()?;
+        self.pending.push(PlayerSlotChange::Left(PlayerLeft {
+            slot,
+            device: device.clone(),
+        }));
+        Some(device)
+    }
+
+    /// Replace the device of a taken slot. Returns false if the slot is free.
+    pub fn swap_device(&mut self, slot: usize, device: DeviceInfo) -> bool {
+        let previous = match self.slots.get_mut(slot) {
+            Some(current @ Some(_)) => current.replace(device.clone()),
+            _ => return false,
+        };
+        if let Some(previous) = previous {
+            self.pending.push(PlayerSlotChange::Swapped(PlayerDeviceSwapped {
+                slot,
+                previous,
+                device,
+            }));
+        }
+        true
+    }
+}
+
+/// A [`Plugin`] adding the [`PlayerSlots`] resource with the given number of slots, and sending its events.
+pub struct PlayerSlotsPlugin {
+    pub max_players: usize,
+    pub join_button: Option<GamepadButtonType>,
+}
+
+impl PlayerSlotsPlugin {
+    pub fn new(max_players: usize) -> Self {
+        Self {
+            max_players,
+            join_button: Some(GamepadButtonType::Start),
+        }
+    }
+}
+
+impl Plugin for PlayerSlotsPlugin {
+    fn build(&self, app: &mut App) {
+        let mut slots = PlayerSlots::new(self.max_players);
+        slots.join_button = self.join_button;
+        app.insert_resource(slots)
+            .add_event::<PlayerJoined>()
+            .add_event::<PlayerLeft>()
+            .add_event::<PlayerDeviceSwapped>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                player_slots_system
+                    .label(PlayerSlotsSystem)
+                    .after(InputSystem),
+            );
+    }
+}
+
+/// System responsible for joining the gamepads pressing the join button, freeing the slots of disconnected gamepads
+/// and sending the events of every slot change.
+pub(crate) fn player_slots_system(
+    mut slots: ResMut<PlayerSlots>,
+    mut rd: EventReader<GamepadEvent>,
+    markers: Query<&GamepadMarker>,
+    mut joined: EventWriter<PlayerJoined>,
+    mut left: EventWriter<PlayerLeft>,
+    mut swapped: EventWriter<PlayerDeviceSwapped>,
+) {
+    for GamepadEvent(gamepad, kind) in rd.iter() {
+        match kind {
+            GamepadEventType::Disconnected => {
+                if let Some(slot) = slots.slot_of(*gamepad) {
+                    slots.leave(slot);
+                }
+            }
+            GamepadEventType::ButtonChanged(button, value)
+                if *value > 0. && slots.join_button == Some(*button) && slots.slot_of(*gamepad).is_none() =>
+            {
+                let name = markers
+                    .iter()
+                    .find(|marker| marker.gamepad == *gamepad)
+                    .and_then(|marker| marker.mapping.as_ref())
+                    .map(|mapping| mapping.name.clone())
+                    .unwrap_or_else(|| format!("Gamepad {}", gamepad.0));
+                slots.join(DeviceInfo::gamepad(*gamepad, name));
+            }
+            _ => {}
+        }
+    }
+    if slots.pending.is_empty() {
+        return;
+    }
+    for change in std::mem::take(&mut slots.pending) {
+        match change {
+            PlayerSlotChange::Joined(ev) => joined.send(ev),
+            PlayerSlotChange::Left(ev) => left.send(ev),
+            PlayerSlotChange::Swapped(ev) => swapped.send(ev),
+        }
+    }
+}
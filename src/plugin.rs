@@ -4,19 +4,44 @@
 use std::marker::PhantomData;
 
 use crate::prelude::*;
+use bevy::ecs::event::Events;
 use bevy::input::InputSystem;
 use bevy::prelude::*;
-use bevy::utils::Instant;
 
 #[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
 pub struct InputHandlingTickSystem;
 
+/// Labels grouping every EZInput system in [`CoreStage::PreUpdate`], so user systems can be ordered against them.
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub enum EZInputSet {
+    /// Systems collecting the input of every input source into the input views.
+    Collect,
+    /// Systems resolving the actions once all input is collected. Order after this set to read the final
+    /// state of the frame.
+    Resolve,
+}
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct GlobalInputViewSystem;
+
+/// Label of every resolution system reading or writing the input views. In global mode, the view is only moved back
+/// into the resource once they all ran, so label custom resolution systems with it too.
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct InputViewResolutionSystem;
+
+/// Marker for the hidden entity holding the global [`InputView`] while the input systems run. Query it to
+/// change the input markers (e.g. the gamepad id or dead zone) used by the global view.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Copy, Default)]
+pub struct GlobalInputView;
+
 /// A [`Plugin`] that handles [`Input`] from different type of input sources.
 pub struct EZInputPlugin<Keys>
 where
     Keys: BindingTypeView,
 {
     phantom_keys: PhantomData<Keys>,
+    global: bool,
+    gamepad_sampling: GamepadSamplingMode,
 }
 
 impl<Keys> Default for EZInputPlugin<Keys>
@@ -26,10 +51,35 @@ where
     fn default() -> Self {
         Self {
             phantom_keys: PhantomData,
+            global: false,
+            gamepad_sampling: GamepadSamplingMode::EveryFrame,
         }
     }
 }
 
+impl<Keys> EZInputPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Creates a plugin storing the input view as a global `InputView<Keys>` resource instead of a component,
+    /// fed by the keyboard, the mouse and the first gamepad. Useful for single-player games.
+    ///
+    /// The resource is up to date from the end of [`CoreStage::PreUpdate`] to the end of the frame.
+    pub fn global() -> Self {
+        Self {
+            global: true,
+            ..Self::default()
+        }
+    }
+
+    /// Set how often the gamepad state is sampled, e.g. to reduce the input processing cost of low-power builds. The
+    /// first plugin added sets the sampling of every gamepad; change the [`GamepadSampling`] resource afterwards.
+    pub fn with_gamepad_sampling(mut self, mode: GamepadSamplingMode) -> Self {
+        self.gamepad_sampling = mode;
+        self
+    }
+}
+
 impl<Keys> Plugin for EZInputPlugin<Keys>
 where
     Keys: BindingTypeView,
@@ -42,12 +92,201 @@ where
         ) {
             app.add_system_to_stage(
                 CoreStage::PreUpdate,
-                func.before(InputHandlingTickSystem).after(InputSystem),
+                func.label(EZInputSet::Collect)
+                    .before(InputHandlingTickSystem)
+                    .after(InputSystem),
+            );
+        }
+        #[inline]
+        fn add_resolution_system<Params>(
+            app: &mut App,
+            func: impl ParallelSystemDescriptorCoercion<Params>,
+        ) {
+            app.add_system_to_stage(
+                CoreStage::PreUpdate,
+                func.label(EZInputSet::Resolve)
+                    .label(InputViewResolutionSystem)
+                    .after(InputHandlingTickSystem),
+            );
+        }
+        app.add_event::<GroupSwitched<Keys>>();
+        app.add_event::<FullyCharged<Keys>>();
+        app.add_event::<LongPress<Keys>>();
+        app.add_event::<ChargedRelease<Keys>>();
+        app.add_event::<SwitchScanSelected<Keys>>();
+        app.add_event::<BindingChanged<Keys>>();
+        app.add_event::<ActionFeedbackEvent<Keys>>();
+        if !app.world.contains_resource::<Events<InputIdle>>() {
+            app.add_event::<InputIdle>();
+        }
+        if !app.world.contains_resource::<Events<GamepadMotionEvent>>() {
+            app.add_event::<GamepadMotionEvent>();
+        }
+        if !app.world.contains_resource::<Events<InjectedInput>>() {
+            app.add_event::<InjectedInput>();
+        }
+        if !app.world.contains_resource::<Events<ImeEvent>>() {
+            app.add_event::<ImeEvent>();
+        }
+        if !app.world.contains_resource::<Events<TextPaste>>() {
+            app.add_event::<TextPaste>();
+        }
+        if !app.world.contains_resource::<Events<TouchGestureEvent>>() {
+            app.add_event::<TouchGestureEvent>();
+        }
+        if !app
+            .world
+            .contains_resource::<Events<GamepadRumbleRequest>>()
+        {
+            app.add_event::<GamepadRumbleRequest>();
+        }
+        if !app.world.contains_resource::<Events<GamepadLedRequest>>() {
+            app.add_event::<GamepadLedRequest>();
+        }
+        if !app
+            .world
+            .contains_resource::<Events<GamepadTriggerEffectRequest>>()
+        {
+            app.add_event::<GamepadTriggerEffectRequest>();
+        }
+        if !app
+            .world
+            .contains_resource::<Events<GamepadConnectionEvent>>()
+        {
+            app.add_event::<GamepadConnectionEvent>();
+        }
+        if !app.world.contains_resource::<Events<GamepadCalibrated>>() {
+            app.add_event::<GamepadCalibrated>();
+        }
+        if !app
+            .world
+            .contains_resource::<Events<GamepadBatteryChanged>>()
+        {
+            app.add_event::<GamepadBatteryChanged>();
+        }
+        if !app.world.contains_resource::<Events<RawInputEvent>>() {
+            app.add_event::<RawInputEvent>();
+        }
+        if !app.world.contains_resource::<Events<DeviceKeyboardInput>>() {
+            app.add_event::<DeviceKeyboardInput>();
+        }
+        if !app
+            .world
+            .contains_resource::<Events<DeviceMouseButtonInput>>()
+        {
+            app.add_event::<DeviceMouseButtonInput>();
+        }
+        if !app.world.contains_resource::<Events<DeviceMouseMotion>>() {
+            app.add_event::<DeviceMouseMotion>();
+        }
+        if !app.world.contains_resource::<Events<DeviceMouseWheel>>() {
+            app.add_event::<DeviceMouseWheel>();
+        }
+        if !app.world.contains_resource::<Events<AnalogKeyInput>>() {
+            app.add_event::<AnalogKeyInput>();
+        }
+        app.init_resource::<PointerWorldPosition>();
+        app.init_resource::<BrowserInputCompat>();
+        app.init_resource::<GamepadBatteryStatus>();
+        app.init_resource::<AdaptiveTriggerSupport>();
+        if !app.world.contains_resource::<Events<MacroFinished>>() {
+            app.add_event::<MacroFinished>();
+            add_handling_system(
+                app,
+                macro_player_system
+                    .label(MacroPlayerSystem)
+                    .before(InjectedInputHandlingSystem),
+            );
+        }
+        if !app.world.contains_resource::<MouseWindows>() {
+            app.init_resource::<MouseWindows>();
+            app.add_system_to_stage(CoreStage::First, mouse_window_system.label(MouseWindowSystem));
+        }
+        if !app.world.contains_resource::<GamepadSampling>() {
+            app.insert_resource(GamepadSampling::new(self.gamepad_sampling));
+            app.add_system_to_stage(CoreStage::First, gamepad_sampling_system.label(GamepadSamplingSystem));
+        }
+        if !app.world.contains_resource::<KeyboardLayout>() {
+            app.init_resource::<KeyboardLayout>();
+            app.add_system_to_stage(CoreStage::First, keyboard_layout_system.label(KeyboardLayoutSystem));
+        }
+        if !app.world.contains_resource::<InputFrame>() {
+            app.init_resource::<InputFrame>();
+            app.add_system_to_stage(CoreStage::First, input_frame_system);
+        }
+        app.add_system_to_stage(
+            CoreStage::First,
+            insert_staged_input_system::<Keys, KeyboardMarker>,
+        );
+        app.add_system_to_stage(
+            CoreStage::First,
+            insert_staged_input_system::<Keys, MouseMarker>,
+        );
+        app.add_system_to_stage(
+            CoreStage::First,
+            insert_staged_input_system::<Keys, GamepadMarker>,
+        );
+        app.add_system_to_stage(
+            CoreStage::First,
+            insert_staged_input_system::<Keys, TouchMarker>,
+        );
+        if self.global {
+            if !app.world.contains_resource::<InputView<Keys>>() {
+                app.insert_resource(InputView::<Keys>::new());
+            }
+            app.world
+                .spawn()
+                .insert_bundle(InputHandlingBundle::new(InputView::<Keys>::new()))
+                .insert(GlobalInputView);
+            app.add_system_to_stage(
+                CoreStage::First,
+                swap_global_view_system::<Keys>.label(GlobalInputViewSystem),
+            );
+            app.add_system_to_stage(
+                CoreStage::PreUpdate,
+                swap_global_view_system::<Keys>
+                    .label(GlobalInputViewSystem)
+                    .label(EZInputSet::Resolve)
+                    .after(InputHandlingTickSystem)
+                    .after(InputViewResolutionSystem),
             );
         }
         app.add_system_to_stage(
             CoreStage::PreUpdate,
-            tick_system::<Keys>.label(InputHandlingTickSystem),
+            tick_system::<Keys>
+                .label(InputHandlingTickSystem)
+                .label(EZInputSet::Resolve),
+        );
+        add_resolution_system(
+            app,
+            idle_detection_system::<Keys>.label(IdleDetectionSystem),
+        );
+        add_resolution_system(app, hold_system::<Keys>.label(HoldSystem));
+        add_resolution_system(app, press_token_system::<Keys>.label(PressTokenSystem));
+        add_resolution_system(app, input_stats_system::<Keys>.label(InputStatsSystem));
+        add_resolution_system(
+            app,
+            browser_pointer_lock_system::<Keys>.label(BrowserPointerLockSystem),
+        );
+        add_resolution_system(
+            app,
+            cursor_mode_system::<Keys>
+                .label(CursorModeSystem)
+                .after(BrowserPointerLockSystem),
+        );
+        add_resolution_system(app, flick_system::<Keys>.label(FlickSystem));
+        add_resolution_system(app, stepped_axis_system::<Keys>.label(SteppedAxisSystem));
+        add_resolution_system(app, axis_ramp_system::<Keys>.label(AxisRampSystem));
+        add_resolution_system(app, action_processor_system::<Keys>.label(ActionProcessorSystem));
+        add_resolution_system(app, direction_system::<Keys>.label(DirectionSystem));
+        add_resolution_system(
+            app,
+            binding_change_system::<Keys>.label(BindingChangeSystem),
+        );
+        add_resolution_system(app, action_feedback_system::<Keys>.label(ActionFeedbackSystem));
+        add_resolution_system(
+            app,
+            pointer_world_position_system::<Keys>.label(PointerWorldPositionSystem),
         );
         add_handling_system(
             app,
@@ -61,26 +300,173 @@ where
             app,
             gamepad_input_system::<Keys>.label(GamepadInputHandlingSystem),
         );
+        add_handling_system(
+            app,
+            gamepad_motion_system::<Keys>.label(GamepadMotionHandlingSystem),
+        );
+        add_handling_system(
+            app,
+            gamepad_sync_system::<Keys>
+                .label(GamepadSyncSystem)
+                .after(GamepadInputHandlingSystem),
+        );
+        add_handling_system(app, touch_gesture_system::<Keys>.label(TouchGestureSystem));
+        add_handling_system(
+            app,
+            gamepad_battery_system::<Keys>
+                .label(GamepadBatterySystem)
+                .after(GamepadSyncSystem),
+        );
+        add_handling_system(
+            app,
+            staged_input_system::<Keys>
+                .label(StagedInputSystem)
+                .after(KeyboardInputHandlingSystem)
+                .after(MouseInputHandlingSystem)
+                .after(GamepadSyncSystem)
+                .after(TouchGestureSystem),
+        );
+        add_handling_system(
+            app,
+            trigger_rumble_system::<Keys>
+                .label(TriggerRumbleSystem)
+                .after(StagedInputSystem),
+        );
+        add_handling_system(
+            app,
+            rumble_pattern_system::<Keys>
+                .label(RumblePatternSystem)
+                .after(TriggerRumbleSystem),
+        );
+        add_handling_system(
+            app,
+            gamepad_led_system::<Keys>
+                .label(GamepadLedSystem)
+                .after(GamepadSyncSystem),
+        );
+        add_handling_system(
+            app,
+            adaptive_trigger_system::<Keys>
+                .label(AdaptiveTriggerSystem)
+                .after(GamepadSyncSystem),
+        );
+        add_handling_system(
+            app,
+            injected_input_system::<Keys>.label(InjectedInputHandlingSystem),
+        );
+        add_handling_system(
+            app,
+            focus_loss_release_system::<Keys>
+                .label(FocusLossReleaseSystem)
+                .after(StagedInputSystem)
+                .after(InjectedInputHandlingSystem),
+        );
+        add_handling_system(
+            app,
+            text_capture_system::<Keys>
+                .label(TextCaptureSystem)
+                .after(StagedInputSystem)
+                .after(InjectedInputHandlingSystem),
+        );
+        add_handling_system(
+            app,
+            accessibility_system::<Keys>
+                .label(AccessibilitySystem)
+                .after(StagedInputSystem)
+                .after(InjectedInputHandlingSystem),
+        );
+        add_handling_system(
+            app,
+            action_group_system::<Keys>
+                .label(ActionGroupSystem)
+                .after(StagedInputSystem)
+                .after(GamepadMotionHandlingSystem)
+                .after(InjectedInputHandlingSystem)
+                .after(AccessibilitySystem),
+        );
     }
 }
 
 /// Tick every input system to update the press state for the current time, letting the input view know the press
-/// state for the action.
+/// state for the action. The view is only mutated when something actually changed, so `Changed<InputView<Keys>>`
+/// filters keep working.
 #[doc(hidden)]
-fn tick_system<Keys>(mut query: Query<&mut InputView<Keys>>)
+fn tick_system<Keys>(mut query: Query<&mut InputView<Keys>>, frame: Res<InputFrame>, time: Res<Time>)
 where
     Keys: BindingTypeView,
 {
+    const JUST_PRESSED: PressState = PressState::Pressed {
+        started_pressing_instant: None,
+    };
     for mut view in query.iter_mut() {
-        for ReceiverDescriptor { axis, .. } in view.descriptors.iter_mut() {
-            if let PressState::Pressed {
-                ref mut started_pressing_instant,
-            } = axis.press
-            {
-                if started_pressing_instant.is_none() {
-                    *started_pressing_instant = Some(Instant::now());
+        if !view.processors.receivers.is_empty() {
+            view.process_receivers(time.delta());
+        }
+
+        if view
+            .descriptors
+            .iter()
+            .any(|descriptor| descriptor.axis.press == JUST_PRESSED)
+        {
+            for ReceiverDescriptor { axis, tracker, .. } in view.descriptors.iter_mut() {
+                if let PressState::Pressed {
+                    ref mut started_pressing_instant,
+                } = axis.press
+                {
+                    if started_pressing_instant.is_none() {
+                        *started_pressing_instant = Some(tracker.record(frame.0));
+                    }
                 }
             }
         }
+
+        if view
+            .suppressed
+            .iter()
+            .any(|rcv| view.state(rcv).press.released())
+        {
+            let view = view.as_mut();
+            let descriptors = &view.descriptors;
+            view.suppressed.retain(|rcv| {
+                descriptors
+                    .iter()
+                    .any(|descriptor| descriptor.input == *rcv && descriptor.axis.press.pressed())
+            });
+        }
+
+        if view.descriptors.iter().any(|descriptor| descriptor.needs_delta_update()) {
+            for descriptor in view.descriptors.iter_mut() {
+                descriptor.update_delta();
+            }
+        }
+
+        if view.delay.frames > 0 {
+            view.advance_input_delay(frame.0);
+        }
+
+        let snapshots = view.changed_snapshots();
+        if !snapshots.is_empty() || !view.changed.is_empty() {
+            view.apply_snapshots(snapshots);
+        }
+    }
+}
+
+/// Swap the global input view resource with the view of the hidden [`GlobalInputView`] entity. Runs once before the
+/// input systems, moving the view into the entity, and once after them, moving it back into the resource.
+#[doc(hidden)]
+fn swap_global_view_system<Keys>(
+    mut global: ResMut<InputView<Keys>>,
+    mut query: Query<&mut InputView<Keys>, With<GlobalInputView>>,
+) where
+    Keys: BindingTypeView,
+{
+    if let Ok(mut view) = query.get_single_mut() {
+        std::mem::swap(global.as_mut(), view.as_mut());
     }
 }
+
+/// Increment the [`InputFrame`] counter at the start of every frame.
+#[doc(hidden)]
+fn input_frame_system(mut frame: ResMut<InputFrame>) {
+    frame.0 += 1;
+}
@@ -0,0 +1,94 @@
+//! A unified pointer abstraction spanning mouse and gamepad, providing the pointer location in world space.
+use bevy::prelude::*;
+use bevy::render::camera::{Camera, RenderTarget};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct PointerWorldPositionSystem;
+
+/// Marker for the camera used to convert the pointer location to world space.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Copy, Default)]
+pub struct PointerCamera;
+
+/// Marker selecting which input view drives the [`PointerWorldPosition`] resource. When the last input source of the
+/// view is a gamepad, the right stick moves a virtual cursor instead.
+#[derive(PartialEq, Debug, Component, Clone, Copy)]
+pub struct PointerMarker {
+    /// How many pixels per second the virtual cursor moves when the right stick is fully tilted.
+    pub gamepad_cursor_speed: f32,
+    /// The current screen location of the pointer.
+    pub cursor: Option<Vec2>,
+}
+
+impl Default for PointerMarker {
+    fn default() -> Self {
+        Self {
+            gamepad_cursor_speed: 800.,
+            cursor: None,
+        }
+    }
+}
+
+/// The location of the pointer on screen and in world space.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct PointerWorldPosition {
+    pub screen: Option<Vec2>,
+    pub world: Option<Vec2>,
+}
+
+/// System responsible for updating the [`PointerWorldPosition`] resource from the selected input view.
+pub(crate) fn pointer_world_position_system<Keys>(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    cameras: Query<(&Camera, &GlobalTransform), With<PointerCamera>>,
+    mut query: Query<(&InputView<Keys>, Option<&MouseMarker>, &mut PointerMarker)>,
+    mut pointer: ResMut<PointerWorldPosition>,
+) where
+    Keys: BindingTypeView,
+{
+    let (camera, transform) = match cameras.get_single() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+    let window = match camera.target {
+        RenderTarget::Window(id) => match windows.get(id) {
+            Some(window) => window,
+            None => return,
+        },
+        _ => return,
+    };
+    let size = Vec2::new(window.width(), window.height());
+
+    for (view, mouse, mut marker) in query.iter_mut() {
+        let cursor = if view.last_input_source == Some(InputSource::Gamepad) {
+            let stick = Vec2::new(
+                view.state(&InputReceiver::GamepadAxis(GamepadAxisType::RightStickX))
+                    .value,
+                view.state(&InputReceiver::GamepadAxis(GamepadAxisType::RightStickY))
+                    .value,
+            );
+            let cursor = marker.cursor.unwrap_or(size / 2.)
+                + stick * marker.gamepad_cursor_speed * time.delta_seconds();
+            Some(cursor.clamp(Vec2::ZERO, size))
+        } else {
+            mouse.and_then(|mouse| mouse.mouse_position).or(marker.cursor)
+        };
+        if marker.cursor != cursor {
+            marker.cursor = cursor;
+        }
+
+        let world = cursor.map(|cursor| {
+            let ndc = cursor / size * 2. - Vec2::ONE;
+            let ndc_to_world = transform.compute_matrix() * camera.projection_matrix.inverse();
+            ndc_to_world.project_point3(ndc.extend(-1.)).truncate()
+        });
+        let position = PointerWorldPosition {
+            screen: cursor,
+            world,
+        };
+        if *pointer != position {
+            *pointer = position;
+        }
+    }
+}
@@ -0,0 +1,425 @@
+//! Composable input processors. An [`InputProcessor`] transforms an axis value, and processors are chained into a
+//! [`ProcessorPipeline`] applied either to the values written to a receiver, or to the value of an action (see
+//! [`InputView::processed_axis`]). Both are applied once per frame, so stateful processors keep moving between input
+//! events. Dead zones, inversion, response curves, smoothing (including the adaptive
+//! [`OneEuroFilter`]) and clamping are built in, and custom processors only need to implement the trait.
+use std::fmt::Debug;
+
+use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct ActionProcessorSystem;
+
+/// A transformation of an axis value. Processors can keep state between values, e.g. for smoothing.
+pub trait InputProcessor: Debug + Send + Sync + 'static {
+    /// Returns the processed value, given the time elapsed since the last processed value.
+    fn process(&mut self, value: f32, elapsed: Duration) -> f32;
+
+    fn clone_box(&self) -> Box<dyn InputProcessor>;
+}
+
+/// Ignores the values under the inner dead zone and saturates them at the outer one, rescaling the range between
+/// them from 0 to 1.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct DeadZoneProcessor {
+    pub inner: f32,
+    pub outer: f32,
+}
+
+impl InputProcessor for DeadZoneProcessor {
+    fn process(&mut self, value: f32, _: Duration) -> f32 {
+        let magnitude = value.abs();
+        if magnitude <= self.inner {
+            0.
+        } else if magnitude >= self.outer {
+            value.signum()
+        } else {
+            value.signum() * (magnitude - self.inner) / (self.outer - self.inner)
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn InputProcessor> {
+        Box::new(*self)
+    }
+}
+
+/// Negates the values.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct InvertProcessor;
+
+impl InputProcessor for InvertProcessor {
+    fn process(&mut self, value: f32, _: Duration) -> f32 {
+        -value
+    }
+
+    fn clone_box(&self) -> Box<dyn InputProcessor> {
+        Box::new(*self)
+    }
+}
+
+/// Raises the magnitude of the values to a power: above 1 gives precision near the center, under 1 near the edge.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct CurveProcessor {
+    pub exponent: f32,
+}
+
+impl InputProcessor for CurveProcessor {
+    fn process(&mut self, value: f32, _: Duration) -> f32 {
+        value.abs().powf(self.exponent).copysign(value)
+    }
+
+    fn clone_box(&self) -> Box<dyn InputProcessor> {
+        Box::new(*self)
+    }
+}
+
+/// Smooths the values with a frame-rate independent exponential filter.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct SmoothingProcessor {
+    /// The time the smoothed value takes to cover about two thirds of a change.
+    pub time_constant: Duration,
+    pub value: f32,
+}
+
+impl SmoothingProcessor {
+    pub fn new(time_constant: Duration) -> Self {
+        Self {
+            time_constant,
+            value: 0.,
+        }
+    }
+}
+
+impl InputProcessor for SmoothingProcessor {
+    fn process(&mut self, value: f32, elapsed: Duration) -> f32 {
+        let alpha = if self.time_constant.is_zero() {
+            1.
+        } else {
+            1. - (-elapsed.as_secs_f32() / self.time_constant.as_secs_f32()).exp()
+        };
+        self.value += (value - self.value) * alpha;
+        self.value
+    }
+
+    fn clone_box(&self) -> Box<dyn InputProcessor> {
+        Box::new(*self)
+    }
+}
+
+/// The One-Euro filter, an adaptive low-pass filter for noisy continuous input such as gyro aiming or touch
+/// positions. Slow movements are smoothed heavily to remove jitter, while fast movements are barely filtered, so it
+/// adds much less latency than a plain smoothing filter.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct OneEuroFilter {
+    /// The cutoff frequency at rest, in hertz. Lower values remove more jitter.
+    pub min_cutoff: f32,
+    /// How much the cutoff frequency grows with the speed of the value. Higher values reduce the lag.
+    pub beta: f32,
+    /// The cutoff frequency of the speed estimation, in hertz.
+    pub derivative_cutoff: f32,
+    /// The last filtered value, if any.
+    pub value: Option<f32>,
+    /// The last filtered speed of the value.
+    pub derivative: f32,
+}
+
+impl Default for OneEuroFilter {
+    fn default() -> Self {
+        Self::new(1., 0.007)
+    }
+}
+
+impl OneEuroFilter {
+    pub fn new(min_cutoff: f32, beta: f32) -> Self {
+        Self {
+            min_cutoff,
+            beta,
+            derivative_cutoff: 1.,
+            value: None,
+            derivative: 0.,
+        }
+    }
+
+    /// Returns the smoothing factor of a low-pass filter with the given cutoff frequency.
+    fn alpha(cutoff: f32, elapsed: f32) -> f32 {
+        let tau = 1. / (2. * std::f32::consts::PI * cutoff);
+        1. / (1. + tau / elapsed)
+    }
+}
+
+impl InputProcessor for OneEuroFilter {
+    fn process(&mut self, value: f32, elapsed: Duration) -> f32 {
+        let elapsed = elapsed.as_secs_f32();
+        let previous = match self.value {
+            Some(previous) if elapsed > 0. => previous,
+            Some(previous) => return previous,
+            None => {
+                self.value = Some(value);
+                return value;
+            }
+        };
+        let derivative = (value - previous) / elapsed;
+        self.derivative += (derivative - self.derivative) * Self::alpha(self.derivative_cutoff, elapsed);
+        let cutoff = self.min_cutoff + self.beta * self.derivative.abs();
+        let filtered = previous + (value - previous) * Self::alpha(cutoff, elapsed);
+        self.value = Some(filtered);
+        filtered
+    }
+
+    fn clone_box(&self) -> Box<dyn InputProcessor> {
+        Box::new(*self)
+    }
+}
+
+/// Clamps the values to a range.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct ClampProcessor {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl InputProcessor for ClampProcessor {
+    fn process(&mut self, value: f32, _: Duration) -> f32 {
+        value.clamp(self.min, self.max)
+    }
+
+    fn clone_box(&self) -> Box<dyn InputProcessor> {
+        Box::new(*self)
+    }
+}
+
+impl InputProcessor for AxisScale {
+    fn process(&mut self, value: f32, _: Duration) -> f32 {
+        self.apply(value)
+    }
+
+    fn clone_box(&self) -> Box<dyn InputProcessor> {
+        Box::new(*self)
+    }
+}
+
+/// A chain of processors, applied in order.
+#[derive(Debug, Default)]
+pub struct ProcessorPipeline {
+    pub processors: Vec<Box<dyn InputProcessor>>,
+}
+
+impl Clone for ProcessorPipeline {
+    fn clone(&self) -> Self {
+        Self {
+            processors: self.processors.iter().map(|processor| processor.clone_box()).collect(),
+        }
+    }
+}
+
+impl ProcessorPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a processor to the pipeline.
+    pub fn with(mut self, processor: impl InputProcessor) -> Self {
+        self.processors.push(Box::new(processor));
+        self
+    }
+
+    /// Returns the value processed by every processor in order.
+    pub fn process(&mut self, value: f32, elapsed: Duration) -> f32 {
+        self.processors
+            .iter_mut()
+            .fold(value, |value, processor| processor.process(value, elapsed))
+    }
+}
+
+/// The processor pipelines of the receivers and actions of an input view.
+#[derive(Clone, Debug)]
+pub struct ProcessorTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The pipelines applied to the values written to each receiver.
+    pub receivers: HashMap<InputReceiver, ProcessorPipeline>,
+    /// The state last written to each receiver with a pipeline, before inversion and processing.
+    pub inputs: HashMap<InputReceiver, AxisState>,
+    /// The pipelines applied to the value of each action.
+    pub actions: HashMap<Keys, ProcessorPipeline>,
+    /// The processed value of each action with a pipeline.
+    pub values: HashMap<Keys, f32>,
+}
+
+impl<Keys> Default for ProcessorTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            receivers: HashMap::default(),
+            inputs: HashMap::default(),
+            actions: HashMap::default(),
+            values: HashMap::default(),
+        }
+    }
+}
+
+/// Processors can't be compared, so trackers are compared by the receivers and actions with a pipeline, and by the
+/// values going in and out of the pipelines.
+impl<Keys> PartialEq for ProcessorTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.receivers.len() == other.receivers.len()
+            && self.receivers.keys().all(|rcv| other.receivers.contains_key(rcv))
+            && self.actions.len() == other.actions.len()
+            && self.actions.keys().all(|kind| other.actions.contains_key(kind))
+            && self.inputs == other.inputs
+            && self.values == other.values
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Set the pipeline applied to the values written to a receiver, or remove it if none.
+    pub fn set_receiver_processors(&mut self, input: InputReceiver, pipeline: Option<ProcessorPipeline>) {
+        match pipeline {
+            Some(pipeline) => {
+                if !self.processors.inputs.contains_key(&input) {
+                    let state = self.raw_state(&input);
+                    self.processors.inputs.insert(input, state);
+                }
+                self.processors.receivers.insert(input, pipeline);
+            }
+            None => {
+                self.processors.receivers.remove(&input);
+                if let Some(state) = self.processors.inputs.remove(&input) {
+                    self.set_axis_value(input, state.value, state.press);
+                }
+            }
+        }
+    }
+
+    /// Set the pipeline applied to the value of an action, or remove it if none.
+    pub fn set_action_processors(&mut self, kind: Keys, pipeline: Option<ProcessorPipeline>) {
+        match pipeline {
+            Some(pipeline) => self.processors.actions.insert(kind, pipeline),
+            None => self.processors.actions.remove(&kind),
+        };
+        self.processors.values.remove(&kind);
+    }
+
+    /// Returns the value of an action processed by its pipeline, or its axis value if it has no pipeline.
+    pub fn processed_axis(&self, kind: &Keys) -> f32 {
+        match self.processors.values.get(kind) {
+            Some(value) => *value,
+            None if self.processors.actions.contains_key(kind) => 0.,
+            None => self.snapshot(kind).value,
+        }
+    }
+
+    /// Store the state written to a receiver with a pipeline, to be processed by the next tick. Returns false if the
+    /// receiver has no pipeline.
+    pub(crate) fn stage_processed_receiver(&mut self, input: InputReceiver, value: f32, press: PressState) -> bool {
+        if !self.processors.receivers.contains_key(&input) {
+            return false;
+        }
+        self.processors.inputs.insert(input, AxisState::new(value, press));
+        true
+    }
+
+    /// Run the last state written to every receiver with a pipeline through it. Receivers whose processed value is
+    /// zero are released, so e.g. a dead zone also stops the receiver from pressing its actions. Done by the tick
+    /// system every frame.
+    pub(crate) fn process_receivers(&mut self, elapsed: Duration) {
+        let mut pipelines = std::mem::take(&mut self.processors.receivers);
+        for (input, pipeline) in pipelines.iter_mut() {
+            let raw = self.processors.inputs.get(input).copied().unwrap_or(AxisState::ZERO);
+            let value = if self.inverted.contains(input) {
+                -raw.value
+            } else {
+                raw.value
+            };
+            let value = pipeline.process(value, elapsed);
+            let current = *self.state(input);
+            let press = match (raw.press.pressed() && value != 0., current.press.pressed()) {
+                (true, true) => current.press,
+                (true, false) => PressState::Pressed {
+                    started_pressing_instant: None,
+                },
+                (false, _) => PressState::Released,
+            };
+            if current.value == value && current.press.pressed() == press.pressed() {
+                continue;
+            }
+            let descriptor = self.descriptor_or_insert(*input);
+            descriptor.set(value, press);
+            descriptor.raw_value = raw.value;
+            self.sync_pressed_receiver(*input);
+        }
+        self.processors.receivers = pipelines;
+    }
+}
+
+/// System responsible for processing the value of every action with a pipeline.
+pub(crate) fn action_processor_system<Keys>(
+    mut query: Query<&mut InputView<Keys>>,
+    time: Res<Time>,
+) where
+    Keys: BindingTypeView,
+{
+    let elapsed = time.delta();
+    for mut view in query.iter_mut() {
+        if view.processors.actions.is_empty() {
+            continue;
+        }
+        let mut actions = view.processors.actions.clone();
+        let mut values = HashMap::default();
+        for (action, pipeline) in actions.iter_mut() {
+            values.insert(*action, pipeline.process(view.snapshot(action).value, elapsed));
+        }
+        // The processors only move while their output does, so the view is left untouched once every value settled.
+        if values != view.processors.values {
+            view.processors.actions = actions;
+            view.processors.values = values;
+        }
+    }
+}
+
+// Test to check that the processors of a pipeline are applied in order.
+#[test]
+fn processor_pipeline_test() {
+    let mut pipeline = ProcessorPipeline::new()
+        .with(DeadZoneProcessor { inner: 0.2, outer: 1. })
+        .with(CurveProcessor { exponent: 2. })
+        .with(InvertProcessor);
+    assert_eq!(pipeline.process(0.1, Duration::ZERO), 0.);
+    assert!((pipeline.process(0.6, Duration::ZERO) + 0.25).abs() < 1e-5);
+    assert_eq!(pipeline.clone().process(0.6, Duration::ZERO), pipeline.process(0.6, Duration::ZERO));
+}
+
+// Test to check that a receiver zeroed by its dead zone is released instead of pressed with no value.
+#[test]
+fn receiver_dead_zone_press_test() {
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Aim;
+    impl BindingTypeView for Aim {}
+
+    let stick = InputReceiver::GamepadAxis(GamepadAxisType::RightStickX);
+    let pressed = PressState::Pressed {
+        started_pressing_instant: None,
+    };
+    let mut view = InputView::<Aim>::new();
+    let dead_zone = ProcessorPipeline::new().with(DeadZoneProcessor { inner: 0.2, outer: 1. });
+    view.set_receiver_processors(stick, Some(dead_zone));
+    view.set_axis_value(stick, 0.1, pressed);
+    view.process_receivers(Duration::ZERO);
+    assert!(view.state(&stick).press.released());
+    assert_eq!(view.raw_state(&stick), AxisState::new(0.1, pressed));
+    view.set_axis_value(stick, 1., pressed);
+    view.process_receivers(Duration::ZERO);
+    assert_eq!(*view.state(&stick), AxisState::new(1., pressed));
+}
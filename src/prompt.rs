@@ -0,0 +1,59 @@
+//! Button prompts for HUDs ("Press [E] to interact"), choosing the binding of an action matching the input source
+//! the player is using, among the bindings of the active context.
+use crate::prelude::*;
+
+/// The receiver to display in a prompt for an action.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Prompt {
+    /// The main receiver of the binding, e.g. `E` for Shift + E.
+    pub receiver: InputReceiver,
+    /// The other receivers of a chord, held with the main receiver.
+    pub modifiers: Vec<InputReceiver>,
+    pub source: InputSource,
+    /// A stable key identifying the glyph of the main receiver, e.g. `KeyboardKey/E` or `GamepadButton/South`.
+    pub glyph_key: String,
+}
+
+impl InputReceiver {
+    /// Returns a stable key identifying the glyph of this receiver, usable as a file name in a glyph atlas.
+    pub fn glyph_key(&self) -> String {
+        format!("{:?}", self).replace('(', "/").replace(')', "")
+    }
+}
+
+/// Returns whether the receivers of the given source are prompted to a player using the other source. The keyboard
+/// and the mouse are used together.
+fn same_device(source: InputSource, other: InputSource) -> bool {
+    let desktop = |source| matches!(source, InputSource::Keyboard | InputSource::Mouse | InputSource::KeyboardMouse);
+    source == other || (desktop(source) && desktop(other))
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the prompt of an action in the active context, preferring the bindings of the last used input source.
+    /// Returns none if the action isn't bound.
+    pub fn prompt_for(&self, kind: &Keys) -> Option<Prompt> {
+        let binding = self.bindings.get(kind)?;
+        let allowed: Vec<&InputReceivers> = binding
+            .input_receivers
+            .iter()
+            .filter(|receivers| !receivers.0.is_empty() && self.accepts_sources(kind, receivers))
+            .collect();
+        let receivers = match self.input_source() {
+            Some(last) => allowed
+                .iter()
+                .find(|receivers| receivers.sources().into_iter().all(|source| same_device(source, last)))
+                .or_else(|| allowed.first()),
+            None => allowed.first(),
+        }?;
+        let (receiver, modifiers) = receivers.0.split_last()?;
+        Some(Prompt {
+            receiver: *receiver,
+            modifiers: modifiers.to_vec(),
+            source: receiver.source(),
+            glyph_key: receiver.glyph_key(),
+        })
+    }
+}
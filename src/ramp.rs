@@ -0,0 +1,158 @@
+//! Analog emulation for digital movement. Holding a key ramps the emulated axis up over an attack time and releasing
+//! it decays the axis, so keyboard movement walks before running and feeds the same analog movement code as a stick.
+//! Analog receivers bypass the ramp.
+use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap, HashSet};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct AxisRampSystem;
+
+/// The times a ramped axis takes to go from rest to full and back.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct AxisRamp {
+    /// The time the axis takes to ramp from 0 to 1 while held.
+    pub attack: Duration,
+    /// The time the axis takes to decay from 1 to 0 once released.
+    pub release: Duration,
+}
+
+impl Default for AxisRamp {
+    fn default() -> Self {
+        Self {
+            attack: Duration::from_millis(300),
+            release: Duration::from_millis(100),
+        }
+    }
+}
+
+impl AxisRamp {
+    /// Move a value towards the target over a frame of the given duration.
+    pub fn step(&self, value: f32, target: f32, frame: Duration) -> f32 {
+        let rising = (target.abs() > value.abs() && target.signum() == value.signum()) || value == 0.;
+        let time = if rising { self.attack } else { self.release };
+        if time.is_zero() {
+            return target;
+        }
+        let max_delta = frame.as_secs_f32() / time.as_secs_f32();
+        value + (target - value).clamp(-max_delta, max_delta)
+    }
+}
+
+/// Per action ramp configuration and emulated axis values.
+#[derive(PartialEq, Clone, Debug)]
+pub struct RampTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub settings: HashMap<Keys, AxisRamp>,
+    /// The ramped value of each action, as a stick position.
+    pub values: HashMap<Keys, Vec2>,
+    /// The actions last driven by an analog receiver, following it without ramping.
+    pub analog: HashSet<Keys>,
+}
+
+impl<Keys> Default for RampTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            settings: HashMap::default(),
+            values: HashMap::default(),
+            analog: HashSet::default(),
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the ramped axis value of an action, or its axis value if the action isn't ramped.
+    pub fn ramped_axis(&self, kind: &Keys) -> f32 {
+        match self.ramp.values.get(kind) {
+            Some(value) => value.x,
+            None if self.ramp.settings.contains_key(kind) => 0.,
+            None => self.stick(kind).x,
+        }
+    }
+
+    /// Returns the ramped stick position of an action, or its stick position if the action isn't ramped.
+    pub fn ramped_stick(&self, kind: &Keys) -> Vec2 {
+        match self.ramp.values.get(kind) {
+            Some(value) => *value,
+            None if self.ramp.settings.contains_key(kind) => Vec2::ZERO,
+            None => self.stick(kind),
+        }
+    }
+
+    /// Enable or disable the analog emulation of an action.
+    pub fn set_axis_ramp(&mut self, kind: Keys, ramp: Option<AxisRamp>) {
+        match ramp {
+            Some(ramp) => self.ramp.settings.insert(kind, ramp),
+            None => self.ramp.settings.remove(&kind),
+        };
+        self.ramp.values.remove(&kind);
+        self.ramp.analog.remove(&kind);
+    }
+}
+
+/// System responsible for moving the ramped axes of each input view towards their axis values.
+pub(crate) fn axis_ramp_system<Keys>(mut query: Query<&mut InputView<Keys>>, time: Res<Time>)
+where
+    Keys: BindingTypeView,
+{
+    let frame = time.delta();
+    for mut view in query.iter_mut() {
+        if view.ramp.settings.is_empty() {
+            continue;
+        }
+        let mut values = view.ramp.values.clone();
+        let mut analog = view.ramp.analog.clone();
+        for (action, ramp) in view.ramp.settings.iter() {
+            let target = view.stick(action);
+            if let Some(receivers) = view.active_receivers(action) {
+                if receivers.0.iter().any(InputReceiver::is_axis) {
+                    analog.insert(*action);
+                } else {
+                    analog.remove(action);
+                }
+            }
+            let value = values.get(action).copied().unwrap_or_default();
+            let value = if analog.contains(action) {
+                target
+            } else {
+                Vec2::new(ramp.step(value.x, target.x, frame), ramp.step(value.y, target.y, frame))
+            };
+            if value == Vec2::ZERO {
+                values.remove(action);
+            } else {
+                values.insert(*action, value);
+            }
+        }
+        if values != view.ramp.values || analog != view.ramp.analog {
+            view.ramp.values = values;
+            view.ramp.analog = analog;
+        }
+    }
+}
+
+// Test to check that a held key ramps up over the attack time and decays faster once released.
+#[test]
+fn axis_ramp_test() {
+    let ramp = AxisRamp {
+        attack: Duration::from_millis(400),
+        release: Duration::from_millis(100),
+    };
+    let frame = Duration::from_millis(100);
+    let mut value = 0.;
+    for _ in 0..2 {
+        value = ramp.step(value, 1., frame);
+    }
+    assert!((value - 0.5).abs() < 1e-5);
+    value = ramp.step(value, 0., frame);
+    assert!((value - 0.).abs() < 1e-5);
+    assert!((ramp.step(0.5, -1., frame) - -0.5).abs() < 1e-5);
+}
@@ -0,0 +1,182 @@
+//! Runtime rebinding, reporting every binding mutation as a [`BindingChanged`] event for telemetry and UI syncing.
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct BindingChangeSystem;
+
+/// The binding set storing the bindings active before the first [`InputView::switch_set`], so they can be switched
+/// back to.
+pub const DEFAULT_BINDING_SET: &str = "default";
+
+/// A mutation of the receivers bound to an action, waiting to be sent as a [`BindingChanged`] event.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BindingChange<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub action: Keys,
+    pub old: Vec<InputReceivers>,
+    pub new: Vec<InputReceivers>,
+}
+
+/// Event sent when the receivers bound to an action of an input view change (rebind, clear or profile apply).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BindingChanged<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub entity: Entity,
+    pub action: Keys,
+    pub old: Vec<InputReceivers>,
+    pub new: Vec<InputReceivers>,
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Replace the receivers bound to an action, keeping the other binding settings.
+    /// The action is bound if it wasn't already.
+    pub fn rebind(&mut self, kind: Keys, receivers: Vec<InputReceivers>) {
+        let mut binding = self
+            .bindings
+            .get(&kind)
+            .cloned()
+            .unwrap_or_else(|| ActionBinding::from(kind));
+        binding.input_receivers = receivers.into_iter().collect();
+        self.apply_binding(&mut binding);
+    }
+
+    /// Remove the binding of an action.
+    pub fn clear_binding(&mut self, kind: &Keys) {
+        if let Some(binding) = self.bindings.remove(kind) {
+            self.binding_changes.push(BindingChange {
+                action: *kind,
+                old: binding.input_receivers.into_iter().collect(),
+                new: Vec::new(),
+            });
+            self.reindex(kind);
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Store a named binding set (e.g. on foot, in a car) that can be activated with [`InputView::switch_set`].
+    /// Replacing the active set applies its bindings right away.
+    pub fn add_binding_set(&mut self, name: impl Into<String>, bindings: Vec<ActionBinding<Keys>>) {
+        let name = name.into();
+        let bindings = bindings
+            .into_iter()
+            .map(|binding| (binding.key, binding))
+            .collect();
+        self.binding_sets.insert(name.clone(), bindings);
+        if self.active_set.as_ref() == Some(&name) {
+            self.active_set = None;
+            self.switch_set(name);
+        }
+    }
+
+    /// Atomically swap the active bindings with a named binding set, storing the current bindings back into the
+    /// previously active set, or into [`DEFAULT_BINDING_SET`] if no set was active yet. Receivers held during the switch are ignored until released, so no action of the new
+    /// set is pressed by a press started in the previous one. Returns false if the set doesn't exist.
+    pub fn switch_set(&mut self, name: impl Into<String>) -> bool {
+        let name = name.into();
+        if self.active_set.as_ref() == Some(&name) {
+            return true;
+        }
+        let next = match self.binding_sets.get(&name) {
+            Some(next) => next.clone(),
+            None => return false,
+        };
+        let active = self
+            .active_set
+            .take()
+            .unwrap_or_else(|| DEFAULT_BINDING_SET.to_string());
+        self.binding_sets.insert(active, self.bindings.clone());
+        let removed: Vec<Keys> = self
+            .bindings
+            .keys()
+            .filter(|kind| !next.contains_key(kind))
+            .copied()
+            .collect();
+        for kind in removed {
+            self.clear_binding(&kind);
+        }
+        for mut binding in next.into_values() {
+            self.apply_binding(&mut binding);
+        }
+        let held: Vec<InputReceiver> = self
+            .descriptors
+            .iter()
+            .filter(|descriptor| descriptor.axis.press.pressed())
+            .map(|descriptor| descriptor.input)
+            .collect();
+        self.suppressed.extend(held);
+        self.active_set = Some(name);
+        true
+    }
+}
+
+/// System responsible for sending a [`BindingChanged`] event for every binding mutation of the input views.
+pub(crate) fn binding_change_system<Keys>(
+    mut query: Query<(Entity, &mut InputView<Keys>)>,
+    mut wr: EventWriter<BindingChanged<Keys>>,
+) where
+    Keys: BindingTypeView,
+{
+    for (entity, mut view) in query.iter_mut() {
+        if view.binding_changes.is_empty() {
+            continue;
+        }
+        for change in view.binding_changes.drain(..) {
+            wr.send(BindingChanged {
+                entity,
+                action: change.action,
+                old: change.old,
+                new: change.new,
+            });
+        }
+    }
+}
+
+// Test to check that the bindings active before the first switch can be switched back to.
+#[test]
+fn switch_set_default_test() {
+    use bevy::prelude::KeyCode;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let space = InputReceiver::KeyboardKey(KeyCode::Space);
+    let mut view = InputView::new();
+    view.add_binding(ActionBinding::from(Jump).receivers(space.into()));
+    view.add_binding_set("car", Vec::new());
+    assert!(view.switch_set("car"));
+    assert!(view.bindings.get(&Jump).is_none());
+    assert!(view.switch_set(DEFAULT_BINDING_SET));
+    assert_eq!(view.receiver_index.get(&Jump), Some(&vec![space]));
+}
+
+// Test to check that binding an action the first time isn't reported, while rebinds and clears are.
+#[test]
+fn binding_change_test() {
+    use bevy::prelude::KeyCode;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let mut view = InputView::new();
+    view.add_binding(ActionBinding::from(Jump).receivers(InputReceiver::KeyboardKey(KeyCode::Space).into()));
+    assert!(view.binding_changes.is_empty());
+    view.rebind(Jump, vec![InputReceiver::KeyboardKey(KeyCode::W).into()]);
+    view.clear_binding(&Jump);
+    assert_eq!(view.binding_changes.len(), 2);
+    assert!(view.binding_changes[1].new.is_empty());
+}
@@ -5,27 +5,328 @@ use std::fmt::Debug;
 use bevy::prelude::{GamepadAxisType, GamepadButtonType, KeyCode, MouseButton};
 use serde::{Deserialize, Serialize};
 
-use crate::{prelude::MouseAxisType, view::InputSource};
+use crate::{
+    prelude::{DPadAxisType, Direction, GestureAxis, MotionAxisType, MouseAxisType, GAMEPAD_AXES, GAMEPAD_BUTTONS},
+    view::InputSource,
+};
+
+/// Every key code, in declaration order.
+pub const ALL_KEY_CODES: [KeyCode; 163] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Key0,
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::Escape,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::F13,
+    KeyCode::F14,
+    KeyCode::F15,
+    KeyCode::F16,
+    KeyCode::F17,
+    KeyCode::F18,
+    KeyCode::F19,
+    KeyCode::F20,
+    KeyCode::F21,
+    KeyCode::F22,
+    KeyCode::F23,
+    KeyCode::F24,
+    KeyCode::Snapshot,
+    KeyCode::Scroll,
+    KeyCode::Pause,
+    KeyCode::Insert,
+    KeyCode::Home,
+    KeyCode::Delete,
+    KeyCode::End,
+    KeyCode::PageDown,
+    KeyCode::PageUp,
+    KeyCode::Left,
+    KeyCode::Up,
+    KeyCode::Right,
+    KeyCode::Down,
+    KeyCode::Back,
+    KeyCode::Return,
+    KeyCode::Space,
+    KeyCode::Compose,
+    KeyCode::Caret,
+    KeyCode::Numlock,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::AbntC1,
+    KeyCode::AbntC2,
+    KeyCode::NumpadAdd,
+    KeyCode::Apostrophe,
+    KeyCode::Apps,
+    KeyCode::Asterisk,
+    KeyCode::Plus,
+    KeyCode::At,
+    KeyCode::Ax,
+    KeyCode::Backslash,
+    KeyCode::Calculator,
+    KeyCode::Capital,
+    KeyCode::Colon,
+    KeyCode::Comma,
+    KeyCode::Convert,
+    KeyCode::NumpadDecimal,
+    KeyCode::NumpadDivide,
+    KeyCode::Equals,
+    KeyCode::Grave,
+    KeyCode::Kana,
+    KeyCode::Kanji,
+    KeyCode::LAlt,
+    KeyCode::LBracket,
+    KeyCode::LControl,
+    KeyCode::LShift,
+    KeyCode::LWin,
+    KeyCode::Mail,
+    KeyCode::MediaSelect,
+    KeyCode::MediaStop,
+    KeyCode::Minus,
+    KeyCode::NumpadMultiply,
+    KeyCode::Mute,
+    KeyCode::MyComputer,
+    KeyCode::NavigateForward,
+    KeyCode::NavigateBackward,
+    KeyCode::NextTrack,
+    KeyCode::NoConvert,
+    KeyCode::NumpadComma,
+    KeyCode::NumpadEnter,
+    KeyCode::NumpadEquals,
+    KeyCode::Oem102,
+    KeyCode::Period,
+    KeyCode::PlayPause,
+    KeyCode::Power,
+    KeyCode::PrevTrack,
+    KeyCode::RAlt,
+    KeyCode::RBracket,
+    KeyCode::RControl,
+    KeyCode::RShift,
+    KeyCode::RWin,
+    KeyCode::Semicolon,
+    KeyCode::Slash,
+    KeyCode::Sleep,
+    KeyCode::Stop,
+    KeyCode::NumpadSubtract,
+    KeyCode::Sysrq,
+    KeyCode::Tab,
+    KeyCode::Underline,
+    KeyCode::Unlabeled,
+    KeyCode::VolumeDown,
+    KeyCode::VolumeUp,
+    KeyCode::Wake,
+    KeyCode::WebBack,
+    KeyCode::WebFavorites,
+    KeyCode::WebForward,
+    KeyCode::WebHome,
+    KeyCode::WebRefresh,
+    KeyCode::WebSearch,
+    KeyCode::WebStop,
+    KeyCode::Yen,
+    KeyCode::Copy,
+    KeyCode::Paste,
+    KeyCode::Cut,
+];
+
+/// The kind of value a receiver produces.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum ReceiverKind {
+    /// Pressed or released, with a value of 1 or 0.
+    Button,
+    /// A single analog axis, e.g. a trigger or the mouse wheel.
+    Axis,
+    /// A component of a two-dimensional axis, e.g. the X axis of a stick or of the mouse motion, usually bound
+    /// together with the other component.
+    Axis2d,
+    /// A screen position, e.g. the cursor or a finger on a touch screen.
+    Pointer,
+}
 
 /// A agnostic type, representing a type of input that can be accepted on ezinput systems.
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum InputReceiver {
     KeyboardKey(KeyCode),
+    /// The depth of a key of an analog keyboard, from 0 to 1, fed by the
+    /// [`AnalogKeyboardPlugin`](crate::analog::AnalogKeyboardPlugin).
+    KeyboardAnalog(KeyCode),
+    /// Any mouse button, including the extra side buttons as [`MouseButton::Other`].
     MouseButton(MouseButton),
+    /// A double click of a mouse button, pressed for a single frame.
+    MouseDoubleClick(MouseButton),
     GamepadButton(GamepadButtonType),
     MouseAxis(MouseAxisType),
     GamepadAxis(GamepadAxisType),
+    /// An axis of the d-pad, from -1 to 1, fed by the d-pad buttons.
+    DPadAxis(DPadAxisType),
     MouseAxisDelta(MouseAxisType),
+    GamepadGyro(MotionAxisType),
+    GamepadAccelerometer(MotionAxisType),
+    TouchButton(u16),
+    TouchAxis(u16),
+    TouchGesture(GestureAxis),
+    /// A swipe with a finger or by dragging the mouse with the left button, pressed for a single frame. Belongs to the
+    /// touch source, the view tracks the actual one (see
+    /// [`InputView::receiver_source`](crate::view::InputView::receiver_source)).
+    Swipe(Direction),
+    /// A user-defined receiver fed through the [`InputInjector`](crate::inject::InputInjector). Treated as a
+    /// button when validating bindings.
+    Custom(u64),
 }
 
 impl InputReceiver {
+    /// Returns whether this receiver is an axis, instead of a button.
+    pub fn is_axis(&self) -> bool {
+        matches!(
+            self,
+            InputReceiver::GamepadAxis(_)
+                | InputReceiver::DPadAxis(_)
+                | InputReceiver::KeyboardAnalog(_)
+                | InputReceiver::MouseAxis(_)
+                | InputReceiver::MouseAxisDelta(_)
+                | InputReceiver::GamepadGyro(_)
+                | InputReceiver::GamepadAccelerometer(_)
+                | InputReceiver::TouchAxis(_)
+                | InputReceiver::TouchGesture(_)
+        )
+    }
+
+    /// Returns the kind of value produced by this receiver, so rebind UIs and validation only accept receivers
+    /// matching the action.
+    pub fn kind(&self) -> ReceiverKind {
+        match *self {
+            InputReceiver::KeyboardKey(_)
+            | InputReceiver::MouseButton(_)
+            | InputReceiver::MouseDoubleClick(_)
+            | InputReceiver::GamepadButton(_)
+            | InputReceiver::TouchButton(_)
+            | InputReceiver::Swipe(_)
+            | InputReceiver::Custom(_) => ReceiverKind::Button,
+            InputReceiver::GamepadAxis(
+                GamepadAxisType::LeftStickX
+                | GamepadAxisType::LeftStickY
+                | GamepadAxisType::RightStickX
+                | GamepadAxisType::RightStickY,
+            )
+            | InputReceiver::MouseAxisDelta(MouseAxisType::X | MouseAxisType::Y) => ReceiverKind::Axis2d,
+            InputReceiver::MouseAxis(MouseAxisType::X | MouseAxisType::Y) | InputReceiver::TouchAxis(_) => {
+                ReceiverKind::Pointer
+            }
+            InputReceiver::GamepadAxis(_)
+            | InputReceiver::DPadAxis(_)
+            | InputReceiver::KeyboardAnalog(_)
+            | InputReceiver::MouseAxis(_)
+            | InputReceiver::MouseAxisDelta(_)
+            | InputReceiver::GamepadGyro(_)
+            | InputReceiver::GamepadAccelerometer(_)
+            | InputReceiver::TouchGesture(_) => ReceiverKind::Axis,
+        }
+    }
+
+    /// Returns every known receiver of a source, e.g. to list the valid choices of a rebind UI. Receivers without a
+    /// fixed set of values (analog keys, extra mouse buttons, touch buttons and axes, custom receivers) are left out.
+    pub fn all(source: InputSource) -> impl Iterator<Item = InputReceiver> {
+        let keyboard = || ALL_KEY_CODES.into_iter().map(InputReceiver::KeyboardKey);
+        let mouse = || {
+            let buttons = [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+            let axes = [MouseAxisType::X, MouseAxisType::Y, MouseAxisType::Wheel];
+            buttons
+                .into_iter()
+                .map(InputReceiver::MouseButton)
+                .chain(buttons.into_iter().map(InputReceiver::MouseDoubleClick))
+                .chain(axes.into_iter().map(InputReceiver::MouseAxis))
+                .chain(axes.into_iter().map(InputReceiver::MouseAxisDelta))
+        };
+        let receivers: Vec<InputReceiver> = match source {
+            InputSource::Keyboard => keyboard().collect(),
+            InputSource::Mouse => mouse().collect(),
+            InputSource::KeyboardMouse => keyboard().chain(mouse()).collect(),
+            InputSource::Gamepad => {
+                let motion = [MotionAxisType::X, MotionAxisType::Y, MotionAxisType::Z];
+                GAMEPAD_BUTTONS
+                    .into_iter()
+                    .map(InputReceiver::GamepadButton)
+                    .chain(GAMEPAD_AXES.into_iter().map(InputReceiver::GamepadAxis))
+                    .chain([DPadAxisType::X, DPadAxisType::Y].into_iter().map(InputReceiver::DPadAxis))
+                    .chain(motion.into_iter().map(InputReceiver::GamepadGyro))
+                    .chain(motion.into_iter().map(InputReceiver::GamepadAccelerometer))
+                    .collect()
+            }
+            InputSource::Touch => [GestureAxis::Pinch, GestureAxis::Rotate]
+                .into_iter()
+                .map(InputReceiver::TouchGesture)
+                .chain(Direction::ALL.into_iter().map(InputReceiver::Swipe))
+                .collect(),
+            InputSource::Custom => Vec::new(),
+        };
+        receivers.into_iter()
+    }
+
     pub fn source(&self) -> InputSource {
         match *self {
-            InputReceiver::KeyboardKey(_) => InputSource::Keyboard,
-            InputReceiver::GamepadButton(_) | InputReceiver::GamepadAxis(_) => InputSource::Gamepad,
+            InputReceiver::KeyboardKey(_) | InputReceiver::KeyboardAnalog(_) => InputSource::Keyboard,
+            InputReceiver::GamepadButton(_)
+            | InputReceiver::GamepadAxis(_)
+            | InputReceiver::DPadAxis(_)
+            | InputReceiver::GamepadGyro(_)
+            | InputReceiver::GamepadAccelerometer(_) => InputSource::Gamepad,
             InputReceiver::MouseButton(_)
+            | InputReceiver::MouseDoubleClick(_)
             | InputReceiver::MouseAxis(_)
             | InputReceiver::MouseAxisDelta(_) => InputSource::Mouse,
+            InputReceiver::TouchButton(_)
+            | InputReceiver::TouchAxis(_)
+            | InputReceiver::TouchGesture(_)
+            | InputReceiver::Swipe(_) => InputSource::Touch,
+            InputReceiver::Custom(_) => InputSource::Custom,
         }
     }
 }
@@ -54,6 +355,12 @@ impl Into<InputReceiver> for GamepadAxisType {
     }
 }
 
+impl From<DPadAxisType> for InputReceiver {
+    fn from(axis: DPadAxisType) -> Self {
+        InputReceiver::DPadAxis(axis)
+    }
+}
+
 impl Into<InputReceiver> for MouseAxisType {
     fn into(self) -> InputReceiver {
         InputReceiver::MouseAxis(self)
@@ -132,3 +439,24 @@ where
         vec
     }
 }
+
+// Test to check that the known receivers of a source are of that source, with their kind.
+#[test]
+fn receiver_kind_test() {
+    for source in [InputSource::Keyboard, InputSource::Mouse, InputSource::Gamepad, InputSource::Touch] {
+        assert!(InputReceiver::all(source).all(|rcv| rcv.source() == source));
+    }
+    assert_eq!(InputReceiver::all(InputSource::Keyboard).count(), ALL_KEY_CODES.len());
+    assert_eq!(InputReceiver::GamepadAxis(GamepadAxisType::LeftZ).kind(), ReceiverKind::Axis);
+    assert_eq!(InputReceiver::MouseAxis(MouseAxisType::X).kind(), ReceiverKind::Pointer);
+    assert_eq!(InputReceiver::MouseAxisDelta(MouseAxisType::Y).kind(), ReceiverKind::Axis2d);
+    assert_eq!(InputReceiver::TouchAxis(0).kind(), ReceiverKind::Pointer);
+    // Button actions keep rejecting exactly the axis receivers, pointers included.
+    let receivers = [InputSource::Keyboard, InputSource::Mouse, InputSource::Gamepad, InputSource::Touch]
+        .into_iter()
+        .flat_map(InputReceiver::all)
+        .chain([InputReceiver::TouchButton(0), InputReceiver::TouchAxis(0)]);
+    for rcv in receivers {
+        assert_eq!(crate::binding::ActionType::Button.accepts(rcv.kind()), !rcv.is_axis());
+    }
+}
@@ -0,0 +1,298 @@
+//! Actions registered at runtime by name, as an alternative to the enumerated [`BindingTypeView`] keys. Useful for
+//! data-driven or mod-defined actions that aren't known at compile time: register the actions in an
+//! [`ActionRegistry`] and use `EZInputPlugin::<ActionId>`. Mods and scripts register their actions under a namespace
+//! through [`ActionRegistry::namespace`], avoiding collisions with the actions of the base game.
+//!
+//! Action handles are serialized by name, through the registry made current with [`ActionRegistry::install`], so
+//! saved bindings don't depend on the order the actions were registered in.
+use std::fmt;
+use std::sync::RwLock;
+
+use bevy::utils::HashMap;
+use serde::de::{self, Visitor};
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::prelude::*;
+
+/// The number of action handles stored densely in an [`ActionMap`]. Greater handles are hashed instead, so a bogus
+/// handle can't grow the maps without bounds.
+pub const DENSE_ACTION_IDS: u32 = 1024;
+
+/// The names of the actions of the installed registry, used to serialize the handles.
+static INSTALLED_NAMES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// A handle to an action registered in an [`ActionRegistry`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct ActionId(pub u32);
+
+impl BindingTypeView for ActionId {
+    fn index(&self) -> Option<usize> {
+        (self.0 < DENSE_ACTION_IDS).then_some(self.0 as usize)
+    }
+}
+
+impl Serialize for ActionId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let names = INSTALLED_NAMES.read().unwrap_or_else(|error| error.into_inner());
+        match names.get(self.0 as usize) {
+            Some(name) => serializer.serialize_str(name),
+            None => Err(ser::Error::custom(format!(
+                "action {} isn't registered in the installed action registry",
+                self.0
+            ))),
+        }
+    }
+}
+
+struct ActionIdVisitor;
+
+impl<'de> Visitor<'de> for ActionIdVisitor {
+    type Value = ActionId;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("the name of a registered action")
+    }
+
+    fn visit_str<E>(self, name: &str) -> Result<ActionId, E>
+    where
+        E: de::Error,
+    {
+        let names = INSTALLED_NAMES.read().unwrap_or_else(|error| error.into_inner());
+        match names.iter().position(|other| other == name) {
+            Some(index) => Ok(ActionId(index as u32)),
+            None => Err(E::custom(format!(
+                "action `{}` isn't registered in the installed action registry",
+                name
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ActionId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ActionIdVisitor)
+    }
+}
+
+/// The separator between a namespace and the name of an action (e.g. `my_mod:dash`).
+pub const NAMESPACE_SEPARATOR: char = ':';
+
+/// An error when registering an action.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum RegistryError {
+    /// The name of the action or namespace contains the [`NAMESPACE_SEPARATOR`], so it could collide with the
+    /// actions of a namespace.
+    ReservedSeparator(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::ReservedSeparator(name) => {
+                write!(f, "`{}` contains the namespace separator `{}`", name, NAMESPACE_SEPARATOR)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+fn check_name(name: &str) -> Result<(), RegistryError> {
+    match name.contains(NAMESPACE_SEPARATOR) {
+        true => Err(RegistryError::ReservedSeparator(name.to_string())),
+        false => Ok(()),
+    }
+}
+
+/// The names of the actions registered at runtime, each with a dense [`ActionId`].
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ActionRegistry {
+    names: Vec<String>,
+    ids: HashMap<String, ActionId>,
+    /// Whether this registry names the handles when serializing them.
+    installed: bool,
+}
+
+impl ActionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an action by name, returning its handle. Registering a name twice returns the same handle. The name
+    /// can't contain the [`NAMESPACE_SEPARATOR`], which is reserved to the namespaces.
+    pub fn register(&mut self, name: impl Into<String>) -> Result<ActionId, RegistryError> {
+        let name = name.into();
+        check_name(&name)?;
+        Ok(self.register_qualified(name))
+    }
+
+    fn register_qualified(&mut self, name: String) -> ActionId {
+        if let Some(id) = self.ids.get(&name) {
+            return *id;
+        }
+        let id = ActionId(self.names.len() as u32);
+        self.names.push(name.clone());
+        self.ids.insert(name, id);
+        if self.installed {
+            self.publish();
+        }
+        id
+    }
+
+    /// Make this the registry naming the action handles when they are serialized or deserialized (e.g. saving or
+    /// loading bindings), replacing the one installed before. The actions registered afterwards are named too.
+    pub fn install(&mut self) {
+        self.installed = true;
+        self.publish();
+    }
+
+    fn publish(&self) {
+        let mut names = INSTALLED_NAMES.write().unwrap_or_else(|error| error.into_inner());
+        names.clone_from(&self.names);
+    }
+
+    /// Returns the handle of a registered action.
+    pub fn get(&self, name: &str) -> Option<ActionId> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name of a registered action.
+    pub fn name(&self, id: ActionId) -> Option<&str> {
+        self.names.get(id.0 as usize).map(String::as_str)
+    }
+
+    /// Returns the number of registered actions.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns every registered action with its name, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = (ActionId, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (ActionId(index as u32), name.as_str()))
+    }
+
+    /// Returns a scope registering actions under the given namespace, usually the id of a mod.
+    pub fn namespace(&mut self, namespace: impl Into<String>) -> ActionNamespace<'_> {
+        ActionNamespace {
+            registry: self,
+            namespace: namespace.into(),
+        }
+    }
+
+    /// Returns every action registered under the given namespace.
+    pub fn actions_in<'a>(&'a self, namespace: &'a str) -> impl Iterator<Item = ActionId> + 'a {
+        self.iter()
+            .filter(move |(_, name)| {
+                name.split_once(NAMESPACE_SEPARATOR)
+                    .is_some_and(|(prefix, _)| prefix == namespace)
+            })
+            .map(|(id, _)| id)
+    }
+}
+
+/// A scope of an [`ActionRegistry`] registering actions under a namespace.
+pub struct ActionNamespace<'a> {
+    registry: &'a mut ActionRegistry,
+    namespace: String,
+}
+
+impl<'a> ActionNamespace<'a> {
+    /// Returns the full name of an action of this namespace.
+    pub fn qualify(&self, name: &str) -> String {
+        format!("{}{}{}", self.namespace, NAMESPACE_SEPARATOR, name)
+    }
+
+    /// Register an action of this namespace, returning its handle. Neither the name nor the namespace can contain the
+    /// [`NAMESPACE_SEPARATOR`].
+    pub fn register(&mut self, name: &str) -> Result<ActionId, RegistryError> {
+        check_name(&self.namespace)?;
+        check_name(name)?;
+        Ok(self.registry.register_qualified(self.qualify(name)))
+    }
+
+    /// Returns the handle of an action of this namespace.
+    pub fn get(&self, name: &str) -> Option<ActionId> {
+        self.registry.get(&self.qualify(name))
+    }
+
+    /// Register an action of this namespace and bind it to the given receivers in an input view.
+    pub fn bind(
+        &mut self,
+        view: &mut InputView<ActionId>,
+        name: &str,
+        receivers: Vec<Vec<InputReceiver>>,
+    ) -> Result<ActionId, RegistryError> {
+        let id = self.register(name)?;
+        view.add_binding(&mut ActionBinding::new_from_vec(id, receivers));
+        Ok(id)
+    }
+}
+
+impl InputView<ActionId> {
+    /// Remove the bindings of every action of a namespace, e.g. when a mod is unloaded.
+    pub fn clear_namespace(&mut self, registry: &ActionRegistry, namespace: &str) {
+        for id in registry.actions_in(namespace) {
+            self.clear_binding(&id);
+        }
+    }
+}
+
+// Test to check that registering a name twice returns the same handle.
+#[test]
+fn action_registry_test() {
+    let mut registry = ActionRegistry::new();
+    let jump = registry.register("jump").unwrap();
+    let dash = registry.register("dash").unwrap();
+    assert_eq!(registry.register("jump").unwrap(), jump);
+    assert_ne!(jump, dash);
+    assert_eq!(registry.get("dash"), Some(dash));
+    assert_eq!(registry.name(jump), Some("jump"));
+    assert_eq!(registry.len(), 2);
+}
+
+// Test to check that namespaced actions can't collide with the actions of the base game or of other namespaces.
+#[test]
+fn action_namespace_test() {
+    let mut registry = ActionRegistry::new();
+    let dash = registry.register("dash").unwrap();
+    assert!(registry.register("mod:dash").is_err());
+    let mut mods = registry.namespace("mod");
+    let mod_dash = mods.register("dash").unwrap();
+    assert_ne!(mod_dash, dash);
+    assert_eq!(mods.register("dash"), Ok(mod_dash));
+    assert_eq!(mods.get("jump"), None);
+    assert!(mods.register("sub:dash").is_err());
+    assert!(registry.namespace("mod:sub").register("dash").is_err());
+    assert_eq!(registry.get("mod:dash"), Some(mod_dash));
+    assert_eq!(registry.actions_in("mod").collect::<Vec<_>>(), vec![mod_dash]);
+}
+
+// Test to check that action handles are serialized by name, and that bogus handles aren't stored densely.
+#[test]
+fn action_id_serde_test() {
+    let mut registry = ActionRegistry::new();
+    registry.register("crouch").unwrap();
+    registry.install();
+    let jump = registry.register("jump").unwrap();
+    assert_eq!(action_name(&jump).unwrap(), "jump");
+    assert_eq!(ActionAliases::new().action::<ActionId>("jump").unwrap(), jump);
+    assert!(ActionAliases::new().action::<ActionId>("dash").is_err());
+    assert!(action_name(&ActionId(7)).is_err());
+
+    let mut map = ActionMap::new();
+    map.insert(ActionId(u32::MAX), ());
+    assert!(map.contains_key(&ActionId(u32::MAX)));
+}
@@ -0,0 +1,398 @@
+//! Remapping layer for exotic gamepads, using SDL_GameControllerDB-style mapping strings
+//! (e.g. `03000000790000000600000000000000,Generic USB Joystick,a:b2,b:b1,leftx:a0,lefty:a1,`).
+//!
+//! The `bN`, `aN` and `hN.M` sources are the raw button, axis and hat indices of the device. Bevy only reports
+//! elements already normalized by its gilrs backend, so these raw indices can't be observed here: install the
+//! mappings into the backend with [`GamepadMappingDb::install`] before adding the Bevy plugins, and gilrs applies
+//! them before the elements reach the input view. Remaps between standard elements, which can be observed, are
+//! applied by the [`GamepadMarker`] holding the mapping (see [`GamepadMapping::with_remap`]).
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use bevy::log::warn;
+
+use crate::prelude::*;
+
+/// A button or axis of a gamepad.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum GamepadElement {
+    Button(GamepadButtonType),
+    Axis(GamepadAxisType),
+}
+
+impl From<GamepadElement> for InputReceiver {
+    fn from(element: GamepadElement) -> Self {
+        match element {
+            GamepadElement::Button(kind) => InputReceiver::GamepadButton(kind),
+            GamepadElement::Axis(kind) => InputReceiver::GamepadAxis(kind),
+        }
+    }
+}
+
+/// The half of an axis used by a mapping entry, written `+` or `-` before the axis.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum AxisHalf {
+    Positive,
+    Negative,
+}
+
+impl AxisHalf {
+    fn prefix(half: Option<AxisHalf>) -> &'static str {
+        match half {
+            Some(AxisHalf::Positive) => "+",
+            Some(AxisHalf::Negative) => "-",
+            None => "",
+        }
+    }
+
+    fn strip(name: &str) -> (Option<AxisHalf>, &str) {
+        if let Some(name) = name.strip_prefix('+') {
+            (Some(AxisHalf::Positive), name)
+        } else if let Some(name) = name.strip_prefix('-') {
+            (Some(AxisHalf::Negative), name)
+        } else {
+            (None, name)
+        }
+    }
+}
+
+/// A raw button, axis or hat of a device, indexed as reported by the operating system.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum MappingSource {
+    Button(u8),
+    Axis {
+        index: u8,
+        /// The half of the axis used, if only one is.
+        half: Option<AxisHalf>,
+        /// Whether the axis is inverted, written `~` after the axis.
+        inverted: bool,
+    },
+    Hat {
+        index: u8,
+        /// The direction of the hat, as a bit mask (1 up, 2 right, 4 down, 8 left).
+        mask: u8,
+    },
+}
+
+impl MappingSource {
+    /// Parse a source such as `b2`, `-a1`, `a5~` or `h0.4`.
+    pub fn parse(source: &str) -> Option<Self> {
+        let (half, rest) = AxisHalf::strip(source);
+        let (inverted, rest) = match rest.strip_suffix('~') {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        let (kind, index) = rest.split_at(rest.char_indices().nth(1)?.0);
+        match kind {
+            "b" if half.is_none() && !inverted => Some(MappingSource::Button(index.parse().ok()?)),
+            "a" => Some(MappingSource::Axis {
+                index: index.parse().ok()?,
+                half,
+                inverted,
+            }),
+            "h" if half.is_none() && !inverted => {
+                let (index, mask) = index.split_once('.')?;
+                let mask = mask.parse().ok()?;
+                matches!(mask, 1 | 2 | 4 | 8).then_some(MappingSource::Hat {
+                    index: index.parse().ok()?,
+                    mask,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for MappingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            MappingSource::Button(index) => write!(f, "b{}", index),
+            MappingSource::Axis { index, half, inverted } => {
+                write!(f, "{}a{}{}", AxisHalf::prefix(half), index, if inverted { "~" } else { "" })
+            }
+            MappingSource::Hat { index, mask } => write!(f, "h{}.{}", index, mask),
+        }
+    }
+}
+
+/// An entry of a mapping string, mapping a raw source to a standard element.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct MappingEntry {
+    pub target: GamepadElement,
+    /// The half of the target axis driven by the source, if only one is.
+    pub target_half: Option<AxisHalf>,
+    pub source: MappingSource,
+}
+
+/// An error when parsing a mapping string.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum GamepadMappingError {
+    /// The mapping string doesn't have a GUID and a name.
+    MissingHeader,
+    /// The given entry isn't a `target:source` pair.
+    InvalidEntry(String),
+    /// The given source isn't a known button, axis or hat.
+    UnknownSource(String),
+}
+
+impl Display for GamepadMappingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GamepadMappingError::MissingHeader => {
+                write!(f, "the mapping is missing its GUID or name")
+            }
+            GamepadMappingError::InvalidEntry(entry) => {
+                write!(f, "invalid mapping entry `{}`", entry)
+            }
+            GamepadMappingError::UnknownSource(source) => {
+                write!(f, "unknown mapping source `{}`", source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GamepadMappingError {}
+
+/// The standard elements, by their name in mapping strings.
+const TARGETS: [(&str, GamepadElement); 21] = [
+    ("a", GamepadElement::Button(GamepadButtonType::South)),
+    ("b", GamepadElement::Button(GamepadButtonType::East)),
+    ("x", GamepadElement::Button(GamepadButtonType::West)),
+    ("y", GamepadElement::Button(GamepadButtonType::North)),
+    ("back", GamepadElement::Button(GamepadButtonType::Select)),
+    ("start", GamepadElement::Button(GamepadButtonType::Start)),
+    ("guide", GamepadElement::Button(GamepadButtonType::Mode)),
+    ("leftstick", GamepadElement::Button(GamepadButtonType::LeftThumb)),
+    ("rightstick", GamepadElement::Button(GamepadButtonType::RightThumb)),
+    ("leftshoulder", GamepadElement::Button(GamepadButtonType::LeftTrigger)),
+    ("rightshoulder", GamepadElement::Button(GamepadButtonType::RightTrigger)),
+    ("lefttrigger", GamepadElement::Button(GamepadButtonType::LeftTrigger2)),
+    ("righttrigger", GamepadElement::Button(GamepadButtonType::RightTrigger2)),
+    ("dpup", GamepadElement::Button(GamepadButtonType::DPadUp)),
+    ("dpdown", GamepadElement::Button(GamepadButtonType::DPadDown)),
+    ("dpleft", GamepadElement::Button(GamepadButtonType::DPadLeft)),
+    ("dpright", GamepadElement::Button(GamepadButtonType::DPadRight)),
+    ("leftx", GamepadElement::Axis(GamepadAxisType::LeftStickX)),
+    ("lefty", GamepadElement::Axis(GamepadAxisType::LeftStickY)),
+    ("rightx", GamepadElement::Axis(GamepadAxisType::RightStickX)),
+    ("righty", GamepadElement::Axis(GamepadAxisType::RightStickY)),
+];
+
+/// A mapping normalizing the buttons and axes of a gamepad to the standard layout.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct GamepadMapping {
+    pub guid: String,
+    pub name: String,
+    /// The raw sources of each standard element, applied by the gamepad backend once installed.
+    pub entries: Vec<MappingEntry>,
+    /// The platform the mapping is meant for, if restricted to one.
+    pub platform: Option<String>,
+    /// Remaps between standard elements, applied to the elements reported by Bevy.
+    pub elements: HashMap<GamepadElement, GamepadElement>,
+}
+
+impl GamepadMapping {
+    /// Parse a single SDL_GameControllerDB mapping string. Unknown targets (e.g. `misc1`) are ignored, and entries
+    /// with an invalid source are skipped with a warning.
+    pub fn parse(mapping: &str) -> Result<Self, GamepadMappingError> {
+        let mut entries = mapping.trim().split(',');
+        let guid = entries.next().filter(|guid| !guid.is_empty());
+        let name = entries.next();
+        let (guid, name) = match guid.zip(name) {
+            Some(header) => header,
+            None => return Err(GamepadMappingError::MissingHeader),
+        };
+        let mut mapping = Self {
+            guid: guid.to_string(),
+            name: name.to_string(),
+            ..Self::default()
+        };
+        for entry in entries.filter(|entry| !entry.is_empty()) {
+            let (target, source) = match entry.split_once(':') {
+                Some(pair) => pair,
+                None => {
+                    warn!(
+                        "Skipping {} in the gamepad mapping of {}",
+                        GamepadMappingError::InvalidEntry(entry.to_string()),
+                        name
+                    );
+                    continue;
+                }
+            };
+            if target == "platform" {
+                mapping.platform = Some(source.to_string());
+                continue;
+            }
+            let (target_half, target) = AxisHalf::strip(target);
+            let target = match Self::target(target) {
+                Some(target) => target,
+                None => continue,
+            };
+            match MappingSource::parse(source) {
+                Some(source) => mapping.entries.push(MappingEntry {
+                    target,
+                    target_half,
+                    source,
+                }),
+                None => warn!(
+                    "Skipping {} in the gamepad mapping of {}",
+                    GamepadMappingError::UnknownSource(source.to_string()),
+                    name
+                ),
+            }
+        }
+        Ok(mapping)
+    }
+
+    /// Remap a standard element reported by Bevy to another one, e.g. for a pad whose faces are swapped even once
+    /// normalized.
+    pub fn with_remap(mut self, from: GamepadElement, to: GamepadElement) -> Self {
+        self.elements.insert(from, to);
+        self
+    }
+
+    /// Returns the standard element for the given reported element, applying the remaps between standard elements.
+    pub fn remap(&self, element: GamepadElement) -> Option<GamepadElement> {
+        Some(self.elements.get(&element).copied().unwrap_or(element))
+    }
+
+    /// Returns the mapping as an SDL_GameControllerDB mapping string.
+    pub fn to_sdl_string(&self) -> String {
+        let mut mapping = format!("{},{},", self.guid, self.name);
+        for entry in self.entries.iter() {
+            mapping.push_str(&format!(
+                "{}{}:{},",
+                AxisHalf::prefix(entry.target_half),
+                Self::target_name(entry.target).unwrap_or_default(),
+                entry.source
+            ));
+        }
+        if let Some(ref platform) = self.platform {
+            mapping.push_str(&format!("platform:{},", platform));
+        }
+        mapping
+    }
+
+    fn target(name: &str) -> Option<GamepadElement> {
+        TARGETS
+            .iter()
+            .find(|(target, _)| *target == name)
+            .map(|(_, element)| *element)
+    }
+
+    fn target_name(element: GamepadElement) -> Option<&'static str> {
+        TARGETS
+            .iter()
+            .find(|(_, target)| *target == element)
+            .map(|(name, _)| *name)
+    }
+}
+
+/// A collection of mappings, usually loaded from a `gamecontrollerdb.txt` file.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct GamepadMappingDb {
+    pub mappings: Vec<GamepadMapping>,
+}
+
+impl GamepadMappingDb {
+    /// Parse every mapping of a database, one per line. Empty lines and `#` comments are skipped, and so are invalid
+    /// mappings, with a warning.
+    pub fn parse(db: &str) -> Self {
+        let mappings = db
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| match GamepadMapping::parse(line) {
+                Ok(mapping) => Some(mapping),
+                Err(err) => {
+                    warn!("Skipping the gamepad mapping `{}`: {}", line, err);
+                    None
+                }
+            })
+            .collect();
+        Self { mappings }
+    }
+
+    /// Install the mappings into the gilrs gamepad backend, through the `SDL_GAMECONTROLLERCONFIG` environment
+    /// variable it reads when created. Call it before adding the Bevy plugins; the mappings already in the variable
+    /// are kept.
+    pub fn install(&self) {
+        let mut config = std::env::var("SDL_GAMECONTROLLERCONFIG").unwrap_or_default();
+        for mapping in self.mappings.iter() {
+            if !config.is_empty() && !config.ends_with('\n') {
+                config.push('\n');
+            }
+            config.push_str(&mapping.to_sdl_string());
+        }
+        std::env::set_var("SDL_GAMECONTROLLERCONFIG", config);
+    }
+
+    /// Returns the mapping matching the given GUID.
+    pub fn by_guid(&self, guid: &str) -> Option<&GamepadMapping> {
+        self.mappings.iter().find(|mapping| mapping.guid == guid)
+    }
+
+    /// Returns the mapping matching the given gamepad name.
+    pub fn by_name(&self, name: &str) -> Option<&GamepadMapping> {
+        self.mappings.iter().find(|mapping| mapping.name == name)
+    }
+}
+
+// Test to check that mapping strings keep their raw sources and modifiers, skipping the invalid entries.
+#[test]
+fn gamepad_mapping_test() {
+    let mapping = GamepadMapping::parse(
+        "03000000790000000600000000000000,Generic USB Joystick,a:b2,dpup:h0.1,+lefty:-a1,righttrigger:a5~,x:q7,\
+         misc1:b9,platform:Linux,",
+    )
+    .unwrap();
+    assert_eq!(mapping.name, "Generic USB Joystick");
+    assert_eq!(mapping.platform.as_deref(), Some("Linux"));
+    assert_eq!(
+        mapping.entries,
+        vec![
+            MappingEntry {
+                target: GamepadElement::Button(GamepadButtonType::South),
+                target_half: None,
+                source: MappingSource::Button(2),
+            },
+            MappingEntry {
+                target: GamepadElement::Button(GamepadButtonType::DPadUp),
+                target_half: None,
+                source: MappingSource::Hat { index: 0, mask: 1 },
+            },
+            MappingEntry {
+                target: GamepadElement::Axis(GamepadAxisType::LeftStickY),
+                target_half: Some(AxisHalf::Positive),
+                source: MappingSource::Axis {
+                    index: 1,
+                    half: Some(AxisHalf::Negative),
+                    inverted: false,
+                },
+            },
+            MappingEntry {
+                target: GamepadElement::Button(GamepadButtonType::RightTrigger2),
+                target_half: None,
+                source: MappingSource::Axis {
+                    index: 5,
+                    half: None,
+                    inverted: true,
+                },
+            },
+        ]
+    );
+    assert_eq!(
+        mapping.to_sdl_string(),
+        "03000000790000000600000000000000,Generic USB Joystick,a:b2,dpup:h0.1,+lefty:-a1,righttrigger:a5~,\
+         platform:Linux,"
+    );
+    assert_eq!(
+        mapping.remap(GamepadElement::Button(GamepadButtonType::North)),
+        Some(GamepadElement::Button(GamepadButtonType::North))
+    );
+    assert_eq!(
+        GamepadMapping::parse("guid-only"),
+        Err(GamepadMappingError::MissingHeader)
+    );
+    assert_eq!(GamepadMappingDb::parse("guid-only\n# comment\n0300,Pad,a:b0,").mappings.len(), 1);
+}
@@ -0,0 +1,387 @@
+//! A stable on-disk format for input replays. A replay stores the action states changed in each frame, with the
+//! actions referenced by name so the files survive the actions being reordered or renamed (through
+//! [`ActionAliases`]) by game updates. Enable the `replay_zstd` feature to compress them, so they are small enough to
+//! attach to bug reports.
+//!
+//! The format is the magic `EZRP`, the format version (u16), the flags (u8, bit 0 for zstd) and the body, possibly
+//! compressed: the bindings version (u32), the action names and the changed actions of each frame, with the frames
+//! delta encoded. Integers are little endian, and variable length where marked.
+use std::fmt::Display;
+use std::io::{Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::prelude::*;
+
+/// The magic bytes starting every replay file.
+pub const REPLAY_MAGIC: [u8; 4] = *b"EZRP";
+
+/// The current version of the replay format.
+pub const REPLAY_FORMAT_VERSION: u16 = 1;
+
+/// The flag set when the body is compressed with zstd.
+const FLAG_ZSTD: u8 = 1;
+
+/// An error when reading or writing a replay.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    /// The file isn't a replay.
+    BadMagic,
+    /// The replay was written by a newer version of the format.
+    UnsupportedFormat(u16),
+    /// The replay is compressed, but the `replay_zstd` feature is disabled.
+    CompressionUnsupported,
+    /// The replay ends in the middle of a frame.
+    Truncated,
+    /// A frame was recorded before the last recorded one.
+    FrameOutOfOrder { frame: u64, last_frame: u64 },
+    /// An action can't be named, see [`action_name`].
+    ActionName(String),
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "replay i/o error: {}", error),
+            ReplayError::BadMagic => write!(f, "the file isn't a replay"),
+            ReplayError::UnsupportedFormat(version) => {
+                write!(f, "unsupported replay format version {}", version)
+            }
+            ReplayError::CompressionUnsupported => {
+                write!(f, "the replay is compressed, enable the `replay_zstd` feature to read it")
+            }
+            ReplayError::Truncated => write!(f, "the replay is truncated"),
+            ReplayError::FrameOutOfOrder { frame, last_frame } => {
+                write!(f, "frame {} recorded after frame {}", frame, last_frame)
+            }
+            ReplayError::ActionName(error) => write!(f, "the action can't be named: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<std::io::Error> for ReplayError {
+    fn from(error: std::io::Error) -> Self {
+        ReplayError::Io(error)
+    }
+}
+
+/// The action states changed in a frame.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ReplayFrame<Keys> {
+    pub frame: u64,
+    pub changes: Vec<(Keys, ActionSnapshot)>,
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, ReplayError> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = bytes.split_first().ok_or(ReplayError::Truncated)?;
+        *bytes = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(ReplayError::Truncated)
+}
+
+fn read_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], ReplayError> {
+    if bytes.len() < len {
+        return Err(ReplayError::Truncated);
+    }
+    let (read, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(read)
+}
+
+/// Records the changed action states of each frame into a replay.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ReplayWriter<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The version of the bindings the replay is recorded with, see [`InputBindings::version`].
+    pub version: u32,
+    /// The recorded actions, in the order of their index in the body.
+    pub actions: Vec<Keys>,
+    pub compress: bool,
+    frames: Vec<u8>,
+    last_frame: u64,
+}
+
+impl<Keys> ReplayWriter<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            actions: Vec::new(),
+            compress: false,
+            frames: Vec::new(),
+            last_frame: 0,
+        }
+    }
+
+    /// Compress the replay with zstd.
+    #[cfg(feature = "replay_zstd")]
+    pub fn compressed(mut self) -> Self {
+        self.compress = true;
+        self
+    }
+
+    /// Record the action states changed in a frame. Frames must be recorded in order, a frame before the last
+    /// recorded one is rejected; frames without changes are skipped.
+    pub fn record(&mut self, frame: u64, changes: &[(Keys, ActionSnapshot)]) -> Result<(), ReplayError> {
+        if frame < self.last_frame {
+            return Err(ReplayError::FrameOutOfOrder {
+                frame,
+                last_frame: self.last_frame,
+            });
+        }
+        if changes.is_empty() {
+            return Ok(());
+        }
+        write_varint(&mut self.frames, frame - self.last_frame);
+        write_varint(&mut self.frames, changes.len() as u64);
+        for (action, snapshot) in changes {
+            let index = match self.actions.iter().position(|other| other == action) {
+                Some(index) => index,
+                None => {
+                    self.actions.push(*action);
+                    self.actions.len() - 1
+                }
+            };
+            write_varint(&mut self.frames, index as u64);
+            self.frames.push(snapshot.pressed as u8);
+            self.frames.extend(snapshot.value.to_le_bytes());
+        }
+        self.last_frame = frame;
+        Ok(())
+    }
+
+    /// Record the actions of an input view changed in a frame, usually the current [`InputFrame`].
+    pub fn record_view(&mut self, frame: u64, view: &InputView<Keys>) -> Result<(), ReplayError> {
+        let changes: Vec<(Keys, ActionSnapshot)> = view
+            .changed_actions()
+            .map(|action| (*action, view.snapshot(action)))
+            .collect();
+        self.record(frame, &changes)
+    }
+}
+
+impl<Keys> ReplayWriter<Keys>
+where
+    Keys: BindingTypeView + Serialize,
+{
+    /// Write the replay, with the actions named as read back by [`ActionAliases::action`].
+    pub fn write<W>(&self, mut writer: W) -> Result<(), ReplayError>
+    where
+        W: Write,
+    {
+        let mut body = Vec::with_capacity(self.frames.len() + 64);
+        body.extend(self.version.to_le_bytes());
+        write_varint(&mut body, self.actions.len() as u64);
+        for action in self.actions.iter() {
+            let name = action_name(action).map_err(|error| ReplayError::ActionName(error.to_string()))?;
+            write_varint(&mut body, name.len() as u64);
+            body.extend(name.as_bytes());
+        }
+        body.extend(self.frames.iter());
+        writer.write_all(&REPLAY_MAGIC)?;
+        writer.write_all(&REPLAY_FORMAT_VERSION.to_le_bytes())?;
+        if self.compress {
+            writer.write_all(&[FLAG_ZSTD])?;
+            writer.write_all(&compress(&body)?)?;
+        } else {
+            writer.write_all(&[0])?;
+            writer.write_all(&body)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "replay_zstd")]
+fn compress(body: &[u8]) -> Result<Vec<u8>, ReplayError> {
+    Ok(zstd::encode_all(body, 0)?)
+}
+
+#[cfg(not(feature = "replay_zstd"))]
+fn compress(_: &[u8]) -> Result<Vec<u8>, ReplayError> {
+    Err(ReplayError::CompressionUnsupported)
+}
+
+#[cfg(feature = "replay_zstd")]
+fn decompress(body: &[u8]) -> Result<Vec<u8>, ReplayError> {
+    Ok(zstd::decode_all(body)?)
+}
+
+#[cfg(not(feature = "replay_zstd"))]
+fn decompress(_: &[u8]) -> Result<Vec<u8>, ReplayError> {
+    Err(ReplayError::CompressionUnsupported)
+}
+
+/// A replay read from a file.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ReplayReader<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub format_version: u16,
+    /// The version of the bindings the replay was recorded with.
+    pub version: u32,
+    pub frames: Vec<ReplayFrame<Keys>>,
+    /// The names of the recorded actions that don't exist anymore. Their changes are skipped.
+    pub unknown_actions: Vec<String>,
+}
+
+impl<Keys> ReplayReader<Keys>
+where
+    Keys: BindingTypeView + DeserializeOwned,
+{
+    /// Read a replay, resolving the renamed actions through the given aliases.
+    pub fn read<R>(mut reader: R, aliases: &ActionAliases) -> Result<Self, ReplayError>
+    where
+        R: Read,
+    {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let mut bytes = data.as_slice();
+        if read_bytes(&mut bytes, 4).map_err(|_| ReplayError::BadMagic)? != REPLAY_MAGIC {
+            return Err(ReplayError::BadMagic);
+        }
+        let format = read_bytes(&mut bytes, 2)?;
+        let format_version = u16::from_le_bytes([format[0], format[1]]);
+        if format_version > REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedFormat(format_version));
+        }
+        let flags = read_bytes(&mut bytes, 1)?[0];
+        let body = if flags & FLAG_ZSTD != 0 {
+            decompress(bytes)?
+        } else {
+            bytes.to_vec()
+        };
+        let mut bytes = body.as_slice();
+        let version = read_bytes(&mut bytes, 4)?;
+        let version = u32::from_le_bytes([version[0], version[1], version[2], version[3]]);
+        let mut actions: Vec<Option<Keys>> = Vec::new();
+        let mut unknown_actions = Vec::new();
+        for _ in 0..read_varint(&mut bytes)? {
+            let len = read_varint(&mut bytes)? as usize;
+            let name = String::from_utf8_lossy(read_bytes(&mut bytes, len)?).into_owned();
+            match aliases.action::<Keys>(&name) {
+                Ok(action) => actions.push(Some(action)),
+                Err(_) => {
+                    actions.push(None);
+                    unknown_actions.push(name);
+                }
+            }
+        }
+        let mut frames = Vec::new();
+        let mut frame = 0;
+        while !bytes.is_empty() {
+            frame += read_varint(&mut bytes)?;
+            let mut changes = Vec::new();
+            for _ in 0..read_varint(&mut bytes)? {
+                let index = read_varint(&mut bytes)? as usize;
+                let entry = read_bytes(&mut bytes, 5)?;
+                let snapshot = ActionSnapshot {
+                    pressed: entry[0] != 0,
+                    value: f32::from_le_bytes([entry[1], entry[2], entry[3], entry[4]]),
+                };
+                if let Some(Some(action)) = actions.get(index) {
+                    changes.push((*action, snapshot));
+                }
+            }
+            frames.push(ReplayFrame { frame, changes });
+        }
+        Ok(Self {
+            format_version,
+            version,
+            frames,
+            unknown_actions,
+        })
+    }
+
+    /// Returns the changes recorded for a frame.
+    pub fn frame(&self, frame: u64) -> &[(Keys, ActionSnapshot)] {
+        self.frames
+            .binary_search_by_key(&frame, |recorded| recorded.frame)
+            .map_or(&[], |index| self.frames[index].changes.as_slice())
+    }
+
+    /// Apply the changes recorded for a frame to an input view.
+    pub fn apply(&self, frame: u64, view: &mut InputView<Keys>) {
+        for (action, snapshot) in self.frame(frame) {
+            view.override_action(*action, *snapshot);
+        }
+    }
+}
+
+// Test to check that a replay survives a write and read round trip, with renamed actions resolved by their alias.
+#[test]
+fn replay_round_trip_test() {
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    enum Movement {
+        Jump,
+        #[serde(rename = "Crouch")]
+        Duck,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    enum Old {
+        Jump,
+        Shoot,
+        Movement(Movement),
+    }
+    impl BindingTypeView for Old {}
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    enum New {
+        Jump,
+        Fire,
+        Movement(Movement),
+    }
+    impl BindingTypeView for New {}
+
+    let pressed = ActionSnapshot {
+        pressed: true,
+        value: 1.,
+    };
+    let mut writer = ReplayWriter::new(1);
+    writer.record(3, &[(Old::Jump, pressed)]).unwrap();
+    writer
+        .record(200, &[(Old::Shoot, pressed), (Old::Jump, ActionSnapshot::default())])
+        .unwrap();
+    writer.record(201, &[(Old::Movement(Movement::Duck), pressed)]).unwrap();
+    assert!(writer.record(5, &[(Old::Jump, pressed)]).is_err());
+    let mut file = Vec::new();
+    writer.write(&mut file).unwrap();
+
+    let aliases = ActionAliases::new().alias("Shoot", "Fire");
+    let replay = ReplayReader::<New>::read(file.as_slice(), &aliases).unwrap();
+    assert_eq!(replay.version, 1);
+    assert_eq!(replay.frame(3), &[(New::Jump, pressed)]);
+    assert_eq!(replay.frame(200)[0], (New::Fire, pressed));
+    assert_eq!(replay.frame(201), &[(New::Movement(Movement::Duck), pressed)]);
+    assert!(replay.unknown_actions.is_empty());
+    assert!(replay.frame(4).is_empty());
+}
@@ -0,0 +1,178 @@
+//! Network replication helper, encoding only the action states changed in a tick into a compact payload and
+//! applying it to the input view of a remote player.
+use std::fmt::Display;
+
+use bevy::utils::Instant;
+
+use crate::prelude::*;
+
+/// Size of an encoded action state: the action index (u16), the pressed flag (u8) and the value (f32).
+const ENTRY_SIZE: usize = 7;
+
+/// An error when applying a replicated payload.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ReplicationError {
+    /// The payload is shorter than its header says.
+    Truncated,
+    /// The payload references an action index not known by the [`ReplicatedInput`].
+    UnknownAction(u16),
+}
+
+impl Display for ReplicationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplicationError::Truncated => write!(f, "the replicated payload is truncated"),
+            ReplicationError::UnknownAction(index) => {
+                write!(
+                    f,
+                    "the replicated payload references unknown action {}",
+                    index
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplicationError {}
+
+/// Encodes and applies action states. Both sides need the same list of actions, since actions are sent by index.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ReplicatedInput<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub actions: Vec<Keys>,
+}
+
+impl<Keys> ReplicatedInput<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub fn new(actions: Vec<Keys>) -> Self {
+        Self { actions }
+    }
+
+    /// Encode the states of the actions changed in the current tick. Returns none if nothing changed.
+    pub fn encode(&self, view: &InputView<Keys>) -> Option<Vec<u8>> {
+        let entries: Vec<(u16, ActionSnapshot)> = self
+            .actions
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| view.has_changed(action))
+            .map(|(index, action)| (index as u16, view.snapshot(action)))
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+        let mut payload = Vec::with_capacity(2 + entries.len() * ENTRY_SIZE);
+        payload.extend((entries.len() as u16).to_le_bytes());
+        for (index, snapshot) in entries {
+            payload.extend(index.to_le_bytes());
+            payload.push(snapshot.pressed as u8);
+            payload.extend(snapshot.value.to_le_bytes());
+        }
+        Some(payload)
+    }
+
+    /// Decode a payload into the replicated action states.
+    pub fn decode(&self, payload: &[u8]) -> Result<Vec<(Keys, ActionSnapshot)>, ReplicationError> {
+        let count = match payload {
+            [a, b, ..] => u16::from_le_bytes([*a, *b]) as usize,
+            _ => return Err(ReplicationError::Truncated),
+        };
+        let entries = &payload[2..];
+        if entries.len() < count * ENTRY_SIZE {
+            return Err(ReplicationError::Truncated);
+        }
+        entries
+            .chunks_exact(ENTRY_SIZE)
+            .take(count)
+            .map(|entry| {
+                let index = u16::from_le_bytes([entry[0], entry[1]]);
+                let action = self
+                    .actions
+                    .get(index as usize)
+                    .ok_or(ReplicationError::UnknownAction(index))?;
+                let snapshot = ActionSnapshot {
+                    pressed: entry[2] != 0,
+                    value: f32::from_le_bytes([entry[3], entry[4], entry[5], entry[6]]),
+                };
+                Ok((*action, snapshot))
+            })
+            .collect()
+    }
+
+    /// Apply a payload to the input view of a remote player, overriding the state of the replicated actions.
+    pub fn apply(
+        &self,
+        view: &mut InputView<Keys>,
+        payload: &[u8],
+    ) -> Result<(), ReplicationError> {
+        for (action, snapshot) in self.decode(payload)? {
+            view.override_action(action, snapshot);
+        }
+        Ok(())
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Override the state of an action regardless of its receivers, keeping the pressing instant if the action is
+    /// still pressed. Releasing the action removes the override.
+    pub fn override_action(&mut self, kind: Keys, snapshot: ActionSnapshot) {
+        if !snapshot.pressed {
+            self.overrides.remove(&kind);
+            return;
+        }
+        let press = match self.overrides.get(&kind) {
+            Some(state) if state.press.pressed() => state.press,
+            _ => PressState::Pressed {
+                started_pressing_instant: Some(Instant::now()),
+            },
+        };
+        self.overrides
+            .insert(kind, AxisState::new(snapshot.value, press));
+    }
+}
+
+// Test to check that changed action states survive an encoding round trip.
+#[test]
+fn replicated_input_round_trip_test() {
+    use bevy::prelude::KeyCode;
+    use ezinput_macros::BindingTypeView;
+
+    #[derive(BindingTypeView, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Jump,
+        Crouch,
+    }
+
+    let replicated = ReplicatedInput::new(vec![Action::Jump, Action::Crouch]);
+    let mut local = InputView::new();
+    local.add_binding(
+        ActionBinding::from(Action::Jump).receivers(KeyboardKey(KeyCode::Space).into()),
+    );
+    local
+        .add_binding(ActionBinding::from(Action::Crouch).receivers(KeyboardKey(KeyCode::C).into()));
+    local.set_key_receiver_state(
+        KeyboardKey(KeyCode::Space),
+        PressState::Pressed {
+            started_pressing_instant: None,
+        },
+    );
+    let snapshots = local.changed_snapshots();
+    local.apply_snapshots(snapshots);
+
+    let payload = replicated.encode(&local).unwrap();
+    assert_eq!(payload.len(), 2 + ENTRY_SIZE);
+    let mut remote = InputView::new();
+    replicated.apply(&mut remote, &payload).unwrap();
+    assert!(remote.key(&Action::Jump).pressed());
+    assert!(remote.key(&Action::Crouch).released());
+    assert_eq!(
+        replicated.apply(&mut remote, &payload[..4]),
+        Err(ReplicationError::Truncated)
+    );
+}
@@ -0,0 +1,266 @@
+//! Reports of the accumulated [`InputStats`] for UX research, e.g. to draw a heat map of the used keys. Reports are
+//! written as CSV, or as JSON with the `json_bindings` feature, when an [`ExportInputStats`] event is sent (e.g. by a
+//! debug command) or when the application exits, through the [`InputStatsReportPlugin`].
+use std::fmt::{Display, Write as _};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use bevy::app::AppExit;
+use bevy::ecs::event::Events;
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct InputStatsReportSystem;
+
+/// The file format of a statistics report.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum StatsReportFormat {
+    /// A row per action and per receiver: `entity,kind,name,presses,held_secs,axis_histogram`, with the histogram
+    /// buckets separated by `;`.
+    Csv,
+    /// Requires the `json_bindings` feature.
+    Json,
+}
+
+/// The statistics of an action in a report.
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct ActionStatsReport {
+    pub name: String,
+    pub presses: u32,
+    /// The time the action was held in total, in seconds.
+    pub held: f32,
+    pub axis_histogram: Vec<u32>,
+}
+
+/// The statistics of an input view in a report.
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct ViewStatsReport {
+    /// The index of the entity of the input view.
+    pub entity: u32,
+    /// The time the statistics were collected for, in seconds.
+    pub elapsed: f32,
+    pub actions_per_minute: f32,
+    pub actions: Vec<ActionStatsReport>,
+    /// The presses of each receiver, by name.
+    pub receivers: Vec<(String, u32)>,
+}
+
+/// A report of the statistics of every input view with an [`InputStats`] component.
+#[derive(PartialEq, Clone, Debug, Default, Serialize)]
+pub struct InputStatsReport {
+    pub views: Vec<ViewStatsReport>,
+}
+
+/// An error when writing a statistics report.
+#[derive(Debug)]
+pub enum StatsReportError {
+    Io(std::io::Error),
+    /// JSON reports require the `json_bindings` feature.
+    JsonUnsupported,
+}
+
+impl Display for StatsReportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatsReportError::Io(error) => write!(f, "input statistics report i/o error: {}", error),
+            StatsReportError::JsonUnsupported => {
+                write!(f, "JSON input statistics reports require the `json_bindings` feature")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StatsReportError {}
+
+impl From<std::io::Error> for StatsReportError {
+    fn from(error: std::io::Error) -> Self {
+        StatsReportError::Io(error)
+    }
+}
+
+impl InputStatsReport {
+    /// Add the statistics of an input view to the report. Actions and receivers are sorted by name, so reports can be
+    /// compared.
+    pub fn add<Keys>(&mut self, entity: Entity, stats: &InputStats<Keys>)
+    where
+        Keys: BindingTypeView,
+    {
+        let mut actions: Vec<ActionStatsReport> = stats
+            .presses
+            .keys()
+            .chain(stats.held.keys())
+            .chain(stats.axis_histograms.keys())
+            .map(|action| ActionStatsReport {
+                name: format!("{:?}", action),
+                presses: stats.press_count(action),
+                held: stats.held_for(action).as_secs_f32(),
+                axis_histogram: stats.axis_histograms.get(action).cloned().unwrap_or_default(),
+            })
+            .collect();
+        actions.sort_by(|a, b| a.name.cmp(&b.name));
+        actions.dedup_by(|a, b| a.name == b.name);
+        let mut receivers: Vec<(String, u32)> = stats
+            .receiver_presses
+            .iter()
+            .map(|(receiver, presses)| (format!("{:?}", receiver), *presses))
+            .collect();
+        receivers.sort();
+        self.views.push(ViewStatsReport {
+            entity: entity.id(),
+            elapsed: stats.elapsed.as_secs_f32(),
+            actions_per_minute: stats.actions_per_minute(),
+            actions,
+            receivers,
+        });
+    }
+
+    /// Returns the report as CSV.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("entity,kind,name,presses,held_secs,axis_histogram\n");
+        for view in self.views.iter() {
+            for action in view.actions.iter() {
+                let histogram: Vec<String> = action.axis_histogram.iter().map(u32::to_string).collect();
+                let _ = writeln!(
+                    csv,
+                    "{},action,{},{},{},{}",
+                    view.entity,
+                    csv_field(&action.name),
+                    action.presses,
+                    action.held,
+                    histogram.join(";")
+                );
+            }
+            for (receiver, presses) in view.receivers.iter() {
+                let _ = writeln!(csv, "{},receiver,{},{},,", view.entity, csv_field(receiver), presses);
+            }
+        }
+        csv
+    }
+
+    /// Returns the report as pretty-printed JSON.
+    #[cfg(feature = "json_bindings")]
+    pub fn to_json(&self) -> Result<String, StatsReportError> {
+        serde_json::to_string_pretty(self).map_err(|error| StatsReportError::Io(error.into()))
+    }
+
+    /// Returns the report as pretty-printed JSON.
+    #[cfg(not(feature = "json_bindings"))]
+    pub fn to_json(&self) -> Result<String, StatsReportError> {
+        Err(StatsReportError::JsonUnsupported)
+    }
+
+    /// Write the report to a file in the given format.
+    pub fn write(&self, path: &std::path::Path, format: StatsReportFormat) -> Result<(), StatsReportError> {
+        let contents = match format {
+            StatsReportFormat::Csv => self.to_csv(),
+            StatsReportFormat::Json => self.to_json()?,
+        };
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Quote a CSV field if needed.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Event requesting a statistics report to be written to a file.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ExportInputStats {
+    pub path: PathBuf,
+    pub format: StatsReportFormat,
+}
+
+/// A [`Plugin`] writing the statistics reports requested with [`ExportInputStats`] events, and optionally a report
+/// when the application exits.
+pub struct InputStatsReportPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The report written when the application exits, if any.
+    pub on_exit: Option<ExportInputStats>,
+    phantom: PhantomData<Keys>,
+}
+
+impl<Keys> Default for InputStatsReportPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            on_exit: None,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Keys> InputStatsReportPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Write a report to the given file when the application exits.
+    pub fn on_exit(path: impl Into<PathBuf>, format: StatsReportFormat) -> Self {
+        Self {
+            on_exit: Some(ExportInputStats {
+                path: path.into(),
+                format,
+            }),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// The report written when the application exits, if any.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StatsReportOnExit(pub Option<ExportInputStats>);
+
+impl<Keys> Plugin for InputStatsReportPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<Events<ExportInputStats>>() {
+            app.add_event::<ExportInputStats>();
+        }
+        app.insert_resource(StatsReportOnExit(self.on_exit.clone()))
+            .add_system_to_stage(
+                CoreStage::Last,
+                input_stats_report_system::<Keys>.label(InputStatsReportSystem),
+            );
+    }
+}
+
+/// System responsible for writing the requested statistics reports.
+pub(crate) fn input_stats_report_system<Keys>(
+    query: Query<(Entity, &InputStats<Keys>)>,
+    mut requests: EventReader<ExportInputStats>,
+    mut exit: EventReader<AppExit>,
+    on_exit: Res<StatsReportOnExit>,
+) where
+    Keys: BindingTypeView,
+{
+    let mut exports: Vec<ExportInputStats> = requests.iter().cloned().collect();
+    if exit.iter().next().is_some() {
+        exports.extend(on_exit.0.clone());
+    }
+    if exports.is_empty() {
+        return;
+    }
+    let mut report = InputStatsReport::default();
+    for (entity, stats) in query.iter() {
+        report.add(entity, stats);
+    }
+    for export in exports {
+        if let Err(err) = report.write(&export.path, export.format) {
+            warn!("Unable to write the input statistics report to {:?}: {}", export.path, err);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Rumble feedback requests. Bevy doesn't drive rumble motors, so requests are sent as [`GamepadRumbleRequest`]
+//! events to be played by the gamepad backend of the application. Named [`RumblePattern`]s can be written with a
+//! small DSL and played with [`GamepadMarker::play_pattern`].
+use std::fmt::Display;
+
+use bevy::prelude::*;
+use bevy::utils::{Duration, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct TriggerRumbleSystem;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct RumblePatternSystem;
+
+/// Event requesting a rumble on a gamepad.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GamepadRumbleRequest {
+    pub gamepad: Gamepad,
+    /// Intensity of the low-frequency motor, from 0 to 1.
+    pub strong: f32,
+    /// Intensity of the high-frequency motor, from 0 to 1.
+    pub weak: f32,
+    pub duration: Duration,
+}
+
+/// A rumble pulse played when an analog button crosses its actuation threshold, mimicking adaptive triggers on
+/// normal pads.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct TriggerRumble {
+    pub threshold: f32,
+    pub intensity: f32,
+    pub duration: Duration,
+}
+
+impl Default for TriggerRumble {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            intensity: 0.3,
+            duration: Duration::from_millis(40),
+        }
+    }
+}
+
+/// A segment of a rumble pattern, with a constant intensity for each motor.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct RumbleSegment {
+    pub strong: f32,
+    pub weak: f32,
+    pub duration: Duration,
+}
+
+/// An error found while parsing a rumble pattern.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RumblePatternError(pub String);
+
+impl Display for RumblePatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid rumble segment \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for RumblePatternError {}
+
+/// A sequence of rumble segments, played one after the other.
+///
+/// Patterns can be written as comma-separated segments of the form `strong/weak@milliseconds`, where the weak
+/// intensity defaults to the strong one, or `from..to@milliseconds` for a ramp of both motors. For example
+/// `1/0.5@80, 0@120, 0.6@80` or `0..1@500`.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct RumblePattern {
+    pub segments: Vec<RumbleSegment>,
+}
+
+impl RumblePattern {
+    /// The number of segments a ramp is split into.
+    const RAMP_STEPS: u32 = 10;
+
+    /// Parse a pattern written with the pattern DSL.
+    pub fn parse(pattern: &str) -> Result<Self, RumblePatternError> {
+        let mut segments = Vec::new();
+        for segment in pattern.split(',').map(str::trim).filter(|segment| !segment.is_empty()) {
+            let error = || RumblePatternError(segment.to_string());
+            let (intensity, millis) = segment.split_once('@').ok_or_else(error)?;
+            let duration = Duration::from_millis(millis.trim().parse().map_err(|_| error())?);
+            let parse = |value: &str| value.trim().parse::<f32>().map_err(|_| error());
+            if let Some((from, to)) = intensity.split_once("..") {
+                let (from, to) = (parse(from)?, parse(to)?);
+                for step in 0..Self::RAMP_STEPS {
+                    let value = from + (to - from) * step as f32 / (Self::RAMP_STEPS - 1) as f32;
+                    segments.push(RumbleSegment {
+                        strong: value,
+                        weak: value,
+                        duration: duration / Self::RAMP_STEPS,
+                    });
+                }
+            } else {
+                let (strong, weak) = match intensity.split_once('/') {
+                    Some((strong, weak)) => (parse(strong)?, parse(weak)?),
+                    None => (parse(intensity)?, parse(intensity)?),
+                };
+                segments.push(RumbleSegment {
+                    strong,
+                    weak,
+                    duration,
+                });
+            }
+        }
+        Ok(Self { segments })
+    }
+
+    /// Returns one of the built-in patterns: `pulse`, `heartbeat`, `ramp` or `explosion`.
+    pub fn named(name: &str) -> Option<Self> {
+        let pattern = match name {
+            "pulse" => "0.6@100",
+            "heartbeat" => "0.8/0.2@90, 0@110, 0.5/0.1@90, 0@600",
+            "ramp" => "0..1@600",
+            "explosion" => "1@150, 1..0@850",
+            _ => return None,
+        };
+        Self::parse(pattern).ok()
+    }
+
+    /// Returns the total duration of the pattern.
+    pub fn duration(&self) -> Duration {
+        self.segments.iter().map(|segment| segment.duration).sum()
+    }
+}
+
+/// A rumble pattern being played, and the index of the next segment to request.
+#[derive(PartialEq, Clone, Debug)]
+pub struct PlayingRumble {
+    pub pattern: RumblePattern,
+    pub started: Instant,
+    pub next: usize,
+}
+
+impl PlayingRumble {
+    /// Returns the instant in which each segment starts.
+    fn starts(&self) -> impl Iterator<Item = (usize, Instant)> + '_ {
+        let mut start = self.started;
+        self.pattern.segments.iter().enumerate().map(move |(index, segment)| {
+            let current = start;
+            start += segment.duration;
+            (index, current)
+        })
+    }
+
+    /// Returns whether a segment not requested yet has started.
+    pub fn due(&self, now: Instant) -> bool {
+        self.starts().any(|(index, start)| index >= self.next && start <= now)
+    }
+
+    /// Returns whether every segment is over.
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.started + self.pattern.duration() <= now
+    }
+
+    /// Returns the latest started segment not requested yet, shortened by the time elapsed since its start. The
+    /// segments skipped in between, e.g. during a long frame, are never requested.
+    pub fn advance(&mut self, now: Instant) -> Option<RumbleSegment> {
+        let (index, start) = self
+            .starts()
+            .filter(|(index, start)| *index >= self.next && *start <= now)
+            .last()?;
+        self.next = index + 1;
+        let segment = self.pattern.segments[index];
+        Some(RumbleSegment {
+            duration: segment.duration.saturating_sub(now - start),
+            ..segment
+        })
+    }
+}
+
+impl GamepadMarker {
+    /// Play one of the built-in rumble patterns (see [`RumblePattern::named`]). Returns false if the pattern
+    /// doesn't exist.
+    pub fn play_pattern(&mut self, name: &str) -> bool {
+        match RumblePattern::named(name) {
+            Some(pattern) => {
+                self.play_rumble(pattern);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Play a rumble pattern, replacing the pattern being played.
+    pub fn play_rumble(&mut self, pattern: RumblePattern) {
+        self.rumble = Some(PlayingRumble {
+            pattern,
+            started: Instant::now(),
+            next: 0,
+        });
+    }
+
+    /// Stop the rumble pattern being played.
+    pub fn stop_rumble(&mut self) {
+        self.rumble = None;
+    }
+
+    /// Enable or disable the rumble pulse played when the given button crosses the actuation threshold.
+    pub fn set_trigger_rumble(&mut self, button: GamepadButtonType, rumble: Option<TriggerRumble>) {
+        match rumble {
+            Some(rumble) => self.trigger_rumble.insert(button, rumble),
+            None => self.trigger_rumble.remove(&button),
+        };
+    }
+}
+
+/// System responsible for sending a rumble request when a button configured with a [`TriggerRumble`] crosses its
+/// actuation threshold since the last frame.
+pub(crate) fn trigger_rumble_system<Keys>(
+    query: Query<(&InputView<Keys>, &GamepadMarker)>,
+    mut wr: EventWriter<GamepadRumbleRequest>,
+) where
+    Keys: BindingTypeView,
+{
+    for (view, svc) in query.iter() {
+        if !svc.connected {
+            continue;
+        }
+        for (button, rumble) in svc.trigger_rumble.iter() {
+            let descriptor = match view.descriptor(&InputReceiver::GamepadButton(*button)) {
+                Some(descriptor) => descriptor,
+                None => continue,
+            };
+            if descriptor.previous_value < rumble.threshold
+                && descriptor.axis.value >= rumble.threshold
+            {
+                wr.send(GamepadRumbleRequest {
+                    gamepad: svc.gamepad,
+                    strong: 0.,
+                    weak: rumble.intensity,
+                    duration: rumble.duration,
+                });
+            }
+        }
+    }
+}
+
+/// System responsible for requesting the segments of the rumble patterns being played once their start time is
+/// reached.
+pub(crate) fn rumble_pattern_system<Keys>(
+    mut query: Query<&mut GamepadMarker, With<InputView<Keys>>>,
+    mut wr: EventWriter<GamepadRumbleRequest>,
+) where
+    Keys: BindingTypeView,
+{
+    let now = Instant::now();
+    for mut svc in query.iter_mut() {
+        let due = |playing: &PlayingRumble| playing.due(now) || playing.is_finished(now);
+        if !svc.rumble.as_ref().is_some_and(due) {
+            continue;
+        }
+        let (gamepad, connected) = (svc.gamepad, svc.connected);
+        let playing = match svc.rumble.as_mut() {
+            Some(playing) => playing,
+            None => continue,
+        };
+        let finished = playing.is_finished(now);
+        if let Some(segment) = playing.advance(now).filter(|_| connected) {
+            wr.send(GamepadRumbleRequest {
+                gamepad,
+                strong: segment.strong,
+                weak: segment.weak,
+                duration: segment.duration,
+            });
+        }
+        if finished {
+            svc.rumble = None;
+        }
+    }
+}
+
+// Test to check that the pattern DSL parses plain segments and ramps.
+#[test]
+fn rumble_pattern_parse_test() {
+    let pattern = RumblePattern::parse("1/0.5@80, 0@120, 0..1@500").unwrap();
+    assert_eq!(pattern.segments.len(), 12);
+    assert_eq!(
+        pattern.segments[0],
+        RumbleSegment {
+            strong: 1.,
+            weak: 0.5,
+            duration: Duration::from_millis(80)
+        }
+    );
+    assert_eq!(pattern.segments[1].weak, 0.);
+    assert_eq!(pattern.segments[11].strong, 1.);
+    assert_eq!(pattern.duration(), Duration::from_millis(700));
+    assert!(RumblePattern::parse("1@fast").is_err());
+    assert!(RumblePattern::named("heartbeat").is_some());
+}
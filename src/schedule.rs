@@ -0,0 +1,134 @@
+//! Press edges tracked per schedule, so gameplay running in a fixed timestep stage sees every press exactly once,
+//! whatever the rate of the input collection.
+use std::ops::DerefMut;
+
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct ScheduleEdgeSystem;
+
+/// The press edges of a schedule: the actions pressed since its last step, and the actions pressed before the
+/// current step.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ScheduleEdges<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub pending: HashSet<Keys>,
+    pub just_pressed: HashSet<Keys>,
+}
+
+impl<Keys> Default for ScheduleEdges<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            pending: HashSet::default(),
+            just_pressed: HashSet::default(),
+        }
+    }
+}
+
+impl<Keys> ScheduleEdges<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns whether a step would change the edges.
+    pub fn needs_step(&self) -> bool {
+        !self.pending.is_empty() || !self.just_pressed.is_empty()
+    }
+
+    /// Start a new step, making the actions pressed since the last step the just pressed ones.
+    pub fn step(&mut self) {
+        self.just_pressed = std::mem::take(&mut self.pending);
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Start tracking the press edges for the given schedule. The [`schedule_edge_system`] for the schedule needs
+    /// to run at the start of each of its steps.
+    pub fn track_schedule(&mut self, schedule: impl Into<String>) {
+        self.schedule_edges.entry(schedule.into()).or_default();
+    }
+
+    /// Stop tracking the press edges for the given schedule.
+    pub fn untrack_schedule(&mut self, schedule: &str) {
+        self.schedule_edges.remove(schedule);
+    }
+
+    /// Returns whether the action was pressed since the previous step of the given schedule. Unlike
+    /// [`PressStateExt::just_pressed`], this is true exactly once per press, in the first step following it, even
+    /// if the schedule runs several times or not at all in a frame.
+    pub fn just_pressed_in(&self, schedule: &str, kind: &Keys) -> bool {
+        self.schedule_edges
+            .get(schedule)
+            .is_some_and(|edges| edges.just_pressed.contains(kind))
+    }
+
+    /// Record a press edge of an action for every tracked schedule.
+    pub(crate) fn record_press_edge(&mut self, kind: Keys) {
+        for edges in self.schedule_edges.values_mut() {
+            edges.pending.insert(kind);
+        }
+    }
+}
+
+/// Returns the system starting a step of the given schedule for every input view, including the global one. Add it
+/// at the start of the schedule (e.g. the stage running with a fixed timestep), labelled with
+/// [`ScheduleEdgeSystem`], and order the gameplay systems after it.
+#[allow(clippy::type_complexity)]
+pub fn schedule_edge_system<Keys>(
+    schedule: &'static str,
+) -> impl FnMut(Query<&mut InputView<Keys>>, Option<ResMut<InputView<Keys>>>)
+where
+    Keys: BindingTypeView,
+{
+    move |mut query: Query<&mut InputView<Keys>>, global: Option<ResMut<InputView<Keys>>>| {
+        for view in query.iter_mut() {
+            step_schedule(view, schedule);
+        }
+        if let Some(view) = global {
+            step_schedule(view, schedule);
+        }
+    }
+}
+
+/// Start a step of the given schedule, only mutating the view when the edges change.
+fn step_schedule<Keys>(mut view: impl DerefMut<Target = InputView<Keys>>, schedule: &str)
+where
+    Keys: BindingTypeView,
+{
+    if view
+        .schedule_edges
+        .get(schedule)
+        .is_some_and(|edges| edges.needs_step())
+    {
+        if let Some(edges) = view.schedule_edges.get_mut(schedule) {
+            edges.step();
+        }
+    }
+}
+
+// Test to check that a press is seen in exactly one step of a schedule.
+#[test]
+fn schedule_edges_test() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let mut edges = ScheduleEdges::<Jump>::default();
+    edges.pending.insert(Jump);
+    edges.step();
+    assert!(edges.just_pressed.contains(&Jump));
+    assert!(edges.needs_step());
+    edges.step();
+    assert!(!edges.just_pressed.contains(&Jump));
+    assert!(!edges.needs_step());
+}
@@ -0,0 +1,78 @@
+//! Simultaneous opposite cardinal direction (SOCD) cleaning: when both receivers of an opposing pair (e.g. Left and
+//! Right) are held, a policy decides which one the actions see. Expected by fighting games and leverless controllers.
+use bevy::utils::Instant;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// How a pair of opposing receivers held at the same time is resolved.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub enum SocdPolicy {
+    /// Both receivers are ignored, as if neither was held.
+    #[default]
+    Neutral,
+    /// The receiver pressed last wins. The other one is seen again once the last one is released.
+    LastInputPriority,
+    /// The receiver pressed first wins, ignoring the other one until it's released.
+    FirstInputPriority,
+}
+
+/// A pair of opposing receivers and the policy resolving them.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct SocdPair {
+    pub negative: InputReceiver,
+    pub positive: InputReceiver,
+    pub policy: SocdPolicy,
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Set the policy resolving a pair of opposing receivers held at the same time, or stop resolving them if none.
+    pub fn set_socd(&mut self, negative: InputReceiver, positive: InputReceiver, policy: Option<SocdPolicy>) {
+        self.socd
+            .retain(|pair| !(pair.negative == negative && pair.positive == positive));
+        if let Some(policy) = policy {
+            self.socd.push(SocdPair {
+                negative,
+                positive,
+                policy,
+            });
+        }
+    }
+
+    /// Returns whether a held receiver is ignored by the actions because its opposing receiver is held too.
+    pub fn is_socd_masked(&self, rcv: &InputReceiver) -> bool {
+        if self.socd.is_empty() {
+            return false;
+        }
+        // Receivers pressed this frame don't have a pressing instant yet.
+        let now = Instant::now();
+        let pressed_at = |rcv: &InputReceiver| match self.state(rcv).press {
+            PressState::Pressed {
+                started_pressing_instant,
+            } => Some(started_pressing_instant.unwrap_or(now)),
+            PressState::Released => None,
+        };
+        self.socd.iter().any(|pair| {
+            let other = if pair.negative == *rcv {
+                pair.positive
+            } else if pair.positive == *rcv {
+                pair.negative
+            } else {
+                return false;
+            };
+            let (pressed, other_pressed) = match (pressed_at(rcv), pressed_at(&other)) {
+                (Some(pressed), Some(other_pressed)) => (pressed, other_pressed),
+                _ => return false,
+            };
+            // Receivers pressed in the same instant can't be ordered, so they are both ignored.
+            match pair.policy {
+                SocdPolicy::Neutral => true,
+                SocdPolicy::LastInputPriority => pressed <= other_pressed,
+                SocdPolicy::FirstInputPriority => pressed >= other_pressed,
+            }
+        })
+    }
+}
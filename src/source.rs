@@ -0,0 +1,75 @@
+//! Classification of the last used input source. Players often use the keyboard and the mouse together, so the
+//! sources can be combined into [`InputSource::KeyboardMouse`] to stop prompts flickering between keyboard and mouse
+//! glyphs, and switches between sources can be debounced.
+use bevy::utils::{Duration, Instant};
+
+use crate::prelude::*;
+
+/// How the input sources used are classified into the last input source of a view.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct SourceClassification {
+    /// Report the keyboard and the mouse as [`InputSource::KeyboardMouse`].
+    pub combine_keyboard_mouse: bool,
+    /// The time a source needs to stay the last input source before another source replaces it.
+    pub debounce: Duration,
+}
+
+impl SourceClassification {
+    /// Returns the source reported for an input source.
+    pub fn classify(&self, source: InputSource) -> InputSource {
+        match source {
+            InputSource::Keyboard | InputSource::Mouse if self.combine_keyboard_mouse => InputSource::KeyboardMouse,
+            source => source,
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Set the last input source used, classified and debounced as configured with
+    /// [`InputView::set_source_classification`].
+    pub fn set_input_source(&mut self, source: InputSource) {
+        let source = self.source_classification.classify(source);
+        if self.last_input_source == Some(source) {
+            return;
+        }
+        let now = Instant::now();
+        let debounce = self.source_classification.debounce;
+        if self.last_input_source.is_some()
+            && self
+                .source_switched
+                .is_some_and(|switched| now.duration_since(switched) < debounce)
+        {
+            return;
+        }
+        self.last_input_source = Some(source);
+        self.source_switched = Some(now);
+    }
+
+    /// Change how the input sources used are classified.
+    pub fn set_source_classification(&mut self, classification: SourceClassification) {
+        self.source_classification = classification;
+        self.last_input_source = self.last_input_source.map(|source| classification.classify(source));
+    }
+}
+
+// Test to check that keyboard and mouse input are reported as a single source when combined.
+#[test]
+fn keyboard_mouse_source_test() {
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let mut view = InputView::<Jump>::new();
+    view.set_source_classification(SourceClassification {
+        combine_keyboard_mouse: true,
+        debounce: Duration::from_secs(60),
+    });
+    view.set_input_source(InputSource::Keyboard);
+    view.set_input_source(InputSource::Mouse);
+    assert_eq!(view.last_input_source, Some(InputSource::KeyboardMouse));
+    view.set_input_source(InputSource::Gamepad);
+    assert_eq!(view.last_input_source, Some(InputSource::KeyboardMouse));
+}
@@ -0,0 +1,458 @@
+//! Per-source staging buffers, letting the keyboard, mouse, gamepad and touch systems collect input in parallel
+//! before a single system merges it into the [`InputView`].
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::utils::Instant;
+
+use crate::gesture::Direction;
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct StagedInputSystem;
+
+/// Write access to the receivers of an input view. Implemented by [`InputView`] itself and by [`StagedView`], so the
+/// input markers can either write into a view directly or into a staging buffer.
+pub trait InputSink {
+    /// Returns the current state of a receiver with its value as written by the input source (see
+    /// [`InputView::raw_state`]), including the writes not merged yet.
+    fn receiver_state(&self, input: &InputReceiver) -> AxisState;
+
+    /// Set the axis value and press state of a receiver.
+    fn set_axis_value(&mut self, input: InputReceiver, value: f32, press: PressState);
+
+    /// Set the press state of a button receiver, using its default axis value as the value.
+    fn set_key_receiver_state(&mut self, input: InputReceiver, press: PressState);
+
+    /// Release a receiver, keeping its axis value.
+    fn release_receiver(&mut self, input: InputReceiver);
+
+    /// Release every pressed receiver from a specific source.
+    fn release_source(&mut self, source: InputSource);
+
+    /// Set the last input source used.
+    fn set_input_source(&mut self, source: InputSource);
+
+    /// Record the source writing a receiver shared between sources, see [`InputView::receiver_source`].
+    fn set_receiver_source(&mut self, input: InputReceiver, source: InputSource);
+
+    /// Press the swipe receiver for the given direction for a single frame.
+    fn set_swipe(&mut self, direction: Direction, source: InputSource) {
+        self.set_input_source(source);
+        self.set_receiver_source(InputReceiver::Swipe(direction), source);
+        self.set_key_receiver_state(
+            InputReceiver::Swipe(direction),
+            PressState::Pressed {
+                started_pressing_instant: None,
+            },
+        );
+    }
+
+    /// Returns whether a swipe receiver was pressed in a previous frame and needs to be released.
+    fn has_stale_swipes(&self) -> bool {
+        Direction::ALL.iter().any(|direction| {
+            matches!(
+                self.receiver_state(&InputReceiver::Swipe(*direction)).press,
+                PressState::Pressed {
+                    started_pressing_instant: Some(_)
+                }
+            )
+        })
+    }
+
+    /// Release the swipe receivers pressed in a previous frame.
+    fn release_stale_swipes(&mut self) {
+        for direction in Direction::ALL {
+            let receiver = InputReceiver::Swipe(direction);
+            if matches!(
+                self.receiver_state(&receiver).press,
+                PressState::Pressed {
+                    started_pressing_instant: Some(_)
+                }
+            ) {
+                self.set_key_receiver_state(receiver, PressState::Released);
+            }
+        }
+    }
+}
+
+impl<Keys> InputSink for InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn receiver_state(&self, input: &InputReceiver) -> AxisState {
+        self.raw_state(input)
+    }
+
+    fn set_axis_value(&mut self, input: InputReceiver, value: f32, press: PressState) {
+        InputView::set_axis_value(self, input, value, press);
+    }
+
+    fn set_key_receiver_state(&mut self, input: InputReceiver, press: PressState) {
+        InputView::set_key_receiver_state(self, input, press);
+    }
+
+    fn release_receiver(&mut self, input: InputReceiver) {
+        self.descriptor_or_insert(input).axis.press = PressState::Released;
+        self.sync_pressed_receiver(input);
+    }
+
+    fn release_source(&mut self, source: InputSource) {
+        InputView::release_source(self, source);
+    }
+
+    fn set_input_source(&mut self, source: InputSource) {
+        InputView::set_input_source(self, source);
+    }
+
+    fn set_receiver_source(&mut self, input: InputReceiver, source: InputSource) {
+        InputView::set_receiver_source(self, input, source);
+    }
+}
+
+impl<'w, Keys> InputSink for Mut<'w, InputView<Keys>>
+where
+    Keys: BindingTypeView,
+{
+    fn receiver_state(&self, input: &InputReceiver) -> AxisState {
+        self.raw_state(input)
+    }
+
+    fn set_axis_value(&mut self, input: InputReceiver, value: f32, press: PressState) {
+        InputSink::set_axis_value(self.as_mut(), input, value, press);
+    }
+
+    fn set_key_receiver_state(&mut self, input: InputReceiver, press: PressState) {
+        InputSink::set_key_receiver_state(self.as_mut(), input, press);
+    }
+
+    fn release_receiver(&mut self, input: InputReceiver) {
+        self.as_mut().release_receiver(input);
+    }
+
+    fn release_source(&mut self, source: InputSource) {
+        InputSink::release_source(self.as_mut(), source);
+    }
+
+    fn set_input_source(&mut self, source: InputSource) {
+        self.as_mut().set_input_source(source);
+    }
+
+    fn set_receiver_source(&mut self, input: InputReceiver, source: InputSource) {
+        self.as_mut().set_receiver_source(input, source);
+    }
+}
+
+/// A write to an input view, waiting to be merged by the [`StagedInputSystem`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum StagedWrite {
+    Axis {
+        input: InputReceiver,
+        value: f32,
+        press: PressState,
+    },
+    Key {
+        input: InputReceiver,
+        press: PressState,
+    },
+    Release(InputReceiver),
+    ReleaseSource(InputSource),
+    Source(InputSource),
+    ReceiverSource {
+        input: InputReceiver,
+        source: InputSource,
+    },
+}
+
+/// Event sent for every receiver written by the keyboard, mouse, gamepad and touch systems, before the actions are
+/// resolved. Useful to implement custom recognizers without handling every input source.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct RawInputEvent {
+    pub entity: Entity,
+    pub receiver: InputReceiver,
+    /// The value as written by the input source, before inversion.
+    pub value: f32,
+    pub pressed: bool,
+    pub source: InputSource,
+    /// The instant in which the input was collected.
+    pub timestamp: Instant,
+}
+
+/// The writes of the input source handled by the `Marker` component for the view of an entity. Inserted
+/// automatically for every entity with an input view and the marker.
+#[derive(PartialEq, Debug, Component, Clone)]
+pub struct StagedInput<Keys, Marker>
+where
+    Keys: BindingTypeView,
+    Marker: Component,
+{
+    /// The staged writes and the instant in which they were staged.
+    pub writes: Vec<(Instant, StagedWrite)>,
+    phantom: PhantomData<fn() -> (Keys, Marker)>,
+}
+
+impl<Keys, Marker> Default for StagedInput<Keys, Marker>
+where
+    Keys: BindingTypeView,
+    Marker: Component,
+{
+    fn default() -> Self {
+        Self {
+            writes: Vec::new(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Keys, Marker> StagedInput<Keys, Marker>
+where
+    Keys: BindingTypeView,
+    Marker: Component,
+{
+    /// Merge the writes into the view, in the order they were staged, collecting a [`RawInputEvent`] for each
+    /// written receiver.
+    pub fn apply(&mut self, entity: Entity, view: &mut InputView<Keys>, raw: &mut Vec<RawInputEvent>) {
+        for (timestamp, write) in self.writes.drain(..) {
+            apply_write(entity, view, timestamp, write, raw);
+        }
+    }
+}
+
+/// Merge a staged write into the view, stamping the written receiver with the instant it was collected.
+fn apply_write<Keys>(
+    entity: Entity,
+    view: &mut InputView<Keys>,
+    timestamp: Instant,
+    write: StagedWrite,
+    raw: &mut Vec<RawInputEvent>,
+) where
+    Keys: BindingTypeView,
+{
+    let receiver = match write {
+        StagedWrite::Axis { input, .. } | StagedWrite::Key { input, .. } => Some(input),
+        _ => None,
+    };
+    match write {
+        StagedWrite::Axis {
+            input,
+            value,
+            press,
+        } => InputSink::set_axis_value(view, input, value, press),
+        StagedWrite::Key { input, press } => InputSink::set_key_receiver_state(view, input, press),
+        StagedWrite::Release(input) => view.release_receiver(input),
+        StagedWrite::ReleaseSource(source) => InputSink::release_source(view, source),
+        StagedWrite::Source(source) => view.set_input_source(source),
+        StagedWrite::ReceiverSource { input, source } => view.set_receiver_source(input, source),
+    }
+    if let Some(receiver) = receiver {
+        if let Some(descriptor) = view.descriptor_mut(&receiver) {
+            descriptor.written_instant = Some(timestamp);
+        }
+        let state = view.raw_state(&receiver);
+        raw.push(RawInputEvent {
+            entity,
+            receiver,
+            value: state.value,
+            pressed: state.press.pressed(),
+            source: view.receiver_source(&receiver),
+            timestamp,
+        });
+    }
+}
+
+/// An input view that can only be read, paired with the staging buffer receiving the writes.
+pub struct StagedView<'a, 'w, Keys, Marker>
+where
+    Keys: BindingTypeView,
+    Marker: Component,
+{
+    pub view: &'a InputView<Keys>,
+    pub staged: &'a mut Mut<'w, StagedInput<Keys, Marker>>,
+}
+
+impl<'a, 'w, Keys, Marker> StagedView<'a, 'w, Keys, Marker>
+where
+    Keys: BindingTypeView,
+    Marker: Component,
+{
+    pub fn new(view: &'a InputView<Keys>, staged: &'a mut Mut<'w, StagedInput<Keys, Marker>>) -> Self {
+        Self { view, staged }
+    }
+
+    fn push(&mut self, write: StagedWrite) {
+        self.staged.writes.push((Instant::now(), write));
+    }
+}
+
+impl<'a, 'w, Keys, Marker> InputSink for StagedView<'a, 'w, Keys, Marker>
+where
+    Keys: BindingTypeView,
+    Marker: Component,
+{
+    fn receiver_state(&self, input: &InputReceiver) -> AxisState {
+        let current = self.view.raw_state(input);
+        for (_, write) in self.staged.writes.iter().rev() {
+            match *write {
+                StagedWrite::Axis {
+                    input: rcv,
+                    value,
+                    press,
+                } if rcv == *input => return AxisState::new(value, press),
+                StagedWrite::Key { input: rcv, press } if rcv == *input => {
+                    let value = match press {
+                        PressState::Pressed { .. } => self
+                            .view
+                            .descriptor(input)
+                            .map_or(0., |descriptor| descriptor.default_axis_value),
+                        PressState::Released => 0.,
+                    };
+                    return AxisState::new(value, press);
+                }
+                StagedWrite::Release(rcv) if rcv == *input => {
+                    return AxisState::new(current.value, PressState::Released)
+                }
+                StagedWrite::ReleaseSource(source)
+                    if self.view.receiver_source(input) == source && current.press.pressed() =>
+                {
+                    return AxisState::new(0., PressState::Released)
+                }
+                _ => {}
+            }
+        }
+        current
+    }
+
+    fn set_axis_value(&mut self, input: InputReceiver, value: f32, press: PressState) {
+        self.push(StagedWrite::Axis {
+            input,
+            value,
+            press,
+        });
+    }
+
+    fn set_key_receiver_state(&mut self, input: InputReceiver, press: PressState) {
+        self.push(StagedWrite::Key { input, press });
+    }
+
+    fn release_receiver(&mut self, input: InputReceiver) {
+        self.push(StagedWrite::Release(input));
+    }
+
+    fn release_source(&mut self, source: InputSource) {
+        self.push(StagedWrite::ReleaseSource(source));
+    }
+
+    fn set_input_source(&mut self, source: InputSource) {
+        if self.staged.writes.last().map(|(_, write)| *write) != Some(StagedWrite::Source(source)) {
+            self.push(StagedWrite::Source(source));
+        }
+    }
+
+    fn set_receiver_source(&mut self, input: InputReceiver, source: InputSource) {
+        self.push(StagedWrite::ReceiverSource { input, source });
+    }
+}
+
+/// Insert the missing staging buffer for every entity with an input view and the `Marker` component.
+#[allow(clippy::type_complexity)]
+pub(crate) fn insert_staged_input_system<Keys, Marker>(
+    mut commands: Commands,
+    query: Query<Entity, (With<InputView<Keys>>, With<Marker>, Without<StagedInput<Keys, Marker>>)>,
+) where
+    Keys: BindingTypeView,
+    Marker: Component,
+{
+    for entity in query.iter() {
+        commands
+            .entity(entity)
+            .insert(StagedInput::<Keys, Marker>::default());
+    }
+}
+
+/// Merge the staging buffers of every input source into the input views, sending the [`RawInputEvent`]s. The writes
+/// of every source are merged in the order they were collected, so [`MergePolicy::LastWriter`] follows the input
+/// rather than the order of the input systems. The views are only mutated when something was staged.
+#[allow(clippy::type_complexity)]
+pub(crate) fn staged_input_system<Keys>(
+    mut query: Query<(
+        Entity,
+        &mut InputView<Keys>,
+        Option<&mut StagedInput<Keys, KeyboardMarker>>,
+        Option<&mut StagedInput<Keys, MouseMarker>>,
+        Option<&mut StagedInput<Keys, GamepadMarker>>,
+        Option<&mut StagedInput<Keys, TouchMarker>>,
+    )>,
+    mut wr: EventWriter<RawInputEvent>,
+) where
+    Keys: BindingTypeView,
+{
+    let mut raw = Vec::new();
+    for (entity, mut view, keyboard, mouse, gamepad, touch) in query.iter_mut() {
+        let mut writes = Vec::new();
+        if let Some(mut staged) = keyboard.filter(|staged| !staged.writes.is_empty()) {
+            writes.append(&mut staged.writes);
+        }
+        if let Some(mut staged) = mouse.filter(|staged| !staged.writes.is_empty()) {
+            writes.append(&mut staged.writes);
+        }
+        if let Some(mut staged) = gamepad.filter(|staged| !staged.writes.is_empty()) {
+            writes.append(&mut staged.writes);
+        }
+        if let Some(mut staged) = touch.filter(|staged| !staged.writes.is_empty()) {
+            writes.append(&mut staged.writes);
+        }
+        if writes.is_empty() {
+            continue;
+        }
+        // Stable, so the writes of a source collected in the same instant keep their order.
+        writes.sort_by_key(|(timestamp, _)| *timestamp);
+        for (timestamp, write) in writes {
+            apply_write(entity, view.as_mut(), timestamp, write, &mut raw);
+        }
+    }
+    wr.send_batch(raw.into_iter());
+}
+
+// Test to check that staged writes are merged into the view in order, with a raw event for each written receiver.
+#[test]
+fn staged_input_apply_test() {
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let space = InputReceiver::KeyboardKey(KeyCode::Space);
+    let pressed = PressState::Pressed {
+        started_pressing_instant: None,
+    };
+    let mut staged = StagedInput::<Jump, KeyboardMarker>::default();
+    let now = Instant::now();
+    staged.writes.push((now, StagedWrite::Source(InputSource::Keyboard)));
+    staged.writes.push((now, StagedWrite::Key { input: space, press: pressed }));
+    staged.writes.push((now, StagedWrite::Release(space)));
+
+    let mut view = InputView::<Jump>::new();
+    let mut raw = Vec::new();
+    staged.apply(Entity::from_raw(0), &mut view, &mut raw);
+    assert!(staged.writes.is_empty());
+    assert!(view.state(&space).press.released());
+    assert_eq!(view.last_input_source, Some(InputSource::Keyboard));
+    assert_eq!(raw.len(), 1);
+    assert!(raw[0].pressed);
+}
+
+// Test to check that a swipe dragged with the mouse reports the mouse as its source and is released with it.
+#[test]
+fn mouse_swipe_source_test() {
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct Jump;
+    impl BindingTypeView for Jump {}
+
+    let swipe = InputReceiver::Swipe(Direction::Left);
+    let mut view = InputView::<Jump>::new();
+    InputSink::set_swipe(&mut view, Direction::Left, InputSource::Mouse);
+    assert_eq!(view.receiver_source(&swipe), InputSource::Mouse);
+    InputSink::release_source(&mut view, InputSource::Touch);
+    assert!(view.state(&swipe).press.pressed());
+    InputSink::release_source(&mut view, InputSource::Mouse);
+    assert!(view.state(&swipe).press.released());
+}
@@ -30,6 +30,9 @@ pub trait PressStateExt {
     fn is_pressed_for(&self, duration: Duration) -> bool;
 
     /// Returns whether the button or axis was just pressed or moved in this exact tick or not.
+    /// This relies on the elapsed time, see [`InputView::press_frame`](crate::view::InputView::press_frame) for
+    /// a frame-exact alternative, or [`InputView::just_pressed_in`](crate::view::InputView::just_pressed_in) for
+    /// systems running in a fixed timestep.
     fn just_pressed(&self) -> bool;
 
     /// Returns whether the button or axis is currently pressed or moving.
@@ -163,6 +166,34 @@ fn partial_ord_press_state_test() {
     assert_eq!(value, std::cmp::Ordering::Less);
 }
 
+/// The frame counter used to stamp presses, incremented at the start of every frame.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct InputFrame(pub u64);
+
+impl InputFrame {
+    /// Returns whether the given press frame is the current frame.
+    pub fn is_current(&self, frame: Option<u64>) -> bool {
+        frame == Some(self.0)
+    }
+}
+
+/// The frame and instant in which a receiver was last pressed, recorded together by the tick system.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct PressTracker {
+    pub frame: Option<u64>,
+    pub instant: Option<Instant>,
+}
+
+impl PressTracker {
+    /// Record a press happening now in the given frame.
+    pub fn record(&mut self, frame: u64) -> Instant {
+        let instant = Instant::now();
+        self.frame = Some(frame);
+        self.instant = Some(instant);
+        instant
+    }
+}
+
 /// The current axis state. In other words, the strength (how much the axis is moved) and press state.
 #[derive(PartialEq, Clone, Copy, Debug)]
 pub struct AxisState {
@@ -0,0 +1,117 @@
+//! Optional input statistics (press counts, hold durations, actions per minute), useful for tutorials and
+//! analytics.
+use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct InputStatsSystem;
+
+/// Statistics accumulated for the input view of the same entity.
+#[derive(PartialEq, Eq, Clone, Debug, Component, Deserialize, Serialize)]
+pub struct InputStats<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// How many times each action was pressed.
+    pub presses: HashMap<Keys, u32>,
+    /// How long each action was held in total.
+    pub held: HashMap<Keys, Duration>,
+    /// How many times each receiver was pressed, e.g. for a key heatmap.
+    pub receiver_presses: HashMap<InputReceiver, u32>,
+    /// How many frames each action spent in each range of axis values, from 0 to 1 in
+    /// [`InputStats::HISTOGRAM_BUCKETS`] buckets. Frames at rest aren't counted.
+    pub axis_histograms: HashMap<Keys, Vec<u32>>,
+    /// The time elapsed since the statistics started being collected.
+    pub elapsed: Duration,
+}
+
+impl<Keys> Default for InputStats<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            presses: HashMap::default(),
+            held: HashMap::default(),
+            receiver_presses: HashMap::default(),
+            axis_histograms: HashMap::default(),
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl<Keys> InputStats<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// The number of buckets of the axis histograms.
+    pub const HISTOGRAM_BUCKETS: usize = 10;
+
+    /// Returns how many times the action was pressed.
+    pub fn press_count(&self, kind: &Keys) -> u32 {
+        self.presses.get(kind).copied().unwrap_or(0)
+    }
+
+    /// Returns how long the action was held in total.
+    pub fn held_for(&self, kind: &Keys) -> Duration {
+        self.held.get(kind).copied().unwrap_or(Duration::ZERO)
+    }
+
+    /// Count a frame with the given axis value in the histogram of an action.
+    pub fn record_axis(&mut self, kind: Keys, value: f32) {
+        if value == 0. {
+            return;
+        }
+        let bucket = ((value.abs().min(1.) * Self::HISTOGRAM_BUCKETS as f32) as usize).min(Self::HISTOGRAM_BUCKETS - 1);
+        let histogram = self
+            .axis_histograms
+            .entry(kind)
+            .or_insert_with(|| vec![0; Self::HISTOGRAM_BUCKETS]);
+        histogram[bucket] += 1;
+    }
+
+    /// Returns the average of action presses per minute.
+    pub fn actions_per_minute(&self) -> f32 {
+        let minutes = self.elapsed.as_secs_f32() / 60.;
+        if minutes <= 0. {
+            return 0.;
+        }
+        self.presses.values().sum::<u32>() as f32 / minutes
+    }
+
+    /// Clear all statistics.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// System responsible for accumulating the statistics of each input view with an [`InputStats`] component.
+pub(crate) fn input_stats_system<Keys>(
+    mut query: Query<(&InputView<Keys>, &mut InputStats<Keys>)>,
+    frame: Res<InputFrame>,
+    time: Res<Time>,
+) where
+    Keys: BindingTypeView,
+{
+    for (view, mut stats) in query.iter_mut() {
+        stats.elapsed += time.delta();
+        for (action, snapshot) in view.snapshots.iter() {
+            stats.record_axis(*action, snapshot.value);
+            if !snapshot.pressed {
+                continue;
+            }
+            if view.has_changed(action) {
+                *stats.presses.entry(*action).or_default() += 1;
+            }
+            *stats.held.entry(*action).or_default() += time.delta();
+        }
+        for descriptor in view.descriptors.iter() {
+            if frame.is_current(descriptor.tracker.frame) && descriptor.axis.press.pressed() {
+                *stats.receiver_presses.entry(descriptor.input).or_default() += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Stepped axes for menu sliders and volume controls: a held axis is converted into discrete steps, repeating while
+//! held like the d-pad in menus.
+use bevy::prelude::*;
+use bevy::utils::{Duration, HashMap, Instant};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct SteppedAxisSystem;
+
+/// The threshold and repeat timing of a stepped axis.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct StepSettings {
+    /// The axis value the action needs to reach to step.
+    pub threshold: f32,
+    /// The time the axis needs to be held before it repeats.
+    pub repeat_delay: Duration,
+    /// The time between repeats while the axis is held.
+    pub repeat_interval: Duration,
+}
+
+impl Default for StepSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            repeat_delay: Duration::from_millis(400),
+            repeat_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Per action stepping configuration and repeat state.
+#[derive(PartialEq, Clone, Debug)]
+pub struct StepTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub settings: HashMap<Keys, StepSettings>,
+    /// The direction each action is held in, the instant it was pressed and the instant it last stepped.
+    pub held: HashMap<Keys, (i32, Instant, Instant)>,
+    /// The steps of each action this frame.
+    pub steps: HashMap<Keys, i32>,
+}
+
+impl<Keys> Default for StepTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            settings: HashMap::default(),
+            held: HashMap::default(),
+            steps: HashMap::default(),
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the steps of a stepped axis this frame: 1 or -1 when the axis is pushed or repeats, 0 otherwise.
+    pub fn stepped_axis(&self, kind: &Keys) -> i32 {
+        self.step.steps.get(kind).copied().unwrap_or_default()
+    }
+
+    /// Enable or disable stepping for an action.
+    pub fn set_stepped_axis(&mut self, kind: Keys, settings: Option<StepSettings>) {
+        match settings {
+            Some(settings) => self.step.settings.insert(kind, settings),
+            None => self.step.settings.remove(&kind),
+        };
+        self.step.held.remove(&kind);
+        self.step.steps.remove(&kind);
+    }
+}
+
+/// System responsible for converting the held stepped axes of each input view into steps.
+pub(crate) fn stepped_axis_system<Keys>(mut query: Query<&mut InputView<Keys>>)
+where
+    Keys: BindingTypeView,
+{
+    let now = Instant::now();
+    for mut view in query.iter_mut() {
+        if view.step.settings.is_empty() {
+            continue;
+        }
+        let mut held = view.step.held.clone();
+        let mut steps = HashMap::default();
+        for (action, settings) in view.step.settings.iter() {
+            let value = view
+                .axis(action)
+                .iter()
+                .map(|state| state.value)
+                .fold(0., |max: f32, value| if value.abs() > max.abs() { value } else { max });
+            let direction = if value.abs() >= settings.threshold {
+                value.signum() as i32
+            } else {
+                0
+            };
+            let step = match (direction, held.get(action).copied()) {
+                (0, _) => {
+                    held.remove(action);
+                    0
+                }
+                (direction, Some((previous, pressed_at, stepped_at))) if direction == previous => {
+                    let repeat = now.duration_since(pressed_at) >= settings.repeat_delay
+                        && now.duration_since(stepped_at) >= settings.repeat_interval;
+                    if !repeat {
+                        continue;
+                    }
+                    held.insert(*action, (direction, pressed_at, now));
+                    direction
+                }
+                (direction, _) => {
+                    held.insert(*action, (direction, now, now));
+                    direction
+                }
+            };
+            if step != 0 {
+                steps.insert(*action, step);
+            }
+        }
+        if held != view.step.held || steps != view.step.steps {
+            view.step.held = held;
+            view.step.steps = steps;
+        }
+    }
+}
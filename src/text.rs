@@ -0,0 +1,200 @@
+//! Text capture for chat boxes and name entry. While a [`TextCapture`] is active, the typed characters are collected
+//! into its text and the keyboard actions of the view are released. Bevy doesn't expose IME composition nor the
+//! clipboard, so a platform integration sends the [`ImeEvent`]s, and pasting with Ctrl+V reads a
+//! [`ClipboardBackend`] through the [`TextClipboardPlugin`].
+use bevy::ecs::event::Events;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::window::ReceivedCharacter;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct TextCaptureSystem;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct TextClipboardSystem;
+
+/// Event sent by the IME integration of the platform while composing text (e.g. Japanese or Chinese input).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ImeEvent {
+    /// The text being composed changed. An empty text cancels the composition.
+    Preedit(String),
+    /// The composition finished with the given text.
+    Commit(String),
+}
+
+/// Event carrying text pasted into the active text captures.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct TextPaste {
+    pub text: String,
+}
+
+/// Captures the text typed while active into the input view of its entity.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Default)]
+pub struct TextCapture {
+    pub active: bool,
+    pub text: String,
+    /// The text being composed by the IME, not committed to the text yet. Display it after the text.
+    pub composing: String,
+    /// The maximum number of characters of the text, if any.
+    pub max_chars: Option<usize>,
+}
+
+impl TextCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start capturing text.
+    pub fn start(&mut self) {
+        self.active = true;
+    }
+
+    /// Stop capturing text, discarding the text being composed.
+    pub fn stop(&mut self) {
+        self.active = false;
+        self.composing.clear();
+    }
+
+    /// Returns the captured text, clearing it.
+    pub fn take(&mut self) -> String {
+        std::mem::take(&mut self.text)
+    }
+
+    /// Append text, skipping the control characters and respecting the maximum number of characters.
+    pub fn push_str(&mut self, text: &str) {
+        for char in text.chars() {
+            self.push(char);
+        }
+    }
+
+    /// Append a typed character. Backspace removes the last character and other control characters are ignored.
+    pub fn push(&mut self, char: char) {
+        if char == '\u{8}' {
+            self.text.pop();
+            return;
+        }
+        if char.is_control() || self.max_chars.is_some_and(|max| self.text.chars().count() >= max) {
+            return;
+        }
+        self.text.push(char);
+    }
+}
+
+/// A source of clipboard text.
+pub trait ClipboardBackend: Send + Sync + 'static {
+    /// Returns the text in the clipboard, if any.
+    fn read(&mut self) -> Option<String>;
+}
+
+/// The clipboard backend read by the [`TextClipboardPlugin`].
+pub struct TextClipboard<B>
+where
+    B: ClipboardBackend,
+{
+    pub backend: B,
+}
+
+/// A [`Plugin`] reading the clipboard when Ctrl+V (or Cmd+V) is pressed, sending a [`TextPaste`] event to the active
+/// text captures.
+pub struct TextClipboardPlugin<B>
+where
+    B: ClipboardBackend + Clone,
+{
+    pub backend: B,
+}
+
+impl<B> TextClipboardPlugin<B>
+where
+    B: ClipboardBackend + Clone,
+{
+    pub fn new(backend: B) -> Self {
+        Self { backend }
+    }
+}
+
+impl<B> Plugin for TextClipboardPlugin<B>
+where
+    B: ClipboardBackend + Clone,
+{
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<Events<TextPaste>>() {
+            app.add_event::<TextPaste>();
+        }
+        app.insert_resource(TextClipboard {
+            backend: self.backend.clone(),
+        });
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            text_clipboard_system::<B>
+                .label(TextClipboardSystem)
+                .after(InputSystem)
+                .before(TextCaptureSystem),
+        );
+    }
+}
+
+/// System responsible for reading the clipboard when the paste shortcut is pressed.
+pub(crate) fn text_clipboard_system<B>(
+    mut clipboard: ResMut<TextClipboard<B>>,
+    keys: Res<Input<KeyCode>>,
+    captures: Query<&TextCapture>,
+    mut wr: EventWriter<TextPaste>,
+) where
+    B: ClipboardBackend,
+{
+    let modifier = keys.any_pressed([KeyCode::LControl, KeyCode::RControl, KeyCode::LWin, KeyCode::RWin]);
+    if !modifier || !keys.just_pressed(KeyCode::V) || !captures.iter().any(|capture| capture.active) {
+        return;
+    }
+    if let Some(text) = clipboard.backend.read() {
+        wr.send(TextPaste { text });
+    }
+}
+
+/// System responsible for collecting the typed, composed and pasted text into the active text captures, releasing
+/// the keyboard receivers of their views.
+pub(crate) fn text_capture_system<Keys>(
+    mut query: Query<(&mut InputView<Keys>, &mut TextCapture)>,
+    mut char_rd: EventReader<ReceivedCharacter>,
+    mut ime_rd: EventReader<ImeEvent>,
+    mut paste_rd: EventReader<TextPaste>,
+) where
+    Keys: BindingTypeView,
+{
+    let chars: Vec<char> = char_rd.iter().map(|ev| ev.char).collect();
+    let ime: Vec<&ImeEvent> = ime_rd.iter().collect();
+    let pastes: Vec<&TextPaste> = paste_rd.iter().collect();
+    for (mut view, mut capture) in query.iter_mut() {
+        if !capture.active {
+            continue;
+        }
+        if !chars.is_empty() || !ime.is_empty() || !pastes.is_empty() {
+            for ev in ime.iter() {
+                match ev {
+                    ImeEvent::Preedit(text) => capture.composing = text.clone(),
+                    ImeEvent::Commit(text) => {
+                        capture.composing.clear();
+                        capture.push_str(text);
+                    }
+                }
+            }
+            // The characters typed while composing are consumed by the IME.
+            if capture.composing.is_empty() {
+                for char in chars.iter() {
+                    capture.push(*char);
+                }
+            }
+            for paste in pastes.iter() {
+                capture.push_str(&paste.text);
+            }
+        }
+        let typing = view.descriptors.iter().any(|descriptor| {
+            descriptor.input.source() == InputSource::Keyboard && descriptor.axis.press.pressed()
+        });
+        if typing {
+            view.release_source(InputSource::Keyboard);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Press tokens: every press of an action gets a unique [`PressToken`], kept until the action is released. Systems
+//! handling a press remember its token instead of consuming the press, so the press is handled exactly once even when
+//! the handling system runs a few frames later or several systems race for it (see [`HandledPresses`]).
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct PressTokenSystem;
+
+/// The identifier of a single press of an action, unique across every input view.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, PartialOrd, Ord)]
+pub struct PressToken(pub u64);
+
+static NEXT_PRESS_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+impl PressToken {
+    /// Returns a token never returned before.
+    pub fn next() -> Self {
+        Self(NEXT_PRESS_TOKEN.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// The token of the current press of each action.
+#[derive(PartialEq, Clone, Debug)]
+pub struct PressTokenTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    pub tokens: HashMap<Keys, PressToken>,
+}
+
+impl<Keys> Default for PressTokenTracker<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            tokens: HashMap::default(),
+        }
+    }
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Returns the token of the current press of an action, or `None` if the action isn't pressed.
+    pub fn press_token(&self, kind: &Keys) -> Option<PressToken> {
+        self.tokens.tokens.get(kind).copied()
+    }
+}
+
+/// The presses already handled, shared as a resource by the systems that must handle a press only once, or kept in a
+/// [`Local`](bevy::prelude::Local) by a single system.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct HandledPresses {
+    pub handled: HashSet<PressToken>,
+}
+
+impl HandledPresses {
+    /// Returns whether the current press of an action wasn't handled yet, marking it as handled.
+    pub fn handle<Keys>(&mut self, view: &InputView<Keys>, kind: &Keys) -> bool
+    where
+        Keys: BindingTypeView,
+    {
+        view.press_token(kind).is_some_and(|token| self.handled.insert(token))
+    }
+
+    /// Forget the handled presses that are over, given every input view handled.
+    pub fn retain_pressed<'a, Keys>(&mut self, views: impl IntoIterator<Item = &'a InputView<Keys>>)
+    where
+        Keys: BindingTypeView,
+    {
+        let pressed: HashSet<PressToken> = views
+            .into_iter()
+            .flat_map(|view| view.tokens.tokens.values().copied())
+            .collect();
+        self.handled.retain(|token| pressed.contains(token));
+    }
+}
+
+/// System responsible for giving a token to every new press of the actions of each input view.
+pub(crate) fn press_token_system<Keys>(mut query: Query<&mut InputView<Keys>>)
+where
+    Keys: BindingTypeView,
+{
+    for mut view in query.iter_mut() {
+        let mut tokens = view.tokens.tokens.clone();
+        for action in view.bindings.keys() {
+            if !view.key(action).pressed() {
+                tokens.remove(action);
+            } else if !tokens.contains_key(action) {
+                tokens.insert(*action, PressToken::next());
+            }
+        }
+        if tokens != view.tokens.tokens {
+            view.tokens.tokens = tokens;
+        }
+    }
+}
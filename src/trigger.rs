@@ -0,0 +1,75 @@
+//! Dual-stage analog triggers: a soft pull activates one action (e.g. aim) and a full pull another one (e.g. fire),
+//! each released slightly under its threshold so the stages don't flicker.
+use crate::prelude::*;
+
+/// The pull thresholds of a dual-stage trigger.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct DualStage {
+    /// The trigger value activating the soft pull action.
+    pub soft: f32,
+    /// The trigger value activating the full pull action.
+    pub full: f32,
+    /// How far under its threshold the trigger needs to go back to release a stage.
+    pub hysteresis: f32,
+}
+
+impl Default for DualStage {
+    fn default() -> Self {
+        Self {
+            soft: 0.2,
+            full: 0.9,
+            hysteresis: 0.05,
+        }
+    }
+}
+
+/// The values at which a stage of a trigger activates and releases its action.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct StageThreshold {
+    pub press: f32,
+    pub release: f32,
+}
+
+impl<Keys> InputView<Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// Make two actions the stages of a dual-stage trigger. Both actions need to be bound to the trigger, and the
+    /// soft pull action stays active during the full pull.
+    pub fn set_dual_stage(&mut self, soft: Keys, full: Keys, stages: DualStage) {
+        self.stage_thresholds.insert(
+            soft,
+            StageThreshold {
+                press: stages.soft,
+                release: stages.soft - stages.hysteresis,
+            },
+        );
+        self.stage_thresholds.insert(
+            full,
+            StageThreshold {
+                press: stages.full,
+                release: stages.full - stages.hysteresis,
+            },
+        );
+    }
+
+    /// Stop treating an action as the stage of a trigger.
+    pub fn clear_stage(&mut self, kind: &Keys) {
+        self.stage_thresholds.remove(kind);
+    }
+
+    /// Returns whether the receivers pull the trigger far enough for the stage of the action, using the release
+    /// threshold if the action was active in the last frame.
+    pub fn reaches_stage(&self, kind: &Keys, receivers: &InputReceivers) -> bool {
+        let threshold = match self.stage_thresholds.get(kind) {
+            Some(threshold) => threshold,
+            None => return true,
+        };
+        let active = self.snapshots.get(kind).is_some_and(|snapshot| snapshot.pressed);
+        let threshold = if active { threshold.release } else { threshold.press };
+        receivers
+            .0
+            .iter()
+            .all(|rcv| self.state(rcv).value.abs() >= threshold)
+    }
+}
@@ -2,7 +2,11 @@
 //! if a key or axis for a [`BindingTypeView`] is pressed or released by proving the [`PressState`].
 use std::{collections::HashMap};
 
-use bevy::{prelude::Component, utils::hashbrown::HashSet};
+use bevy::{
+    math::Vec2,
+    prelude::Component,
+    utils::{hashbrown::HashSet, Duration, Instant},
+};
 
 use crate::prelude::*;
 
@@ -12,6 +16,11 @@ pub enum InputSource {
     Gamepad,
     Keyboard,
     Mouse,
+    /// The keyboard and the mouse used together, reported instead of them when combined (see
+    /// [`SourceClassification`]). Receivers never have this source.
+    KeyboardMouse,
+    Touch,
+    Custom,
 }
 
 #[allow(dead_code)]
@@ -30,26 +39,196 @@ impl InputSource {
     pub fn is_mouse(&self) -> bool {
         *self == InputSource::Mouse
     }
+
+    /// Returns whether this input source is referent to the keyboard and mouse combined.
+    pub fn is_keyboard_mouse(&self) -> bool {
+        *self == InputSource::KeyboardMouse
+    }
+
+    /// Returns whether this input source is referent to a touch screen.
+    pub fn is_touch(&self) -> bool {
+        *self == InputSource::Touch
+    }
+
+    /// Returns whether this input source is referent to a user-defined receiver.
+    pub fn is_custom(&self) -> bool {
+        *self == InputSource::Custom
+    }
 }
 
 /// A holder for input states and its default value.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ReceiverDescriptor {
     pub axis: AxisState,
+    /// The axis value as written by the input source, before inversion.
+    pub raw_value: f32,
     pub default_axis_value: f32,
     pub input: InputReceiver,
+    pub released_instant: Option<Instant>,
+    /// The instant in which the state was last written, or collected by its input system for the staged writes.
+    pub written_instant: Option<Instant>,
+    pub previous_value: f32,
+    pub delta: f32,
+    pub tracker: PressTracker,
 }
 
 impl ReceiverDescriptor {
     pub fn new(input: InputReceiver, default_axis_value: f32) -> Self {
         Self {
             axis: AxisState::ZERO,
+            raw_value: 0.,
             default_axis_value,
             input,
+            released_instant: None,
+            written_instant: None,
+            previous_value: 0.,
+            delta: 0.,
+            tracker: PressTracker::default(),
+        }
+    }
+
+    /// Returns whether the axis value moved since the last frame or the stored delta is stale.
+    pub fn needs_delta_update(&self) -> bool {
+        self.axis.value != self.previous_value || self.delta != 0.
+    }
+
+    /// Store the change of the axis value since the last frame.
+    pub fn update_delta(&mut self) {
+        self.delta = self.axis.value - self.previous_value;
+        self.previous_value = self.axis.value;
+    }
+
+    /// Set the axis state, recording the release instant when the receiver goes from pressed to released.
+    pub fn set(&mut self, value: f32, press: PressState) {
+        let now = Instant::now();
+        if self.axis.press.pressed() && press.released() {
+            self.released_instant = Some(now);
+        }
+        self.written_instant = Some(now);
+        self.axis.set(value, press);
+        self.raw_value = value;
+    }
+
+    /// Returns the elapsed time since the receiver was released, or `None` if it is pressed or was never released.
+    pub fn released_for(&self) -> Option<Duration> {
+        if self.axis.press.pressed() {
+            return None;
         }
+        self.released_instant.map(|instant| instant.elapsed())
     }
 }
 
+/// How the final state of an action is computed when multiple collections of receivers bound to it are pressed at
+/// the same time (e.g. keyboard and gamepad).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum MergePolicy {
+    /// The collection of receivers written last wins, by the instant the writes were collected by their input
+    /// systems.
+    #[default]
+    LastWriter,
+    /// The collection of receivers with the highest value wins.
+    MaxMagnitude,
+    /// The collection of receivers using the given source wins, falling back to the last writer.
+    PreferSource(InputSource),
+}
+
+/// The result of resolving an action, see [`InputView::resolve`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ActionResolution<'a, Keys>
+where
+    Keys: BindingTypeView,
+{
+    /// No collection of receivers bound to the action is fully pressed.
+    Inactive,
+    /// The action is a member of an action group, but not the active one.
+    Gated,
+    /// The action is a world action pressed while the pointer was over a UI node.
+    Occluded,
+    /// The action is pressed, but another action with a higher priority shares its receivers.
+    Shadowed(Keys),
+    /// The action is pressed by the given receivers.
+    Active(&'a InputReceivers),
+}
+
+/// The range of axis values activating an action, applied on top of the dead zone of the device. Lets one physical
+/// axis drive several actions with different thresholds (e.g. walking from 0.2 and sprinting from 0.8).
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct ActivationBand {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ActivationBand {
+    /// Creates a band activating the action from the given magnitude.
+    pub fn above(min: f32) -> Self {
+        Self { min, max: f32::INFINITY }
+    }
+
+    /// Returns whether the magnitude of the given value is inside the band.
+    pub fn contains(&self, value: f32) -> bool {
+        (self.min..=self.max).contains(&value.abs())
+    }
+}
+
+/// The scale and clamping applied to the axis values of an action when it is read, leaving the receiver values
+/// untouched for the other actions sharing them (e.g. halving the stick sensitivity of a precision aim action). Both
+/// apply to the magnitude of the value, and a value at rest stays at rest.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct AxisScale {
+    pub scale: f32,
+    /// The range the magnitude of a value not at rest is clamped to, e.g. `(0.2, 1.)` for a trigger that always
+    /// outputs at least 0.2 once pulled.
+    pub clamp: Option<(f32, f32)>,
+}
+
+impl Default for AxisScale {
+    fn default() -> Self {
+        Self { scale: 1., clamp: None }
+    }
+}
+
+impl AxisScale {
+    pub fn scaled(scale: f32) -> Self {
+        Self { scale, clamp: None }
+    }
+
+    pub fn clamped(min: f32, max: f32) -> Self {
+        Self {
+            scale: 1.,
+            clamp: Some((min, max)),
+        }
+    }
+
+    /// Returns the scaled and clamped value.
+    pub fn apply(&self, value: f32) -> f32 {
+        if value == 0. {
+            return 0.;
+        }
+        let magnitude = value.abs() * self.scale;
+        let magnitude = match self.clamp {
+            Some((min, max)) => magnitude.clamp(min, max),
+            None => magnitude,
+        };
+        magnitude.copysign(value)
+    }
+
+    /// Returns the stick position with its length scaled and clamped.
+    pub fn apply_vec(&self, value: Vec2) -> Vec2 {
+        let length = value.length();
+        if length == 0. {
+            return value;
+        }
+        value * (self.apply(length) / length)
+    }
+}
+
+/// The state of an action at the end of a frame, used to detect which actions changed between frames.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct ActionSnapshot {
+    pub pressed: bool,
+    pub value: f32,
+}
+
 /// A view is a object where all input states are stored. It also has useful methods such checking
 /// if a key or axis for a [`BindingTypeView`] is pressed or released by proving the [`PressState`].
 #[derive(PartialEq, Clone, Debug, Component, Default)]
@@ -58,8 +237,72 @@ where
     Keys: BindingTypeView,
 {
     pub last_input_source: Option<InputSource>,
-    pub bindings: HashMap<Keys, ActionBinding<Keys>>,
+    pub source_classification: SourceClassification,
+    /// The instant the last input source last changed.
+    pub source_switched: Option<Instant>,
+    pub merge_policy: MergePolicy,
+    pub bindings: ActionMap<Keys, ActionBinding<Keys>>,
+    pub binding_changes: Vec<BindingChange<Keys>>,
+    /// Reverse lookup indices, kept up to date by [`InputView::add_binding`] and [`InputView::clear_binding`].
+    pub receiver_index: ActionMap<Keys, Vec<InputReceiver>>,
+    pub action_index: HashMap<InputReceiver, Vec<Keys>>,
+    pub binding_sets: HashMap<String, ActionMap<Keys, ActionBinding<Keys>>>,
+    pub active_set: Option<String>,
+    /// The pushed input contexts, see [`InputView::push_context`].
+    pub contexts: ContextStack<Keys>,
+    /// Receivers held while switching binding sets, ignored by the actions until they are released.
+    pub suppressed: HashSet<InputReceiver>,
+    /// The opposing receivers resolved when held together, see [`InputView::set_socd`].
+    pub socd: Vec<SocdPair>,
+    /// Axis receivers whose values are negated when set, see [`InputView::set_inverted`].
+    pub inverted: HashSet<InputReceiver>,
+    /// The source that last wrote the receivers shared between sources (the swipes), see
+    /// [`InputView::receiver_source`].
+    pub receiver_sources: HashMap<InputReceiver, InputSource>,
+    /// The state of every receiver. Unlike the per-action state, receivers have no dense index and are looked up
+    /// linearly, which stays cheap for the handful of receivers a view holds at once.
     pub descriptors: Vec<ReceiverDescriptor>,
+    /// The dense index of every receiver, used by the bitsets below.
+    pub receiver_registry: ReceiverRegistry,
+    /// The receivers currently pressed, see [`InputView::chord_pressed`].
+    pub pressed_receivers: ReceiverBitSet,
+    /// The receivers of every bound chord, as a bitset.
+    pub chord_masks: HashMap<InputReceivers, ReceiverBitSet>,
+    pub groups: Vec<ActionGroup<Keys>>,
+    pub snapshots: ActionMap<Keys, ActionSnapshot>,
+    pub changed: ActionMap<Keys, ()>,
+    pub released: ActionMap<Keys, Instant>,
+    pub idle: IdleTracker,
+    pub hold: HoldTracker<Keys>,
+    pub flick: FlickTracker<Keys>,
+    pub step: StepTracker<Keys>,
+    pub ramp: RampTracker<Keys>,
+    pub tokens: PressTokenTracker<Keys>,
+    pub processors: ProcessorTracker<Keys>,
+    pub delay: InputDelay<Keys>,
+    pub direction: DirectionTracker<Keys>,
+    pub overrides: ActionMap<Keys, AxisState>,
+    pub held_modifiers: Modifiers,
+    /// The maximum time between the presses of the receivers of a chord, see [`InputView::set_chord_window`].
+    pub chord_windows: HashMap<InputReceivers, Duration>,
+    /// The chord window used by the chords without their own window. Chords accept any timing if none.
+    pub default_chord_window: Option<Duration>,
+    /// The input sources allowed to update each action, see [`InputView::set_source_filter`].
+    pub source_filters: ActionMap<Keys, Vec<InputSource>>,
+    /// The axis values activating each action, see [`InputView::set_activation_band`].
+    pub activation_bands: ActionMap<Keys, ActivationBand>,
+    /// The scale and clamping of the axis values of each action, see [`InputView::set_axis_scale`].
+    pub axis_scales: ActionMap<Keys, AxisScale>,
+    /// The trigger stages of the actions, see [`InputView::set_dual_stage`].
+    pub stage_thresholds: ActionMap<Keys, StageThreshold>,
+    /// The press edges of every tracked schedule, see [`InputView::track_schedule`].
+    pub schedule_edges: HashMap<String, ScheduleEdges<Keys>>,
+    /// The last transitions of each action, see [`InputView::set_history_capacity`].
+    pub history: HistoryTracker<Keys>,
+    /// The world actions suppressed over the UI, see [`InputView::set_world_action`].
+    pub occlusion: OcclusionTracker<Keys>,
+    /// The metadata set at runtime for each action, see [`InputView::set_action_metadata`].
+    pub metadata: ActionMap<Keys, ActionMetadata>,
 }
 
 impl<Keys> InputView<Keys>
@@ -75,8 +318,49 @@ where
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             last_input_source: None,
-            bindings: HashMap::new(),
+            source_classification: SourceClassification::default(),
+            source_switched: None,
+            merge_policy: MergePolicy::default(),
+            bindings: ActionMap::new(),
+            binding_changes: Vec::new(),
+            receiver_index: ActionMap::new(),
+            action_index: HashMap::new(),
+            binding_sets: HashMap::new(),
+            active_set: None,
+            contexts: ContextStack::default(),
+            suppressed: HashSet::new(),
+            socd: Vec::new(),
+            inverted: HashSet::new(),
+            receiver_sources: HashMap::new(),
             descriptors: Vec::with_capacity(capacity),
+            receiver_registry: ReceiverRegistry::default(),
+            pressed_receivers: ReceiverBitSet::default(),
+            chord_masks: HashMap::new(),
+            groups: Vec::new(),
+            snapshots: ActionMap::new(),
+            changed: ActionMap::new(),
+            released: ActionMap::new(),
+            idle: IdleTracker::default(),
+            hold: HoldTracker::default(),
+            flick: FlickTracker::default(),
+            step: StepTracker::default(),
+            ramp: RampTracker::default(),
+            tokens: PressTokenTracker::default(),
+            processors: ProcessorTracker::default(),
+            delay: InputDelay::default(),
+            direction: DirectionTracker::default(),
+            overrides: ActionMap::new(),
+            held_modifiers: Modifiers::NONE,
+            chord_windows: HashMap::new(),
+            default_chord_window: None,
+            source_filters: ActionMap::new(),
+            activation_bands: ActionMap::new(),
+            axis_scales: ActionMap::new(),
+            stage_thresholds: ActionMap::new(),
+            schedule_edges: HashMap::new(),
+            history: HistoryTracker::default(),
+            occlusion: OcclusionTracker::default(),
+            metadata: ActionMap::new(),
         }
     }
 
@@ -102,7 +386,9 @@ where
         if self.descriptors.len() >= self.capacity() {
             self.cleanup();
         }
+        let input = descriptor.input;
         self.descriptors.push(descriptor);
+        self.sync_pressed_receiver(input);
     }
 
     /// Get an existing descriptor.
@@ -126,13 +412,39 @@ where
         }
     }
 
-    /// Insert a new binding into the storage.
+    /// Insert a new binding into the storage. Replacing the receivers of a bound action is reported as a
+    /// [`BindingChange`], binding an action the first time isn't.
     pub fn add_binding(&mut self, binding: &mut ActionBinding<Keys>) -> &mut Self {
-        binding.apply_default_axis_to_all_receivers(self);
-        self.bindings.insert(binding.key, binding.clone());
+        self.insert_binding(binding, false);
         self
     }
 
+    /// Insert a binding, reporting it as a [`BindingChange`] even if the action wasn't bound yet. Used by rebinds
+    /// and profile applies.
+    pub(crate) fn apply_binding(&mut self, binding: &mut ActionBinding<Keys>) {
+        self.insert_binding(binding, true);
+    }
+
+    fn insert_binding(&mut self, binding: &mut ActionBinding<Keys>, report_new: bool) {
+        binding.apply_default_axis_to_all_receivers(self);
+        let old = self
+            .bindings
+            .insert(binding.key, binding.clone())
+            .map(|old| old.input_receivers);
+        let report = old.is_some() || report_new;
+        let old = old.unwrap_or_default();
+        if old != binding.input_receivers {
+            if report {
+                self.binding_changes.push(BindingChange {
+                    action: binding.key,
+                    old: old.into_iter().collect(),
+                    new: binding.input_receivers.iter().cloned().collect(),
+                });
+            }
+            self.reindex(&binding.key);
+        }
+    }
+
     /// Set the button state for a specific key receiver.
     pub fn state(&self, key: &InputReceiver) -> &AxisState {
         self.descriptor(key)
@@ -140,15 +452,71 @@ where
             .unwrap_or(&AxisState::ZERO)
     }
 
+    /// Returns the state of a receiver with its value as written by the input source, so input sources can compare
+    /// it with the value they read.
+    pub fn raw_state(&self, key: &InputReceiver) -> AxisState {
+        if let Some(state) = self.processors.inputs.get(key) {
+            return *state;
+        }
+        self.descriptor(key)
+            .map_or(AxisState::ZERO, |descriptor| AxisState::new(descriptor.raw_value, descriptor.axis.press))
+    }
+
     /// Set the axis state for a specific input receiver.
     pub fn set_axis_value(&mut self, input: InputReceiver, value: f32, element_state: PressState) {
-        self.descriptor_or_insert(input)
-            .axis
-            .set(value, element_state);
+        if self.stage_processed_receiver(input, value, element_state) {
+            return;
+        }
+        let raw = value;
+        let value = if self.inverted.contains(&input) {
+            -value
+        } else {
+            value
+        };
+        let descriptor = self.descriptor_or_insert(input);
+        descriptor.set(value, element_state);
+        descriptor.raw_value = raw;
+        self.sync_pressed_receiver(input);
+    }
+
+    /// Invert the axis values of a receiver from now on (e.g. "invert look Y").
+    pub fn set_inverted(&mut self, input: InputReceiver, inverted: bool) {
+        if inverted {
+            self.inverted.insert(input);
+        } else {
+            self.inverted.remove(&input);
+        }
+    }
+
+    /// Returns whether the axis values of a receiver are inverted.
+    pub fn is_inverted(&self, input: &InputReceiver) -> bool {
+        self.inverted.contains(input)
+    }
+
+    /// Record the source writing a receiver shared between sources, e.g. a swipe from a touch or a mouse drag.
+    pub fn set_receiver_source(&mut self, input: InputReceiver, source: InputSource) {
+        self.receiver_sources.insert(input, source);
+    }
+
+    /// Returns the source of a receiver: the one that last wrote it if it's shared between sources, otherwise the
+    /// one it belongs to.
+    pub fn receiver_source(&self, input: &InputReceiver) -> InputSource {
+        self.receiver_sources.get(input).copied().unwrap_or_else(|| input.source())
     }
 
     /// Set the axis state for a specific input receiver.
     pub fn set_key_receiver_state(&mut self, input: InputReceiver, state: PressState) {
+        if let InputReceiver::KeyboardKey(key) = input {
+            if let Some(modifier) = Modifiers::from_key(key) {
+                let held = state.pressed()
+                    || self.descriptors.iter().any(|dsc| {
+                        dsc.input != input
+                            && dsc.axis.press.pressed()
+                            && matches!(dsc.input, InputReceiver::KeyboardKey(other) if Modifiers::from_key(other) == Some(modifier))
+                    });
+                self.held_modifiers.set(modifier, held);
+            }
+        }
         let descriptor = self.descriptor_or_insert(input);
         let value = match state {
             PressState::Pressed { .. } => {
@@ -159,7 +527,55 @@ where
             },
             PressState::Released => 0.0,
         };
-        descriptor.axis.set(value, state);
+        descriptor.set(value, state);
+        self.sync_pressed_receiver(input);
+    }
+
+    /// Returns every receiver bound to an action.
+    pub fn receivers_for(&self, kind: &Keys) -> &[InputReceiver] {
+        self.receiver_index
+            .get(kind)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns every action bound to a receiver.
+    pub fn actions_for(&self, input: &InputReceiver) -> Vec<Keys> {
+        self.action_index.get(input).cloned().unwrap_or_default()
+    }
+
+    /// Update the reverse lookup indices for the current binding of an action.
+    pub(crate) fn reindex(&mut self, kind: &Keys) {
+        let old = self.receiver_index.remove(kind).unwrap_or_default();
+        for rcv in old.iter() {
+            if let Some(actions) = self.action_index.get_mut(rcv) {
+                actions.retain(|action| action != kind);
+                if actions.is_empty() {
+                    self.action_index.remove(rcv);
+                }
+            }
+        }
+        // Drop the masks of the chords of the previous binding that no binding uses anymore.
+        let bindings = &self.bindings;
+        self.chord_masks.retain(|chord, _| {
+            !chord.0.iter().any(|rcv| old.contains(rcv))
+                || bindings.values().any(|binding| binding.input_receivers.contains(chord))
+        });
+        let binding = match self.bindings.get(kind) {
+            Some(binding) => binding,
+            None => return,
+        };
+        let mut receivers: Vec<InputReceiver> = Vec::new();
+        for rcv in binding.input_receivers.iter().flat_map(|receivers| receivers.0.iter()) {
+            if !receivers.contains(rcv) {
+                receivers.push(*rcv);
+            }
+        }
+        for rcv in receivers.iter() {
+            self.action_index.entry(*rcv).or_default().push(*kind);
+        }
+        self.receiver_index.insert(*kind, receivers);
+        self.index_chords(kind);
     }
 
     /// Return the current press state for a specific binding matching with the given BindingTypeView.
@@ -169,29 +585,289 @@ where
 
     /// Return the current axis state for a specific binding matching with the given BindingTypeView.
     pub fn axis(&self, kind: &Keys) -> Vec<AxisState> {
-        let binding = self.bindings.get(kind);
-        if let Some(binding) = binding {
-            'initial: for r in binding.input_receivers.iter() {
-                if r.0.is_empty() {
-                    continue 'initial;
-                }
-                let states = r.0.iter();
-                let mut output = Vec::with_capacity(states.len());
-                for rcv in states {
-                    let state = self.state(rcv);
-                    if state.press.released() {
-                        continue 'initial;
+        if let Some(state) = self.overrides.get(kind) {
+            return vec![*state];
+        }
+        if self.delay.frames > 0 {
+            return self
+                .delay
+                .delayed
+                .actions
+                .get(kind)
+                .map(|delayed| delayed.axes.clone())
+                .unwrap_or_default();
+        }
+        self.live_axis(kind)
+    }
+
+    /// Return the current axis state of an action, ignoring the input delay (see [`InputView::set_input_delay`]).
+    pub fn live_axis(&self, kind: &Keys) -> Vec<AxisState> {
+        let scale = self.axis_scales.get(kind);
+        match self.live_resolve(kind) {
+            ActionResolution::Active(receivers) => receivers
+                .0
+                .iter()
+                .map(|rcv| {
+                    let mut state = *self.state(rcv);
+                    if let Some(scale) = scale {
+                        state.value = scale.apply(state.value);
                     }
-                    output.push(*state);
-                }
-                return output;
+                    state
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Resolve an action against the action groups and the priority of the other actions sharing its receivers.
+    /// Useful for debugging why an action isn't firing.
+    pub fn resolve(&self, kind: &Keys) -> ActionResolution<'_, Keys> {
+        if self.delay.frames > 0 {
+            return self.delayed_resolution(kind);
+        }
+        self.live_resolve(kind)
+    }
+
+    /// Resolve an action, ignoring the input delay (see [`InputView::set_input_delay`]).
+    pub fn live_resolve(&self, kind: &Keys) -> ActionResolution<'_, Keys> {
+        if self.is_gated(kind) {
+            return ActionResolution::Gated;
+        }
+        if self.is_occluded(kind) {
+            return ActionResolution::Occluded;
+        }
+        let (binding, receivers) = match self
+            .bindings
+            .get(kind)
+            .zip(self.live_active_receivers(kind))
+        {
+            Some(active) => active,
+            None => return ActionResolution::Inactive,
+        };
+        let shadowed_by = self.bindings.values().find(|other| {
+            (other.priority, other.while_held.len()) > (binding.priority, binding.while_held.len())
+                && !self.is_gated(&other.key)
+                && self.live_active_receivers(&other.key).is_some_and(|other| {
+                    other.0.iter().any(|rcv| receivers.0.contains(rcv))
+                })
+        });
+        match shadowed_by {
+            Some(other) => ActionResolution::Shadowed(other.key),
+            None => ActionResolution::Active(receivers),
+        }
+    }
+
+    /// Returns whether every receiver of a collection of receivers of an action is pressed, regardless of its
+    /// resolution. Used for the actions conditional bindings wait for, avoiding cycles between them. Always live,
+    /// since the input delay applies to the resolved actions as a whole.
+    fn is_held(&self, kind: &Keys) -> bool {
+        self.bindings
+            .get(kind)
+            .is_some_and(|binding| binding.input_receivers.iter().any(|receivers| self.chord_pressed(receivers)))
+    }
+
+    /// Returns whether the given action is a member of an action group, but not the active one.
+    fn is_gated(&self, kind: &Keys) -> bool {
+        self.groups
+            .iter()
+            .any(|group| group.contains(kind) && group.active != Some(*kind))
+    }
+
+    /// Return the press state for a binding without applying any resolution rule.
+    pub fn raw_key(&self, kind: &Keys) -> PressState {
+        self.raw_axis(kind).last().unwrap_or(&AxisState::ZERO).press
+    }
+
+    /// Return the axis state for a binding only by evaluating its receivers, without applying any resolution rule.
+    pub fn raw_axis(&self, kind: &Keys) -> Vec<AxisState> {
+        self.live_active_receivers(kind)
+            .map(|receivers| receivers.0.iter().map(|rcv| *self.state(rcv)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the first collection of receivers of a binding where every receiver is pressed.
+    pub fn active_receivers(&self, kind: &Keys) -> Option<&InputReceivers> {
+        if self.delay.frames > 0 {
+            return self.delay.delayed.actions.get(kind)?.receivers.as_ref();
+        }
+        self.live_active_receivers(kind)
+    }
+
+    /// Returns the first collection of receivers of a binding where every receiver is pressed, ignoring the input
+    /// delay (see [`InputView::set_input_delay`]).
+    pub fn live_active_receivers(&self, kind: &Keys) -> Option<&InputReceivers> {
+        let binding = self.bindings.get(kind)?;
+        if !binding.accepts_modifiers(self.modifiers()) || !binding.while_held.iter().all(|held| self.is_held(held)) {
+            return None;
+        }
+        let candidates = binding.input_receivers.iter().filter(|receivers| {
+            !receivers.0.is_empty()
+                && self.accepts_sources(kind, receivers)
+                && self.in_activation_band(kind, receivers)
+                && self.reaches_stage(kind, receivers)
+                && self.within_chord_window(receivers)
+                && self.chord_pressed(receivers)
+                && (self.suppressed.is_empty() && self.socd.is_empty()
+                    || receivers
+                        .0
+                        .iter()
+                        .all(|rcv| !self.suppressed.contains(rcv) && !self.is_socd_masked(rcv)))
+        });
+        let last_writer = |receivers: &&InputReceivers| {
+            receivers
+                .0
+                .iter()
+                .filter_map(|rcv| self.descriptor(rcv).and_then(|dsc| dsc.written_instant))
+                .max()
+        };
+        match self.merge_policy {
+            MergePolicy::LastWriter => candidates.max_by_key(last_writer),
+            MergePolicy::MaxMagnitude => candidates.max_by(|a, b| {
+                let magnitude = |receivers: &&InputReceivers| {
+                    receivers
+                        .0
+                        .iter()
+                        .map(|rcv| self.state(rcv).value.abs())
+                        .fold(0., f32::max)
+                };
+                magnitude(a).total_cmp(&magnitude(b))
+            }),
+            MergePolicy::PreferSource(source) => candidates.max_by_key(|receivers| {
+                let preferred = receivers.0.iter().any(|rcv| self.receiver_source(rcv) == source);
+                (preferred, last_writer(receivers))
+            }),
+        }
+    }
+
+    /// Require the receivers of a chord to be pressed within the given window of each other, treating them as
+    /// simultaneous even if they were pressed in different frames. Removes the window of the chord if none.
+    pub fn set_chord_window(&mut self, receivers: impl Into<InputReceivers>, window: Option<Duration>) {
+        let receivers = receivers.into();
+        match window {
+            Some(window) => self.chord_windows.insert(receivers, window),
+            None => self.chord_windows.remove(&receivers),
+        };
+    }
+
+    /// Returns whether the receivers of a chord were pressed within its chord window.
+    pub fn within_chord_window(&self, receivers: &InputReceivers) -> bool {
+        if receivers.0.len() < 2 {
+            return true;
+        }
+        let window = match self.chord_windows.get(receivers).or(self.default_chord_window.as_ref()) {
+            Some(window) => *window,
+            None => return true,
+        };
+        let held: Vec<Duration> = receivers
+            .0
+            .iter()
+            .filter_map(|rcv| self.state(rcv).press.elapsed())
+            .collect();
+        match (held.iter().min(), held.iter().max()) {
+            (Some(min), Some(max)) => *max - *min <= window,
+            _ => true,
+        }
+    }
+
+    /// Restrict an action to the given input sources (e.g. a keyboard-only quick save), so receivers from the other
+    /// sources don't update it. An empty list removes the filter.
+    pub fn set_source_filter(&mut self, kind: Keys, sources: impl IntoIterator<Item = InputSource>) {
+        let sources: Vec<InputSource> = sources.into_iter().collect();
+        if sources.is_empty() {
+            self.source_filters.remove(&kind);
+        } else {
+            self.source_filters.insert(kind, sources);
+        }
+    }
+
+    /// Returns whether every receiver of the collection is from a source allowed to update the action.
+    pub fn accepts_sources(&self, kind: &Keys, receivers: &InputReceivers) -> bool {
+        match self.source_filters.get(kind) {
+            Some(sources) => receivers.0.iter().all(|rcv| sources.contains(&self.receiver_source(rcv))),
+            None => true,
+        }
+    }
+
+    /// Set the range of axis values activating an action, or remove it if none.
+    pub fn set_activation_band(&mut self, kind: Keys, band: Option<ActivationBand>) {
+        match band {
+            Some(band) => self.activation_bands.insert(kind, band),
+            None => self.activation_bands.remove(&kind),
+        };
+    }
+
+    /// Set the scale and clamping of the axis values of an action, or remove them if none.
+    pub fn set_axis_scale(&mut self, kind: Keys, scale: Option<AxisScale>) {
+        match scale {
+            Some(scale) => self.axis_scales.insert(kind, scale),
+            None => self.axis_scales.remove(&kind),
+        };
+    }
+
+    /// Returns whether every axis receiver of the collection is inside the activation band of the action.
+    pub fn in_activation_band(&self, kind: &Keys, receivers: &InputReceivers) -> bool {
+        match self.activation_bands.get(kind) {
+            Some(band) => receivers
+                .0
+                .iter()
+                .filter(|rcv| rcv.is_axis())
+                .all(|rcv| band.contains(self.state(rcv).value)),
+            None => true,
+        }
+    }
+
+    /// Set the policy used when multiple collections of receivers bound to an action are pressed at the same time.
+    pub fn set_merge_policy(&mut self, policy: MergePolicy) {
+        self.merge_policy = policy;
+    }
+
+    /// Declare a group of actions where at most one member can be pressed at a time. Returns the group index.
+    pub fn add_group(&mut self, members: Vec<Keys>) -> usize {
+        self.groups.push(ActionGroup::new(members));
+        self.groups.len() - 1
+    }
+
+    /// Returns the active member of the given action group.
+    pub fn active_in_group(&self, group: usize) -> Option<Keys> {
+        self.groups.get(group).and_then(|group| group.active)
+    }
+
+    /// Release every pressed receiver from a specific source.
+    pub fn release_source(&mut self, source: InputSource) {
+        let mut released = Vec::new();
+        let receiver_sources = &self.receiver_sources;
+        for descriptor in self.descriptors.iter_mut() {
+            let input_source = receiver_sources.get(&descriptor.input).copied();
+            if input_source.unwrap_or_else(|| descriptor.input.source()) == source && descriptor.axis.press.pressed() {
+                descriptor.set(0., PressState::Released);
+                released.push(descriptor.input);
             }
         }
-        Vec::new()
+        for input in released {
+            self.sync_pressed_receiver(input);
+        }
     }
 
-    /// A utility function for removing all receivers with a specific source.
+    /// Release every pressed receiver, e.g. when the release events are lost because the window lost focus.
+    pub fn release_all(&mut self) {
+        let mut released = Vec::new();
+        for descriptor in self.descriptors.iter_mut() {
+            if descriptor.axis.press.pressed() {
+                descriptor.set(0., PressState::Released);
+                released.push(descriptor.input);
+            }
+        }
+        for input in released {
+            self.sync_pressed_receiver(input);
+        }
+        self.held_modifiers = Modifiers::NONE;
+    }
+
+    /// A utility function for removing all receivers with a specific source. The receivers of that source are
+    /// stripped from the collections mixing sources (e.g. Shift + left click), and the collections left empty are
+    /// removed. Every binding changed this way is reported as a [`BindingChange`].
     pub fn clear_from_specific_source(&mut self, source: InputSource) {
+        let mut changed = Vec::new();
         for binding in self.bindings.values_mut() {
             let mut rcvs_: HashSet<InputReceivers> = HashSet::new();
             for rcvs in binding.input_receivers.iter() {
@@ -205,12 +881,24 @@ where
                     rcvs_.insert(InputReceivers(rcvs));
                 }
             }
-            binding.input_receivers = rcvs_;
+            if rcvs_ != binding.input_receivers {
+                let old = std::mem::replace(&mut binding.input_receivers, rcvs_);
+                changed.push(BindingChange {
+                    action: binding.key,
+                    old: old.into_iter().collect(),
+                    new: binding.input_receivers.iter().cloned().collect(),
+                });
+            }
             binding
                 .default_axis_value
                 .retain(|k, _| k.source() != source);
         }
+        for change in changed {
+            self.reindex(&change.action);
+            self.binding_changes.push(change);
+        }
         self.descriptors.retain(|dsc| dsc.input.source() != source );
+        self.refresh_pressed_receivers();
     }
 
     /// Combine the axis states of all given keys into a [`Vec`].
@@ -231,6 +919,218 @@ where
         output
     }
 
+    /// Returns the keyboard modifiers currently held.
+    pub fn modifiers(&self) -> Modifiers {
+        self.held_modifiers
+    }
+
+    /// Returns whether any receiver bound to an action is currently active.
+    pub fn is_active(&self) -> bool {
+        self.bindings
+            .values()
+            .flat_map(|binding| binding.input_receivers.iter())
+            .flat_map(|receivers| receivers.0.iter())
+            .any(|rcv| self.state(rcv).press.pressed())
+    }
+
+    /// Returns how long since any bound receiver was active.
+    pub fn idle_time(&self) -> Duration {
+        self.idle.idle_time()
+    }
+
+    /// Set the idle duration needed to send an [`InputIdle`] event for this view.
+    pub fn set_idle_threshold(&mut self, threshold: Option<Duration>) {
+        self.idle.threshold = threshold;
+        self.idle.notified = false;
+    }
+
+    /// Returns the progress (0.0 to 1.0) of a held action toward a maximum duration.
+    pub fn charge(&self, kind: &Keys, max: Duration) -> f32 {
+        charge_progress(&self.key(kind), max)
+    }
+
+    /// Set the duration needed to fully charge an action, sending a [`FullyCharged`] event when reached.
+    pub fn set_charge_duration(&mut self, kind: Keys, max: Option<Duration>) {
+        match max {
+            Some(max) => self.hold.charge.insert(kind, max),
+            None => self.hold.charge.remove(&kind),
+        };
+        self.hold.charged.remove(&kind);
+    }
+
+    /// Set the duration an action needs to be held to send a [`LongPress`] event, or disable it if none.
+    pub fn set_long_press_duration(&mut self, kind: Keys, duration: Option<Duration>) {
+        match duration {
+            Some(duration) => self.hold.long_press.insert(kind, duration),
+            None => self.hold.long_press.remove(&kind),
+        };
+        self.hold.long_pressed.remove(&kind);
+    }
+
+    /// Returns the stick position of an action, using the first two receivers of each collection of receivers as
+    /// the X and Y axes. The furthest position is returned when the action has multiple collections of receivers.
+    pub fn stick(&self, kind: &Keys) -> Vec2 {
+        if self.delay.frames > 0 {
+            return self
+                .delay
+                .delayed
+                .actions
+                .get(kind)
+                .map_or(Vec2::ZERO, |delayed| delayed.stick);
+        }
+        self.live_stick(kind)
+    }
+
+    /// Returns the stick position of an action, ignoring the input delay (see [`InputView::set_input_delay`]).
+    pub fn live_stick(&self, kind: &Keys) -> Vec2 {
+        if self.is_gated(kind) {
+            return Vec2::ZERO;
+        }
+        let stick = self
+            .bindings
+            .get(kind)
+            .into_iter()
+            .flat_map(|binding| binding.input_receivers.iter())
+            .filter(|receivers| self.accepts_sources(kind, receivers))
+            .map(|receivers| {
+                let mut values = receivers.0.iter().map(|rcv| self.state(rcv).value);
+                Vec2::new(values.next().unwrap_or(0.), values.next().unwrap_or(0.))
+            })
+            .fold(Vec2::ZERO, |max, stick| {
+                if stick.length() > max.length() {
+                    stick
+                } else {
+                    max
+                }
+            });
+        match (self.activation_bands.get(kind), self.axis_scales.get(kind)) {
+            (Some(band), _) if !band.contains(stick.length()) => Vec2::ZERO,
+            (_, Some(scale)) => scale.apply_vec(stick),
+            _ => stick,
+        }
+    }
+
+    /// Returns the change of the axis value since the last frame for an action, picking the receiver that moved the
+    /// most. Useful for flick-stick aiming and quick-turn detection.
+    pub fn axis_delta(&self, kind: &Keys) -> f32 {
+        if self.is_gated(kind) {
+            return 0.;
+        }
+        self.bindings
+            .get(kind)
+            .into_iter()
+            .flat_map(|binding| binding.input_receivers.iter())
+            .filter(|receivers| self.accepts_sources(kind, receivers))
+            .flat_map(|receivers| receivers.0.iter())
+            .filter_map(|rcv| self.descriptor(rcv).map(|descriptor| descriptor.delta))
+            .fold(0., |max, delta| if delta.abs() > max.abs() { delta } else { max })
+    }
+
+    /// Returns the frame in which the action was pressed, or none if it isn't pressed. Compare it with the
+    /// [`InputFrame`] resource to know if the action was pressed in the current frame.
+    pub fn press_frame(&self, kind: &Keys) -> Option<u64> {
+        if self.delay.frames > 0 {
+            return self.delay.delayed.actions.get(kind)?.press_frame;
+        }
+        self.press_trackers(kind)
+            .filter_map(|tracker| tracker.frame)
+            .max()
+    }
+
+    /// Returns the instant in which the action was pressed, or none if it isn't pressed.
+    pub fn press_instant(&self, kind: &Keys) -> Option<Instant> {
+        if self.delay.frames > 0 {
+            return self.delay.delayed.actions.get(kind)?.press_instant;
+        }
+        self.press_trackers(kind)
+            .filter_map(|tracker| tracker.instant)
+            .max()
+    }
+
+    /// Returns the press trackers of the receivers currently pressing an action.
+    fn press_trackers(&self, kind: &Keys) -> impl Iterator<Item = PressTracker> + '_ {
+        let receivers = match self.live_resolve(kind) {
+            ActionResolution::Active(receivers) => receivers.0.as_slice(),
+            _ => &[],
+        };
+        receivers
+            .iter()
+            .filter_map(|rcv| self.descriptor(rcv).map(|descriptor| descriptor.tracker))
+    }
+
+    /// Returns the elapsed time since the action was last released, or `None` if it is pressed or was never
+    /// released. Useful for coyote time and other "recently let go" mechanics.
+    pub fn released_for(&self, kind: &Keys) -> Option<Duration> {
+        if self.key(kind).pressed() {
+            return None;
+        }
+        self.released.get(kind).map(|instant| instant.elapsed())
+    }
+
+    /// Returns the elapsed time since a specific receiver was last released.
+    pub fn receiver_released_for(&self, input: &InputReceiver) -> Option<Duration> {
+        self.descriptor(input).and_then(|descriptor| descriptor.released_for())
+    }
+
+    /// Returns all actions whose state changed this frame.
+    pub fn changed_actions(&self) -> impl Iterator<Item = &Keys> {
+        self.changed.keys()
+    }
+
+    /// Returns whether the state of the given action changed this frame.
+    pub fn has_changed(&self, kind: &Keys) -> bool {
+        self.changed.contains_key(kind)
+    }
+
+    /// Returns the current snapshot for a specific action.
+    pub fn snapshot(&self, kind: &Keys) -> ActionSnapshot {
+        ActionSnapshot {
+            pressed: self.key(kind).pressed(),
+            value: self.axis(kind).first().map_or(0., |state| state.value),
+        }
+    }
+
+    /// Returns the snapshots of all actions whose state differs from the last stored snapshot.
+    pub fn changed_snapshots(&self) -> Vec<(Keys, ActionSnapshot)> {
+        let unbound = self
+            .snapshots
+            .keys()
+            .filter(|kind| !self.bindings.contains_key(kind));
+        self.bindings
+            .keys()
+            .chain(unbound)
+            .filter_map(|kind| {
+                let snapshot = self.snapshot(kind);
+                let previous = self.snapshots.get(kind).copied().unwrap_or_default();
+                (snapshot != previous).then_some((*kind, snapshot))
+            })
+            .collect()
+    }
+
+    /// Store the given snapshots, replacing the set of actions changed this frame.
+    pub fn apply_snapshots(&mut self, snapshots: Vec<(Keys, ActionSnapshot)>) {
+        self.changed.clear();
+        let now = Instant::now();
+        for (kind, snapshot) in snapshots {
+            self.history.record(
+                kind,
+                ActionTransition {
+                    snapshot,
+                    source: self.last_input_source,
+                    instant: now,
+                },
+            );
+            let previous = self.snapshots.insert(kind, snapshot);
+            let was_pressed = previous.is_some_and(|previous| previous.pressed);
+            if was_pressed && !snapshot.pressed {
+                self.released.insert(kind, now);
+            } else if !was_pressed && snapshot.pressed {
+                self.record_press_edge(kind);
+            }
+            self.changed.insert(kind, ());
+        }
+    }
+
     /// Remove all irrelevant descriptors to be with accordance with the descriptor vector capacity.
     pub fn cleanup(&mut self) {
         self.descriptors.retain(|dsc| dsc.default_axis_value != 0. || dsc.axis.press.pressed());
@@ -0,0 +1,227 @@
+//! An optional set of on-screen widgets (buttons and sticks) built on Bevy UI, which feed virtual receivers into an
+//! input view. Useful for mobile ports, since touches are handled by Bevy UI like clicks.
+use std::marker::PhantomData;
+
+use bevy::input::InputSystem;
+use bevy::math::Rect;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct VirtualGamepadSystem;
+
+/// Colors and sizes used by the virtual gamepad widgets. Replace the resource to theme every widget.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct VirtualGamepadTheme {
+    pub button_color: Color,
+    pub button_pressed_color: Color,
+    pub button_size: f32,
+    pub stick_color: Color,
+    pub stick_pressed_color: Color,
+    pub stick_size: f32,
+}
+
+impl Default for VirtualGamepadTheme {
+    fn default() -> Self {
+        Self {
+            button_color: Color::rgba(1., 1., 1., 0.25),
+            button_pressed_color: Color::rgba(1., 1., 1., 0.5),
+            button_size: 72.,
+            stick_color: Color::rgba(1., 1., 1., 0.15),
+            stick_pressed_color: Color::rgba(1., 1., 1., 0.3),
+            stick_size: 160.,
+        }
+    }
+}
+
+/// A virtual button which presses its receiver in the target input view while clicked or touched.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Copy)]
+pub struct VirtualButton {
+    pub receiver: InputReceiver,
+    pub target: Entity,
+}
+
+/// A virtual stick which moves its receivers in the target input view by dragging from the center of the widget.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Copy)]
+pub struct VirtualStick {
+    pub x: InputReceiver,
+    pub y: InputReceiver,
+    pub target: Entity,
+}
+
+#[derive(Bundle)]
+pub struct VirtualButtonBundle {
+    #[bundle]
+    pub button: ButtonBundle,
+    pub virtual_button: VirtualButton,
+}
+
+impl VirtualButtonBundle {
+    pub fn new(
+        theme: &VirtualGamepadTheme,
+        position: Rect<Val>,
+        receiver: InputReceiver,
+        target: Entity,
+    ) -> Self {
+        Self {
+            button: widget(position, theme.button_size, theme.button_color),
+            virtual_button: VirtualButton { receiver, target },
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct VirtualStickBundle {
+    #[bundle]
+    pub button: ButtonBundle,
+    pub virtual_stick: VirtualStick,
+}
+
+impl VirtualStickBundle {
+    pub fn new(
+        theme: &VirtualGamepadTheme,
+        position: Rect<Val>,
+        x: InputReceiver,
+        y: InputReceiver,
+        target: Entity,
+    ) -> Self {
+        Self {
+            button: widget(position, theme.stick_size, theme.stick_color),
+            virtual_stick: VirtualStick { x, y, target },
+        }
+    }
+}
+
+fn widget(position: Rect<Val>, size: f32, color: Color) -> ButtonBundle {
+    ButtonBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            position,
+            size: Size::new(Val::Px(size), Val::Px(size)),
+            ..Default::default()
+        },
+        color: color.into(),
+        ..Default::default()
+    }
+}
+
+/// A [`Plugin`] that feeds the virtual gamepad widgets into the input views. It isn't added by [`EZInputPlugin`].
+pub struct VirtualGamepadPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    phantom_keys: PhantomData<Keys>,
+}
+
+impl<Keys> Default for VirtualGamepadPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn default() -> Self {
+        Self {
+            phantom_keys: PhantomData,
+        }
+    }
+}
+
+impl<Keys> Plugin for VirtualGamepadPlugin<Keys>
+where
+    Keys: BindingTypeView,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VirtualGamepadTheme>();
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            virtual_gamepad_system::<Keys>
+                .label(VirtualGamepadSystem)
+                .label(EZInputSet::Collect)
+                .after(InputSystem)
+                .before(InputHandlingTickSystem),
+        );
+    }
+}
+
+/// System responsible for applying the state of the virtual widgets to their target input views.
+#[allow(clippy::type_complexity)]
+pub(crate) fn virtual_gamepad_system<Keys>(
+    theme: Res<VirtualGamepadTheme>,
+    windows: Res<Windows>,
+    touches: Res<Touches>,
+    mut buttons: Query<(&Interaction, &VirtualButton, &mut UiColor), Changed<Interaction>>,
+    mut sticks: Query<(
+        &Interaction,
+        &VirtualStick,
+        &Node,
+        &GlobalTransform,
+        &mut UiColor,
+    )>,
+    mut views: Query<&mut InputView<Keys>>,
+) where
+    Keys: BindingTypeView,
+{
+    for (interaction, button, mut color) in buttons.iter_mut() {
+        let mut view = match views.get_mut(button.target) {
+            Ok(view) => view,
+            Err(_) => continue,
+        };
+        let clicked = *interaction == Interaction::Clicked;
+        *color = if clicked {
+            theme.button_pressed_color
+        } else {
+            theme.button_color
+        }
+        .into();
+        view.set_input_source(InputSource::Touch);
+        if clicked {
+            view.set_axis_value(
+                button.receiver,
+                1.,
+                PressState::Pressed {
+                    started_pressing_instant: None,
+                },
+            );
+        } else {
+            view.set_axis_value(button.receiver, 0., PressState::Released);
+        }
+    }
+
+    let pointer = windows
+        .get_primary()
+        .and_then(|window| window.cursor_position())
+        .or_else(|| touches.iter().next().map(|touch| touch.position()));
+    for (interaction, stick, node, transform, mut color) in sticks.iter_mut() {
+        let mut view = match views.get_mut(stick.target) {
+            Ok(view) => view,
+            Err(_) => continue,
+        };
+        let offset = match (interaction, pointer) {
+            (Interaction::Clicked, Some(pointer)) => {
+                let offset = (pointer - transform.translation.truncate()) / (node.size / 2.);
+                Some(offset.clamp_length_max(1.))
+            }
+            _ => None,
+        };
+        if let Some(offset) = offset {
+            if color.0 != theme.stick_pressed_color {
+                *color = theme.stick_pressed_color.into();
+            }
+            view.set_input_source(InputSource::Touch);
+            for (receiver, value) in [(stick.x, offset.x), (stick.y, offset.y)] {
+                let press = view.state(&receiver).press;
+                let press = if press.pressed() {
+                    press
+                } else {
+                    PressState::Pressed {
+                        started_pressing_instant: None,
+                    }
+                };
+                view.set_axis_value(receiver, value, press);
+            }
+        } else if view.state(&stick.x).press.pressed() || view.state(&stick.y).press.pressed() {
+            *color = theme.stick_color.into();
+            view.set_axis_value(stick.x, 0., PressState::Released);
+            view.set_axis_value(stick.y, 0., PressState::Released);
+        }
+    }
+}
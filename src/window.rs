@@ -0,0 +1,133 @@
+//! Mouse routing in multi-window applications. Bevy reports the window of the cursor movements only, so the mouse
+//! buttons, wheel and motion are routed to the focused or hovered window. Add a [`WindowFilter`] to an input view to
+//! only receive the mouse input of its window. Every input view is also released when the application loses focus,
+//! since the release events of the keys held while alt-tabbing never arrive.
+use bevy::prelude::*;
+use bevy::window::{CursorEntered, CursorLeft, CursorMoved, WindowFocused, WindowId};
+
+use crate::prelude::*;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct MouseWindowSystem;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct FocusLossReleaseSystem;
+
+/// Whether every input view is released when the application loses focus. Enabled by default.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ReleaseOnFocusLoss(pub bool);
+
+impl Default for ReleaseOnFocusLoss {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Which window receives the mouse input not tied to a window, such as buttons and wheel.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum WindowRouting {
+    /// The focused window receives the mouse input, as for keyboard input.
+    #[default]
+    Focused,
+    /// The window below the cursor receives the mouse input, even if it isn't focused.
+    Hovered,
+}
+
+/// The focused window and the window below the cursor, if any.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct MouseWindows {
+    pub focused: Option<WindowId>,
+    pub hovered: Option<WindowId>,
+}
+
+impl MouseWindows {
+    /// Returns the window receiving the mouse input with the given routing.
+    pub fn routed(&self, routing: WindowRouting) -> Option<WindowId> {
+        match routing {
+            WindowRouting::Focused => self.focused,
+            WindowRouting::Hovered => self.hovered,
+        }
+    }
+}
+
+/// Restricts the mouse input of the input view of this entity to a single window. Views without a filter receive
+/// the mouse input of every window.
+#[derive(PartialEq, Eq, Debug, Component, Clone, Copy)]
+pub struct WindowFilter {
+    pub window: WindowId,
+    pub routing: WindowRouting,
+}
+
+impl WindowFilter {
+    /// Receive the mouse input of the given window while it is focused.
+    pub fn focused(window: WindowId) -> Self {
+        Self {
+            window,
+            routing: WindowRouting::Focused,
+        }
+    }
+
+    /// Receive the mouse input of the given window while it is hovered.
+    pub fn hovered(window: WindowId) -> Self {
+        Self {
+            window,
+            routing: WindowRouting::Hovered,
+        }
+    }
+
+    /// Returns whether the view receives the mouse input not tied to a window.
+    pub fn accepts(&self, windows: &MouseWindows) -> bool {
+        windows.routed(self.routing) == Some(self.window)
+    }
+}
+
+/// System responsible for tracking the focused and hovered windows.
+pub(crate) fn mouse_window_system(
+    mut windows: ResMut<MouseWindows>,
+    mut focused_rd: EventReader<WindowFocused>,
+    mut entered_rd: EventReader<CursorEntered>,
+    mut moved_rd: EventReader<CursorMoved>,
+    mut left_rd: EventReader<CursorLeft>,
+) {
+    let mut current = *windows;
+    for ev in focused_rd.iter() {
+        if ev.focused {
+            current.focused = Some(ev.id);
+        } else if current.focused == Some(ev.id) {
+            current.focused = None;
+        }
+    }
+    // The cursor may already be inside a window when the app starts, so moving inside it also marks it hovered.
+    let entered = entered_rd.iter().fold(None, |_, ev| Some(ev.id));
+    if let Some(id) = moved_rd.iter().fold(None, |_, ev| Some(ev.id)).or(entered) {
+        current.hovered = Some(id);
+    }
+    for ev in left_rd.iter() {
+        if current.hovered == Some(ev.id) {
+            current.hovered = None;
+        }
+    }
+    if current != *windows {
+        *windows = current;
+    }
+}
+
+/// System responsible for releasing every input view when the application loses focus. Moving the focus between
+/// windows of the application doesn't release anything.
+pub(crate) fn focus_loss_release_system<Keys>(
+    mut query: Query<&mut InputView<Keys>>,
+    mut rd: EventReader<WindowFocused>,
+    release: Option<Res<ReleaseOnFocusLoss>>,
+) where
+    Keys: BindingTypeView,
+{
+    let lost = rd.iter().fold(None, |_, ev| Some(ev.focused)) == Some(false);
+    if !lost || release.is_some_and(|release| !release.0) {
+        return;
+    }
+    for mut view in query.iter_mut() {
+        if view.descriptors.iter().any(|descriptor| descriptor.axis.press.pressed()) {
+            view.release_all();
+        }
+    }
+}